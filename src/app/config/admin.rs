@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+fn default_listen() -> String {
+    "127.0.0.1:9185".to_string()
+}
+
+/// Local HTTP/JSON admin API for runtime control.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct AdminConfig {
+    #[serde(rename = "enabled", alias = "admin_api", default)]
+    pub enabled: bool,
+    /// Address the admin API binds to; keep it on loopback.
+    #[serde(rename = "listen", alias = "listen_addr", alias = "bind", default = "default_listen")]
+    pub listen: String,
+    /// Bearer token every request must carry (`Authorization: Bearer <token>`).
+    /// Required when the API is enabled.
+    #[serde(rename = "auth_token", alias = "token", default)]
+    pub auth_token: String,
+}
+
+impl Default for AdminConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_listen(),
+            auth_token: String::new(),
+        }
+    }
+}
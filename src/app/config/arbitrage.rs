@@ -1,21 +1,69 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct StrategyConfig {
     #[serde(rename = "instruments", alias = "base_tokens", alias = "mother_token")]
     pub base_tokens: Vec<BaseTokenConfig>,
-    #[serde(rename = "nonce_account_pubkey", alias = "nonce_account", alias = "nonce_addr")]
+    /// Primary durable nonce account; required unless `transaction_lifetime = "blockhash"`.
+    #[serde(rename = "nonce_account_pubkey", alias = "nonce_account", alias = "nonce_addr", default)]
     pub nonce_account: String,
+    /// More durable nonce accounts for concurrent submissions; every in-flight transaction
+    /// leases one account of the pool (`nonce_account_pubkey` first).
+    #[serde(rename = "nonce_account_pubkeys", alias = "nonce_accounts", alias = "nonce_pool", default)]
+    pub nonce_pool: Vec<String>,
+    /// A lease whose transaction neither landed nor advanced the nonce ends after this long.
+    #[serde(rename = "nonce_lease_timeout_secs", alias = "nonce_lease_secs", default = "default_nonce_lease_secs")]
+    pub nonce_lease_secs: u64,
+    /// What keeps a transaction valid until it lands, see [`TxLifetimeMode`].
+    #[serde(rename = "transaction_lifetime", alias = "tx_lifetime", default)]
+    pub tx_lifetime: TxLifetimeMode,
+    /// How often the recent blockhash is refetched with `transaction_lifetime = "blockhash"`.
+    #[serde(rename = "blockhash_refresh_ms", alias = "blockhash_interval_ms", default = "default_blockhash_refresh_ms")]
+    pub blockhash_refresh_ms: u64,
+    /// A blockhash fetched longer ago than this is not signed with (the refresher is
+    /// failing); it leaves the rest of its ~60 s validity for the transaction to land.
+    #[serde(rename = "blockhash_max_age_ms", alias = "max_blockhash_age_ms", default = "default_blockhash_max_age_ms")]
+    pub blockhash_max_age_ms: u64,
     #[serde(rename = "default_quote_mint", alias = "quote_mint", alias = "target_token", default)]
     pub quote_mint: Option<String>,
     #[serde(rename = "execution_enabled", alias = "live_trading", alias = "submit_transactions", default = "default_live_trading")]
     pub live_trading: bool,
     #[serde(rename = "geyser_watch_enabled", alias = "watch_flows", alias = "enable_big_trades_monitor", default = "default_watch_flows")]
     pub watch_flows: bool,
+    /// Stream the `[pools]` accounts over Yellowstone and re-evaluate affected instruments on every change.
+    #[serde(rename = "pool_watch_enabled", alias = "watch_pools", default)]
+    pub watch_pools: bool,
     #[serde(rename = "polling_enabled", alias = "poll_quotes", alias = "enable_continuous_polling", default = "default_poll_quotes")]
     pub poll_quotes: bool,
     #[serde(rename = "poll_interval_ms", alias = "polling_interval_ms", default = "default_poll_interval_ms")]
     pub poll_interval_ms: u64,
+    /// Quote provider used by instruments that do not set their own.
+    #[serde(rename = "quote_provider", alias = "default_quote_provider", default = "default_quote_provider")]
+    pub quote_provider: String,
+    /// Longest cycle (in swap legs) the polling loop searches; 2 = base -> target -> base only.
+    #[serde(rename = "max_cycle_legs", alias = "cycle_length", default = "default_max_cycle_legs")]
+    pub max_cycle_legs: usize,
+    /// Intermediate tokens for cycles of 3+ legs; empty = `INTEMEDIATE_TOKEN`.
+    #[serde(rename = "cycle_tokens", alias = "intermediate_tokens", default)]
+    pub cycle_tokens: Vec<String>,
+    /// Cap on 3+ leg cycles quoted per instrument per poll.
+    #[serde(rename = "max_cycles_per_poll", alias = "max_cycles", default = "default_max_cycles_per_poll")]
+    pub max_cycles_per_poll: usize,
+}
+
+/// How submitted transactions stay valid until they land.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TxLifetimeMode {
+    /// Sign with a leased durable nonce account and advance it first; the transaction
+    /// stays valid until the nonce moves on.
+    #[default]
+    #[serde(alias = "durable_nonce")]
+    Nonce,
+    /// Sign with a recent blockhash kept fresh in the background; the transaction expires
+    /// once the chain passes the blockhash's last valid block height.
+    #[serde(alias = "recent_blockhash")]
+    Blockhash,
 }
 
 fn default_live_trading() -> bool {
@@ -30,8 +78,29 @@ fn default_poll_quotes() -> bool {
 fn default_poll_interval_ms() -> u64 {
     500
 }
+fn default_nonce_lease_secs() -> u64 {
+    60
+}
+fn default_blockhash_refresh_ms() -> u64 {
+    400
+}
+fn default_blockhash_max_age_ms() -> u64 {
+    30_000
+}
+fn default_max_cycle_legs() -> usize {
+    2
+}
+fn default_max_cycles_per_poll() -> usize {
+    16
+}
+fn default_instrument_enabled() -> bool {
+    true
+}
+fn default_quote_provider() -> String {
+    super::DEFAULT_QUOTE_PROVIDER.to_string()
+}
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct BaseTokenConfig {
     #[serde(rename = "token_mint", alias = "mint", alias = "token_addr")]
     pub mint: String,
@@ -43,4 +112,13 @@ pub struct BaseTokenConfig {
     pub amount_range: [f64; 2],
     #[serde(rename = "grid_steps", alias = "steps", alias = "input_amount_steps")]
     pub steps: u64,
+    /// Overrides `[sizing] max_quotes` for this instrument.
+    #[serde(rename = "quote_budget", alias = "max_quotes", default)]
+    pub quote_budget: Option<usize>,
+    /// Overrides `[strategy] quote_provider` for this instrument.
+    #[serde(rename = "quote_provider", alias = "provider", default)]
+    pub quote_provider: Option<String>,
+    /// Disabled instruments are neither polled nor watched.
+    #[serde(rename = "enabled", alias = "active", default = "default_instrument_enabled")]
+    pub enabled: bool,
 }
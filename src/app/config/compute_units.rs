@@ -0,0 +1,38 @@
+use serde::Deserialize;
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_margin_bps() -> u64 {
+    1000
+}
+
+fn default_cache_ttl_secs() -> u64 {
+    300
+}
+
+/// Compute unit limit sized from simulation instead of the fixed `[fees] compute_unit_limit`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ComputeUnitsConfig {
+    /// Simulate each assembled transaction on a route without a learned limit and request
+    /// `unitsConsumed` plus `margin_bps`; `[fees] compute_unit_limit` stays the upper bound.
+    #[serde(rename = "enabled", alias = "right_size", default = "default_false")]
+    pub enabled: bool,
+    /// Headroom over the simulated units, in basis points.
+    #[serde(rename = "margin_bps", alias = "margin", default = "default_margin_bps")]
+    pub margin_bps: u64,
+    /// How long a learned limit is reused for the same route before simulating again.
+    #[serde(default = "default_cache_ttl_secs")]
+    pub cache_ttl_secs: u64,
+}
+
+impl Default for ComputeUnitsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            margin_bps: default_margin_bps(),
+            cache_ttl_secs: default_cache_ttl_secs(),
+        }
+    }
+}
@@ -1,12 +1,13 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct NodeConfig {
     #[serde(rename = "signer_keypair_path", alias = "keypair_path", alias = "wallet_path")]
     pub keypair_path: String,
     #[serde(rename = "rpc_endpoint", alias = "rpc_url")]
     pub rpc_url: String,
-    #[serde(rename = "submit_endpoint", alias = "submit_url")]
+    /// Where transactions are sent; optional when `[submit] endpoints` lists them.
+    #[serde(rename = "submit_endpoint", alias = "submit_url", default)]
     pub submit_url: String,
     #[serde(default)]
     pub laser_token: String,
@@ -16,7 +17,7 @@ pub struct NodeConfig {
     pub geyser_token: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct SwapApiConfig {
     #[serde(rename = "endpoint", alias = "base_url", alias = "jupiter_endpoint")]
     pub jupiter_endpoint: String,
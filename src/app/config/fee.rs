@@ -1,17 +1,33 @@
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct TxCostConfig {
     #[serde(rename = "compute_unit_limit", alias = "compute_units", alias = "cu")]
     pub compute_units: u64,
     #[serde(rename = "priority_fee_lamports", alias = "priority_lamports", alias = "priority_fee_micro_lamport")]
     pub priority_lamports: u64,
+    /// Tip paid by trades sent to the submit endpoints (bundles pay `[jito]` tips instead).
     #[serde(rename = "relay_tip_sol", alias = "tip_sol", alias = "third_party_fee")]
     pub tip_sol: f64,
+    /// Account receiving `relay_tip_sol`.
+    #[serde(rename = "relay_tip_account", alias = "tip_account", default = "default_relay_tip_account")]
+    pub relay_tip_account: String,
     #[serde(rename = "sol_price_usd", alias = "sol_usd", alias = "sol_price_usdc", default = "default_sol_usd")]
     pub sol_usd: f64,
+    /// How long a SOL price in a base token (used to convert lamport costs) stays fresh.
+    #[serde(default = "default_price_ttl_secs")]
+    pub price_ttl_secs: u64,
 }
 
 fn default_sol_usd() -> f64 {
     150.0
 }
+
+/// One of Jito's mainnet tip accounts.
+fn default_relay_tip_account() -> String {
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5".to_string()
+}
+
+fn default_price_ttl_secs() -> u64 {
+    60
+}
@@ -8,7 +8,7 @@ fn default_program_id() -> String {
     "jupgfSgfuAXv4B6R2Uxu85Z1qdzgju79s6MfZekN6XS".to_string()
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
 pub struct FlashLoanConfig {
     #[serde(default = "default_false")]
     pub enabled: bool,
@@ -19,9 +19,12 @@ pub struct FlashLoanConfig {
     /// Per-token reserve configuration for flash loans.
     #[serde(default)]
     pub reserves: Vec<FlashLoanReserveConfig>,
+    /// Fee charged on the borrowed amount, in basis points.
+    #[serde(default)]
+    pub fee_bps: u16,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct FlashLoanReserveConfig {
     /// The SPL token mint (e.g. WSOL mint).
     pub token_mint: String,
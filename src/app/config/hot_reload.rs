@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_check_interval_ms() -> u64 {
+    2000
+}
+
+/// Watching the config file for changes while the bot runs.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct HotReloadConfig {
+    /// Re-read the config file when it changes and apply `[strategy]`, `[fees]` and
+    /// `[flash_loan]`; other sections still need a restart.
+    #[serde(rename = "enabled", alias = "watch", default = "default_enabled")]
+    pub enabled: bool,
+    /// How often the file's modification time is checked.
+    #[serde(rename = "check_interval_ms", alias = "interval_ms", default = "default_check_interval_ms")]
+    pub check_interval_ms: u64,
+}
+
+impl Default for HotReloadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            check_interval_ms: default_check_interval_ms(),
+        }
+    }
+}
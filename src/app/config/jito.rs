@@ -0,0 +1,88 @@
+use serde::Deserialize;
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_block_engine_url() -> String {
+    "https://mainnet.block-engine.jito.wtf".to_string()
+}
+
+/// Mainnet tip accounts published by Jito (`getTipAccounts`).
+fn default_tip_accounts() -> Vec<String> {
+    [
+        "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+        "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+        "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+        "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+        "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+        "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+        "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+        "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect()
+}
+
+fn default_tip_profit_share() -> f64 {
+    0.5
+}
+
+fn default_min_tip_lamports() -> u64 {
+    1_000
+}
+
+fn default_max_tip_lamports() -> u64 {
+    10_000_000
+}
+
+fn default_status_poll_interval_ms() -> u64 {
+    1000
+}
+
+fn default_status_timeout_secs() -> u64 {
+    30
+}
+
+/// Submission as a Jito bundle (`sendBundle`) with a tip instead of `sendTransaction`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct JitoConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// Block engine base URL; bundles go to `<url>/api/v1/bundles`.
+    #[serde(rename = "block_engine_url", alias = "block_engine", alias = "url", default = "default_block_engine_url")]
+    pub block_engine_url: String,
+    /// Tip accounts, used in turn.
+    #[serde(default = "default_tip_accounts")]
+    pub tip_accounts: Vec<String>,
+    /// Share of a trade's expected profit, after every other cost, paid as the tip.
+    #[serde(rename = "tip_profit_share", alias = "tip_share", default = "default_tip_profit_share")]
+    pub tip_profit_share: f64,
+    #[serde(rename = "min_tip_lamports", alias = "min_tip", default = "default_min_tip_lamports")]
+    pub min_tip_lamports: u64,
+    /// Upper bound on the tip; 0 = no bound.
+    #[serde(rename = "max_tip_lamports", alias = "max_tip", default = "default_max_tip_lamports")]
+    pub max_tip_lamports: u64,
+    /// Interval between `getInflightBundleStatuses` polls of a sent bundle.
+    #[serde(default = "default_status_poll_interval_ms")]
+    pub status_poll_interval_ms: u64,
+    /// Stop polling a bundle that has neither landed nor failed after this long.
+    #[serde(default = "default_status_timeout_secs")]
+    pub status_timeout_secs: u64,
+}
+
+impl Default for JitoConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            block_engine_url: default_block_engine_url(),
+            tip_accounts: default_tip_accounts(),
+            tip_profit_share: default_tip_profit_share(),
+            min_tip_lamports: default_min_tip_lamports(),
+            max_tip_lamports: default_max_tip_lamports(),
+            status_poll_interval_ms: default_status_poll_interval_ms(),
+            status_timeout_secs: default_status_timeout_secs(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_ledger_path() -> String {
+    "landings.jsonl".to_string()
+}
+
+fn default_poll_interval_ms() -> u64 {
+    400
+}
+
+fn default_timeout_secs() -> u64 {
+    90
+}
+
+/// Confirmation tracking for submitted transactions.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct LandingConfig {
+    /// Track every submitted signature until it is finalized, fails or expires.
+    #[serde(rename = "enabled", alias = "track_landings", default = "default_true")]
+    pub enabled: bool,
+    /// Append-only JSON-lines ledger with one record per submitted transaction.
+    #[serde(rename = "ledger_path", alias = "ledger_file", default = "default_ledger_path")]
+    pub ledger_path: String,
+    /// Interval between `getSignatureStatuses` polls.
+    #[serde(rename = "poll_interval_ms", alias = "status_poll_ms", default = "default_poll_interval_ms")]
+    pub poll_interval_ms: u64,
+    /// Give up and record the transaction as expired after this long.
+    #[serde(rename = "timeout_secs", alias = "expiry_secs", default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for LandingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_true(),
+            ledger_path: default_ledger_path(),
+            poll_interval_ms: default_poll_interval_ms(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+fn default_ledger_path() -> String {
+    "ledger.sqlite".to_string()
+}
+
+/// Durable SQLite record of opportunities, quotes, build attempts, submissions and
+/// confirmations, keyed by opportunity ID.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct LedgerConfig {
+    #[serde(rename = "enabled", alias = "sqlite_ledger", default)]
+    pub enabled: bool,
+    /// SQLite database file, created on first use (`:memory:` keeps it in memory).
+    #[serde(rename = "path", alias = "database_path", alias = "db_path", default = "default_ledger_path")]
+    pub path: String,
+}
+
+impl Default for LedgerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_ledger_path(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use serde::Deserialize;
+
+fn default_listen() -> String {
+    "127.0.0.1:9184".to_string()
+}
+
+fn default_jupiter_probe_interval_secs() -> u64 {
+    30
+}
+
+/// Prometheus `/metrics` endpoint.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct MetricsConfig {
+    #[serde(rename = "enabled", alias = "prometheus", default)]
+    pub enabled: bool,
+    /// Address the `/metrics` HTTP endpoint binds to.
+    #[serde(rename = "listen", alias = "listen_addr", alias = "bind", default = "default_listen")]
+    pub listen: String,
+    /// Time one Jupiter quote round trip and swap build this often for the latency
+    /// histogram; 0 = once at startup only.
+    #[serde(rename = "jupiter_probe_interval_secs", alias = "latency_probe_secs", default = "default_jupiter_probe_interval_secs")]
+    pub jupiter_probe_interval_secs: u64,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen: default_listen(),
+            jupiter_probe_interval_secs: default_jupiter_probe_interval_secs(),
+        }
+    }
+}
@@ -1,18 +1,50 @@
 use once_cell::sync::Lazy;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 
+pub mod admin;
 pub mod arbitrage;
+pub mod compute_units;
 pub mod credential;
 pub mod fee;
 pub mod flash_loan;
+pub mod hot_reload;
+pub mod jito;
+pub mod landing;
+pub mod ledger;
+pub mod metrics;
+pub mod pools;
+pub mod priority_fee;
+pub mod quote_provider;
+pub mod simulation;
+pub mod sizing;
+pub mod submit;
+pub mod tokens;
+pub mod validate;
 
+pub use admin::*;
 pub use arbitrage::*;
+pub use compute_units::*;
 pub use credential::*;
 pub use fee::*;
 pub use flash_loan::*;
+pub use hot_reload::*;
+pub use jito::*;
+pub use landing::*;
+pub use ledger::*;
+pub use metrics::*;
+pub use pools::*;
+pub use priority_fee::*;
+pub use quote_provider::*;
+pub use simulation::*;
+pub use sizing::*;
+pub use submit::*;
+pub use tokens::*;
+pub use validate::*;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct Config {
     #[serde(rename = "connection", alias = "node", alias = "credential")]
     pub node: NodeConfig,
@@ -24,14 +56,69 @@ pub struct Config {
     pub tx_cost: TxCostConfig,
     #[serde(default)]
     pub flash_loan: FlashLoanConfig,
+    #[serde(default)]
+    pub priority_fee: PriorityFeeConfig,
+    #[serde(default)]
+    pub compute_units: ComputeUnitsConfig,
+    #[serde(default)]
+    pub submit: SubmitConfig,
+    #[serde(default)]
+    pub jito: JitoConfig,
+    #[serde(default)]
+    pub simulation: SimulationConfig,
+    #[serde(default)]
+    pub landing: LandingConfig,
+    #[serde(default)]
+    pub ledger: LedgerConfig,
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    #[serde(default)]
+    pub admin: AdminConfig,
+    #[serde(default)]
+    pub hot_reload: HotReloadConfig,
+    #[serde(default)]
+    pub sizing: SizingConfig,
+    #[serde(default)]
+    pub pools: PoolsConfig,
+    #[serde(default)]
+    pub tokens: TokensConfig,
+    /// Extra named quote providers, selectable per instrument.
+    #[serde(default)]
+    pub quote_providers: HashMap<String, QuoteProviderConfig>,
+}
+
+impl Config {
+    /// Parse a config from TOML text.
+    pub fn from_toml_str(content: &str) -> Result<Self, anyhow::Error> {
+        toml::from_str(content).map_err(|e| anyhow::anyhow!("Failed to parse config: {}", e))
+    }
+
+    /// Read and parse the config file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+        Self::from_toml_str(&content)
+    }
+
+    /// `settings.toml` if it exists in the current directory, else `Config.toml`.
+    pub fn default_path() -> &'static str {
+        if Path::new("settings.toml").exists() {
+            "settings.toml"
+        } else {
+            "Config.toml"
+        }
+    }
+
+    /// Load `settings.toml` from the current directory, falling back to `Config.toml`.
+    pub fn load_default() -> Result<Self, anyhow::Error> {
+        Self::load(Self::default_path())
+    }
 }
 
-pub static CONFIG: Lazy<Config> = Lazy::new(|| {
-    let path = "settings.toml";
-    let content = fs::read_to_string(path).unwrap_or_else(|_| {
-        fs::read_to_string("Config.toml").unwrap_or_else(|e| {
-            panic!("Failed to read settings.toml or Config.toml: {}", e)
-        })
-    });
-    toml::from_str(&content).expect("Failed to parse config")
-});
+/// Process-wide config loaded from the current directory ([`Config::default_path`];
+/// the binary's `--config` does not apply).
+/// Convenience layer for binaries; library code takes an [`crate::Engine`] instead.
+/// This is the config as first loaded: hot reloads update [`crate::Engine::config`] only.
+pub static CONFIG: Lazy<Config> =
+    Lazy::new(|| Config::load_default().unwrap_or_else(|e| panic!("{}", e)));
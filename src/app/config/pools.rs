@@ -0,0 +1,56 @@
+use serde::Deserialize;
+
+fn default_prefilter_margin_bps() -> f64 {
+    10.0
+}
+
+fn default_refresh_interval_ms() -> u64 {
+    1000
+}
+
+fn default_pump_swap_fee_bps() -> u64 {
+    25
+}
+
+fn default_watch_debounce_ms() -> u64 {
+    25
+}
+
+/// Local AMM pool math used to prefilter candidates before quoting them on Jupiter.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PoolsConfig {
+    #[serde(rename = "enabled", alias = "local_pools", default)]
+    pub enabled: bool,
+    /// Pool state accounts to decode (Raydium AMM v4, Raydium CP, Whirlpool,
+    /// Meteora DLMM or PumpSwap); the venue is detected from the account owner.
+    #[serde(rename = "addresses", alias = "pools", alias = "pool_addresses", default)]
+    pub addresses: Vec<String>,
+    /// A cycle fully covered by local pools is only quoted on Jupiter if its best local
+    /// round trip loses less than this many basis points (Jupiter may route better).
+    #[serde(rename = "prefilter_margin_bps", alias = "margin_bps", default = "default_prefilter_margin_bps")]
+    pub prefilter_margin_bps: f64,
+    /// Re-read all pool accounts over RPC this often; 0 = startup only, relying on the
+    /// Yellowstone account subscription to keep them fresh.
+    #[serde(rename = "refresh_interval_ms", alias = "rpc_refresh_ms", default = "default_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+    /// PumpSwap fee (LP + protocol) applied to the input; the pool account does not store it.
+    #[serde(rename = "pump_swap_fee_bps", alias = "pumpswap_fee_bps", default = "default_pump_swap_fee_bps")]
+    pub pump_swap_fee_bps: u64,
+    /// Pool watcher mode: wait this long after a pool change before re-evaluating, so the
+    /// vault updates of one transaction are handled together.
+    #[serde(rename = "watch_debounce_ms", alias = "debounce_ms", default = "default_watch_debounce_ms")]
+    pub watch_debounce_ms: u64,
+}
+
+impl Default for PoolsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            addresses: Vec::new(),
+            prefilter_margin_bps: default_prefilter_margin_bps(),
+            refresh_interval_ms: default_refresh_interval_ms(),
+            pump_swap_fee_bps: default_pump_swap_fee_bps(),
+            watch_debounce_ms: default_watch_debounce_ms(),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_percentile() -> u8 {
+    75
+}
+
+fn default_refresh_interval_ms() -> u64 {
+    2000
+}
+
+fn default_max_micro_lamports() -> u64 {
+    1_000_000
+}
+
+fn default_max_profit_share() -> f64 {
+    0.5
+}
+
+/// Compute unit price estimated from `getRecentPrioritizationFees` instead of the fixed
+/// `[fees] priority_fee_lamports`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct PriorityFeeConfig {
+    #[serde(default = "default_false")]
+    pub enabled: bool,
+    /// Percentile (0-100) of the recent per-slot fees to pay.
+    #[serde(rename = "percentile", alias = "fee_percentile", default = "default_percentile")]
+    pub percentile: u8,
+    /// Interval between `getRecentPrioritizationFees` samples.
+    #[serde(default = "default_refresh_interval_ms")]
+    pub refresh_interval_ms: u64,
+    /// Upper bound on the estimated price, in micro-lamports per compute unit.
+    #[serde(rename = "max_micro_lamports", alias = "max_price", default = "default_max_micro_lamports")]
+    pub max_micro_lamports: u64,
+    /// Largest share of a trade's expected profit (after every other cost) the priority fee may take.
+    #[serde(default = "default_max_profit_share")]
+    pub max_profit_share: f64,
+    /// Extra writable accounts to sample besides the pools and accounts seen in our routes.
+    #[serde(default)]
+    pub accounts: Vec<String>,
+}
+
+impl Default for PriorityFeeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            percentile: default_percentile(),
+            refresh_interval_ms: default_refresh_interval_ms(),
+            max_micro_lamports: default_max_micro_lamports(),
+            max_profit_share: default_max_profit_share(),
+            accounts: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+/// Name of the built-in provider backed by `[dex_api] endpoint`.
+pub const DEFAULT_QUOTE_PROVIDER: &str = "jupiter";
+
+/// One named entry under `[quote_providers.<name>]`.
+///
+/// Instruments pick a provider by name (`quote_provider = "<name>"`); the
+/// built-in `"jupiter"` provider always exists and uses `[dex_api] endpoint`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum QuoteProviderConfig {
+    /// A Jupiter swap API compatible endpoint (hosted or self-hosted).
+    Jupiter {
+        #[serde(alias = "base_url")]
+        endpoint: String,
+    },
+}
@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+fn default_false() -> bool {
+    false
+}
+
+fn default_log_path() -> String {
+    "simulations.txt".to_string()
+}
+
+/// Pre-submit `simulateTransaction` gate.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SimulationConfig {
+    /// Simulate every fully built transaction before it is sent and skip
+    /// submission when it fails or the realized profit is below the
+    /// instrument's `min_profit_quote_units`.
+    #[serde(rename = "enabled", alias = "simulate_before_submit", default = "default_false")]
+    pub enabled: bool,
+    /// File the simulation outcome, error and program logs are appended to.
+    #[serde(rename = "log_path", alias = "log_file", default = "default_log_path")]
+    pub log_path: String,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_false(),
+            log_path: default_log_path(),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+/// How `simulate_amount_in` picks input amounts within `notional_range`.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SizingMode {
+    /// Quote every point of a geometric grid of `grid_steps` amounts.
+    #[default]
+    Grid,
+    /// Coarse geometric grid of `grid_steps` (at least 3) points, then golden-section
+    /// search around the best one until the quote budget or tolerance is reached.
+    GoldenSection,
+}
+
+fn default_max_quotes() -> usize {
+    12
+}
+
+fn default_tolerance() -> f64 {
+    0.01
+}
+
+/// Trade-size search settings.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SizingConfig {
+    #[serde(rename = "mode", alias = "sizing_mode", default)]
+    pub mode: SizingMode,
+    /// Quotes per instrument per poll in `golden_section` mode, shared across targets
+    /// and cycles. Instruments may override it with `quote_budget`.
+    #[serde(rename = "max_quotes", alias = "quote_budget", default = "default_max_quotes")]
+    pub max_quotes: usize,
+    /// Stop refining once the search bracket is narrower than this fraction of the amount.
+    #[serde(rename = "tolerance", default = "default_tolerance")]
+    pub tolerance: f64,
+}
+
+impl Default for SizingConfig {
+    fn default() -> Self {
+        Self {
+            mode: SizingMode::default(),
+            max_quotes: default_max_quotes(),
+            tolerance: default_tolerance(),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// One endpoint signed transactions are sent to.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct SubmitEndpointConfig {
+    /// Label of the endpoint in logs and metrics.
+    pub name: String,
+    /// JSON-RPC URL accepting `sendTransaction`.
+    #[serde(rename = "url", alias = "endpoint")]
+    pub url: String,
+    /// Share of transactions the endpoint receives when `fanout` limits the copies; 0
+    /// disables it without removing it from the file.
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// Broadcast of each signed transaction to several endpoints.
+#[derive(Debug, Deserialize, Clone, PartialEq, Default)]
+pub struct SubmitConfig {
+    /// Endpoints every transaction is sent to in parallel; empty = `[connection] submit_endpoint` only.
+    #[serde(rename = "endpoints", alias = "submit_endpoints", default)]
+    pub endpoints: Vec<SubmitEndpointConfig>,
+    /// Send each transaction to this many endpoints, picked by weight; 0 = every enabled endpoint.
+    #[serde(rename = "fanout", alias = "max_fanout", default)]
+    pub fanout: usize,
+}
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Token metadata resolution. Decimals and the token program always come from the
+/// on-chain mint account; this section only controls caching and display names.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct TokensConfig {
+    /// JSON file persisting resolved mints across restarts; empty keeps them in memory only.
+    #[serde(rename = "cache_path", alias = "cache_file", default)]
+    pub cache_path: String,
+    /// Display symbol per mint, taking precedence over the built-in list.
+    #[serde(rename = "symbols", alias = "symbol_overrides", default)]
+    pub symbols: HashMap<String, String>,
+}
+
+impl Default for TokensConfig {
+    fn default() -> Self {
+        Self {
+            cache_path: String::new(),
+            symbols: HashMap::new(),
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//! Startup validation of a parsed [`Config`].
+//!
+//! Parsing only checks types; [`Config::validate`] checks values the pipeline would
+//! otherwise trip over late (a panic in `Pubkey::from_str_const`, an empty amount grid,
+//! a flash-loan reserve that is never used) and reports every problem at once, each with its TOML path.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::str::FromStr;
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::{Config, TxLifetimeMode, DEFAULT_QUOTE_PROVIDER};
+
+/// One invalid setting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// TOML path of the setting, e.g. `strategy.instruments[0].notional_range`.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Collects problems while walking the config.
+#[derive(Default)]
+struct Problems(Vec<ConfigProblem>);
+
+impl Problems {
+    fn push(&mut self, path: impl Into<String>, message: impl Into<String>) {
+        self.0.push(ConfigProblem {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    fn pubkey(&mut self, path: impl Into<String>, value: &str) {
+        if let Err(e) = Pubkey::from_str(value) {
+            self.push(path, format!("{:?} is not a valid public key ({})", value, e));
+        }
+    }
+
+    fn listen(&mut self, path: impl Into<String>, value: &str) {
+        if SocketAddr::from_str(value).is_err() {
+            self.push(path, format!("{:?} is not a host:port socket address", value));
+        }
+    }
+}
+
+impl Config {
+    /// Every invalid setting, in file order; empty if the config is usable.
+    pub fn validate(&self) -> Vec<ConfigProblem> {
+        let mut p = Problems::default();
+
+        let node = &self.node;
+        let submit = &self.submit;
+        let mut urls = vec![("connection.rpc_endpoint".to_string(), &node.rpc_url)];
+        if submit.endpoints.is_empty() || !node.submit_url.is_empty() {
+            urls.push(("connection.submit_endpoint".to_string(), &node.submit_url));
+        }
+        for (i, endpoint) in submit.endpoints.iter().enumerate() {
+            urls.push((format!("submit.endpoints[{}].url", i), &endpoint.url));
+        }
+        for (path, url) in urls {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                p.push(path, format!("{:?} is not an http(s) URL", url));
+            }
+        }
+        for (i, endpoint) in submit.endpoints.iter().enumerate() {
+            let path = format!("submit.endpoints[{}].name", i);
+            if endpoint.name.trim().is_empty() {
+                p.push(path, "must not be empty");
+            } else if submit.endpoints[..i].iter().any(|e| e.name == endpoint.name) {
+                p.push(path, format!("{:?} is used twice", endpoint.name));
+            }
+        }
+        if !submit.endpoints.is_empty() && submit.endpoints.iter().all(|e| e.weight == 0) {
+            p.push("submit.endpoints", "at least one endpoint needs a weight above 0");
+        }
+        let strategy = &self.strategy;
+        if strategy.watch_flows || strategy.watch_pools {
+            let setting = if strategy.watch_flows {
+                "geyser_watch_enabled"
+            } else {
+                "pool_watch_enabled"
+            };
+            if node.geyser_url.as_deref().unwrap_or_default().is_empty() {
+                p.push("connection.geyser_endpoint", format!("required when strategy.{} = true", setting));
+            }
+            if node.geyser_token.as_deref().unwrap_or_default().is_empty() {
+                p.push("connection.geyser_auth_token", format!("required when strategy.{} = true", setting));
+            }
+        }
+
+        match strategy.tx_lifetime {
+            TxLifetimeMode::Nonce => {
+                p.pubkey("strategy.nonce_account_pubkey", &strategy.nonce_account);
+                for (i, account) in strategy.nonce_pool.iter().enumerate() {
+                    p.pubkey(format!("strategy.nonce_account_pubkeys[{}]", i), account);
+                    if *account == strategy.nonce_account || strategy.nonce_pool[..i].contains(account) {
+                        p.push(format!("strategy.nonce_account_pubkeys[{}]", i), format!("{} is listed twice", account));
+                    }
+                }
+                if strategy.nonce_lease_secs == 0 {
+                    p.push("strategy.nonce_lease_timeout_secs", "must be at least 1");
+                }
+            }
+            TxLifetimeMode::Blockhash => {
+                if strategy.blockhash_refresh_ms == 0 {
+                    p.push("strategy.blockhash_refresh_ms", "must be at least 1 when transaction_lifetime = \"blockhash\"");
+                }
+                if strategy.blockhash_max_age_ms <= strategy.blockhash_refresh_ms {
+                    p.push("strategy.blockhash_max_age_ms", "must be greater than blockhash_refresh_ms");
+                }
+            }
+        }
+        if let Some(mint) = &strategy.quote_mint {
+            p.pubkey("strategy.default_quote_mint", mint);
+        }
+        if strategy.poll_quotes && strategy.poll_interval_ms == 0 {
+            p.push("strategy.poll_interval_ms", "must be at least 1 when polling_enabled = true");
+        }
+        if strategy.max_cycle_legs < 2 {
+            p.push("strategy.max_cycle_legs", format!("must be at least 2, got {}", strategy.max_cycle_legs));
+        }
+        for (i, mint) in strategy.cycle_tokens.iter().enumerate() {
+            p.pubkey(format!("strategy.cycle_tokens[{}]", i), mint);
+        }
+        let known_provider = |name: &str| name == DEFAULT_QUOTE_PROVIDER || self.quote_providers.contains_key(name);
+        if !known_provider(&strategy.quote_provider) {
+            p.push(
+                "strategy.quote_provider",
+                format!("unknown provider {:?}; define [quote_providers.{}]", strategy.quote_provider, strategy.quote_provider),
+            );
+        }
+
+        if strategy.base_tokens.is_empty() {
+            p.push("strategy.instruments", "no instruments configured");
+        }
+        for (i, token) in strategy.base_tokens.iter().enumerate() {
+            let path = format!("strategy.instruments[{}]", i);
+            p.pubkey(format!("{}.token_mint", path), &token.mint);
+            if strategy.base_tokens[..i].iter().any(|t| t.mint == token.mint) {
+                p.push(format!("{}.token_mint", path), format!("{} is configured more than once", token.mint));
+            }
+            let [lo, hi] = token.amount_range;
+            if !(lo.is_finite() && hi.is_finite() && lo > 0.0 && lo <= hi) {
+                p.push(
+                    format!("{}.notional_range", path),
+                    format!("must satisfy 0 < min <= max, got [{}, {}]", lo, hi),
+                );
+            }
+            if token.steps == 0 {
+                p.push(format!("{}.grid_steps", path), "must be at least 1");
+            }
+            if !token.min_profit.is_finite() || token.min_profit < 0.0 {
+                p.push(format!("{}.min_profit_quote_units", path), "must be a finite number >= 0");
+            }
+            if token.quote_budget == Some(0) {
+                p.push(format!("{}.quote_budget", path), "must be at least 1");
+            }
+            if let Some(name) = &token.quote_provider {
+                if !known_provider(name) {
+                    p.push(
+                        format!("{}.quote_provider", path),
+                        format!("unknown provider {:?}; define [quote_providers.{}]", name, name),
+                    );
+                }
+            }
+        }
+
+        let fees = &self.tx_cost;
+        if fees.compute_units == 0 || fees.compute_units > 1_400_000 {
+            p.push("fees.compute_unit_limit", format!("must be between 1 and 1400000, got {}", fees.compute_units));
+        }
+        if !fees.tip_sol.is_finite() || fees.tip_sol < 0.0 {
+            p.push("fees.relay_tip_sol", "must be a finite number >= 0");
+        }
+        if fees.tip_sol > 0.0 {
+            p.pubkey("fees.relay_tip_account", &fees.relay_tip_account);
+        }
+        if !fees.sol_usd.is_finite() || fees.sol_usd <= 0.0 {
+            p.push("fees.sol_price_usd", "must be a positive number");
+        }
+
+        let priority_fee = &self.priority_fee;
+        if priority_fee.enabled {
+            if priority_fee.percentile > 100 {
+                p.push("priority_fee.percentile", format!("must be between 0 and 100, got {}", priority_fee.percentile));
+            }
+            if !(priority_fee.max_profit_share > 0.0 && priority_fee.max_profit_share <= 1.0) {
+                p.push("priority_fee.max_profit_share", "must be in (0, 1]");
+            }
+            if priority_fee.refresh_interval_ms == 0 {
+                p.push("priority_fee.refresh_interval_ms", "must be at least 1");
+            }
+            for (i, account) in priority_fee.accounts.iter().enumerate() {
+                p.pubkey(format!("priority_fee.accounts[{}]", i), account);
+            }
+        }
+
+        let compute_units = &self.compute_units;
+        if compute_units.enabled && compute_units.margin_bps > 10_000 {
+            p.push("compute_units.margin_bps", format!("must be at most 10000, got {}", compute_units.margin_bps));
+        }
+
+        let jito = &self.jito;
+        if jito.enabled {
+            let url = &jito.block_engine_url;
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                p.push("jito.block_engine_url", format!("{:?} is not an http(s) URL", url));
+            }
+            if jito.tip_accounts.is_empty() {
+                p.push("jito.tip_accounts", "at least one tip account is required");
+            }
+            for (i, account) in jito.tip_accounts.iter().enumerate() {
+                p.pubkey(format!("jito.tip_accounts[{}]", i), account);
+            }
+            if !(jito.tip_profit_share >= 0.0 && jito.tip_profit_share <= 1.0) {
+                p.push("jito.tip_profit_share", "must be in [0, 1]");
+            }
+            if jito.max_tip_lamports != 0 && jito.max_tip_lamports < jito.min_tip_lamports {
+                p.push(
+                    "jito.max_tip_lamports",
+                    format!("must be 0 or at least min_tip_lamports ({})", jito.min_tip_lamports),
+                );
+            }
+            if jito.status_poll_interval_ms == 0 {
+                p.push("jito.status_poll_interval_ms", "must be at least 1");
+            }
+        }
+
+        let flash_loan = &self.flash_loan;
+        if flash_loan.enabled {
+            p.pubkey("flash_loan.program_id", &flash_loan.program_id);
+            if flash_loan.fee_bps > 10_000 {
+                p.push("flash_loan.fee_bps", format!("must be at most 10000, got {}", flash_loan.fee_bps));
+            }
+            for (i, reserve) in flash_loan.reserves.iter().enumerate() {
+                let path = format!("flash_loan.reserves[{}]", i);
+                for (field, value) in [
+                    ("token_mint", &reserve.token_mint),
+                    ("flashloan_token_reserves_liquidity", &reserve.flashloan_token_reserves_liquidity),
+                    ("flashloan_borrow_position_on_liquidity", &reserve.flashloan_borrow_position_on_liquidity),
+                    ("rate_model", &reserve.rate_model),
+                    ("vault", &reserve.vault),
+                    ("liquidity", &reserve.liquidity),
+                    ("liquidity_program", &reserve.liquidity_program),
+                ] {
+                    p.pubkey(format!("{}.{}", path, field), value);
+                }
+                if !strategy.base_tokens.iter().any(|t| t.mint == reserve.token_mint) {
+                    p.push(
+                        format!("{}.token_mint", path),
+                        format!("{} is not an instrument; the reserve would never be used", reserve.token_mint),
+                    );
+                }
+            }
+        }
+
+        let sizing = &self.sizing;
+        if sizing.max_quotes == 0 {
+            p.push("sizing.max_quotes", "must be at least 1");
+        }
+        if !(sizing.tolerance > 0.0 && sizing.tolerance < 1.0) {
+            p.push("sizing.tolerance", format!("must be between 0 and 1, got {}", sizing.tolerance));
+        }
+
+        if self.pools.enabled {
+            for (i, address) in self.pools.addresses.iter().enumerate() {
+                p.pubkey(format!("pools.addresses[{}]", i), address);
+            }
+        }
+        let mut overridden: Vec<&String> = self.tokens.symbols.keys().collect();
+        overridden.sort();
+        for mint in overridden {
+            p.pubkey(format!("tokens.symbols.{:?}", mint), mint);
+            if self.tokens.symbols[mint].trim().is_empty() {
+                p.push(format!("tokens.symbols.{:?}", mint), "symbol must not be empty");
+            }
+        }
+        if self.metrics.enabled {
+            p.listen("metrics.listen", &self.metrics.listen);
+        }
+        if self.admin.enabled {
+            p.listen("admin.listen", &self.admin.listen);
+            if self.admin.auth_token.is_empty() {
+                p.push("admin.auth_token", "required when the admin API is enabled");
+            }
+        }
+
+        p.0
+    }
+
+    /// [`Config::validate`] as a single error listing every problem.
+    pub fn check(&self) -> Result<(), anyhow::Error> {
+        let problems = self.validate();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        let lines: Vec<String> = problems.iter().map(|problem| format!("  {}", problem)).collect();
+        Err(anyhow::anyhow!(
+            "Invalid config ({} problem{}):\n{}",
+            problems.len(),
+            if problems.len() == 1 { "" } else { "s" },
+            lines.join("\n")
+        ))
+    }
+}
@@ -8,4 +8,6 @@ pub use settings::arbitrage::*;
 pub use settings::credential::*;
 pub use settings::fee::*;
 pub use settings::flash_loan::*;
+pub use settings::landing::*;
 pub use settings::services::*;
+pub use settings::simulation::*;
@@ -3,6 +3,7 @@ use solana_sdk::pubkey::Pubkey;
 
 use crate::app::config;
 
+/// Instruments as first loaded; [`crate::Engine::base_tokens`] follows hot reloads.
 pub static BASE_TOKENS: Lazy<Vec<config::BaseTokenConfig>> =
     Lazy::new(|| config::CONFIG.strategy.base_tokens.clone());
 
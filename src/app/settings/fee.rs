@@ -2,4 +2,5 @@ use once_cell::sync::Lazy;
 
 use crate::app::config;
 
+/// Fees as first loaded; [`crate::Engine::fees`] follows hot reloads.
 pub static FEES: Lazy<config::TxCostConfig> = Lazy::new(|| config::CONFIG.tx_cost.clone());
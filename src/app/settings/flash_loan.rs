@@ -8,9 +8,16 @@ use crate::app::config;
 use crate::{FlashLoanContext, ReserveInfo};
 
 /// Pre-built flash loan contexts keyed by token mint string.
-/// None if flash loans are disabled in config.
-pub static FLASH_LOAN_CONTEXTS: Lazy<Option<HashMap<String, FlashLoanContext>>> = Lazy::new(|| {
-    let fl_config = &config::CONFIG.flash_loan;
+/// None if flash loans are disabled in config. Built once; [`crate::Engine::flash_loan_context`]
+/// follows hot reloads.
+pub static FLASH_LOAN_CONTEXTS: Lazy<Option<HashMap<String, FlashLoanContext>>> =
+    Lazy::new(|| build_flash_loan_contexts(&config::CONFIG.flash_loan));
+
+/// Build flash loan contexts keyed by token mint string from `[flash_loan]`.
+/// Returns None if flash loans are disabled or no reserve is valid.
+pub fn build_flash_loan_contexts(
+    fl_config: &config::FlashLoanConfig,
+) -> Option<HashMap<String, FlashLoanContext>> {
     if !fl_config.enabled {
         return None;
     }
@@ -100,4 +107,4 @@ pub static FLASH_LOAN_CONTEXTS: Lazy<Option<HashMap<String, FlashLoanContext>>>
         "Flash loan contexts initialized"
     );
     Some(contexts)
-});
+}
@@ -0,0 +1,5 @@
+use once_cell::sync::Lazy;
+
+use crate::app::config;
+
+pub static LANDING: Lazy<config::LandingConfig> = Lazy::new(|| config::CONFIG.landing.clone());
@@ -2,10 +2,14 @@ pub mod arbitrage;
 pub mod credential;
 pub mod fee;
 pub mod flash_loan;
+pub mod landing;
 pub mod services;
+pub mod simulation;
 
 pub use arbitrage::*;
 pub use credential::*;
 pub use fee::*;
 pub use flash_loan::*;
+pub use landing::*;
 pub use services::*;
+pub use simulation::*;
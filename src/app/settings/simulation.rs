@@ -0,0 +1,6 @@
+use once_cell::sync::Lazy;
+
+use crate::app::config;
+
+pub static SIMULATION: Lazy<config::SimulationConfig> =
+    Lazy::new(|| config::CONFIG.simulation.clone());
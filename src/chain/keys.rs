@@ -70,6 +70,8 @@ pub const WSOL: Pubkey = Pubkey::from_str_const("So11111111111111111111111111111
 
 pub const TOKEN_PROGRAM_ID: Pubkey =
     Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
 pub const JUPITER_PROGRAM_ADDR: Pubkey =
     Pubkey::from_str_const("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
 pub const JUPITER_TRANSFER_AUTH: Pubkey =
@@ -77,6 +79,8 @@ pub const JUPITER_TRANSFER_AUTH: Pubkey =
 pub const JUPITER_EVENT_AUTH: Pubkey =
     Pubkey::from_str_const("D8cy77BBepLMngZx6ZukaTff5hCt1HrWyKk3Hnd9oitf");
 
+/// Built-in display symbols (and, for reference, decimals) of common mints. Code looks
+/// tokens up through [`crate::TokenRegistry`], which reads decimals from chain.
 #[derive(Debug, Clone)]
 pub struct TokenInfo {
     pub mint: &'static str,
@@ -0,0 +1,71 @@
+//! `ledger`: report from the SQLite ledger.
+
+use std::path::Path;
+
+use chrono::Utc;
+use jupiter_arbitrage_bot_offchain::*;
+
+/// Net results per instrument and target token over the last `days` days, then the
+/// `recent` most recent opportunities and submissions.
+pub fn print_ledger_report(config_path: &Path, days: i64, recent: usize) -> Result<(), anyhow::Error> {
+    let config = Config::load(config_path)?;
+    if !Path::new(&config.ledger.path).exists() {
+        return Err(anyhow::anyhow!(
+            "No ledger at {}; set [ledger] enabled = true and run the bot first",
+            config.ledger.path
+        ));
+    }
+    let ledger = Ledger::open(&config.ledger.path)?;
+    let since = Utc::now() - chrono::Duration::days(days);
+
+    println!("Net results per target since {} ({} days)", since.format("%Y-%m-%d %H:%M"), days);
+    println!(
+        "{:<36} {:>6} {:>6} {:>6} {:>16} {:>16} {:>12}",
+        "route", "opps", "sent", "landed", "expected", "realized", "fees (SOL)"
+    );
+    for row in ledger.profit_by_target(since)? {
+        let scale = 10_f64.powi(row.decimals as i32);
+        println!(
+            "{:<36} {:>6} {:>6} {:>6} {:>16.6} {:>16.6} {:>12.6}",
+            row.route,
+            row.opportunities,
+            row.submissions,
+            row.landed,
+            row.expected_profit as f64 / scale,
+            row.realized_profit_ui(),
+            row.fee_lamports as f64 / 1e9
+        );
+    }
+
+    println!("\nRecent opportunities");
+    for row in ledger.recent_opportunities(recent)? {
+        let scale = 10_f64.powi(row.decimals as i32);
+        println!(
+            "{}  {}  {:<10} {:<36} in={:.6} net={:.6}{}",
+            row.discovered_at,
+            row.id,
+            row.source,
+            row.route,
+            row.in_amount as f64 / scale,
+            row.net_profit as f64 / scale,
+            row.trigger_tx.map(|tx| format!(" trigger={}", tx)).unwrap_or_default()
+        );
+    }
+
+    println!("\nRecent submissions");
+    for row in ledger.recent_submissions(recent)? {
+        println!(
+            "{}  {}  {:<10} {} status={} expected={} realized={}{}",
+            row.submitted_at,
+            row.opportunity_id,
+            row.path,
+            row.signature.as_deref().unwrap_or("-"),
+            row.status.as_deref().unwrap_or("untracked"),
+            row.expected_profit,
+            row.realized_profit.map(|p| p.to_string()).unwrap_or_else(|| "-".to_string()),
+            row.error.map(|e| format!(" error={}", e)).unwrap_or_default()
+        );
+    }
+    Ok(())
+}
+
@@ -0,0 +1,109 @@
+//! Command line of the `jupiter_arbitrage_bot_offchain` binary.
+//!
+//! `run` (the default) starts the bot; the other subcommands are one-off tools over the
+//! same library functions, for checking a config, a route or the wallet without
+//! starting it.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use jupiter_arbitrage_bot_offchain::Config;
+
+pub mod ledger;
+pub mod nonce;
+pub mod run;
+pub mod tools;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Solana arbitrage bot: discovers and executes profitable DEX swaps via Jupiter")]
+pub struct Cli {
+    /// Config file [default: settings.toml, else Config.toml, in the current directory]
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Same as `check-config`; kept for existing scripts.
+    #[arg(long, hide = true)]
+    pub check_config: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+impl Cli {
+    /// `--config`, or the default file from the current directory.
+    pub fn config_path(&self) -> PathBuf {
+        self.config
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(Config::default_path()))
+    }
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the bot (default when no subcommand is given).
+    Run,
+    /// Validate the config file and exit; exits 1 if it has problems.
+    CheckConfig,
+    /// Quote a round trip input -> output -> input through the input's quote provider.
+    Quote {
+        /// Input mint or symbol (e.g. WSOL).
+        input: String,
+        /// Output mint or symbol (e.g. USDC).
+        output: String,
+        /// Amount of the input token, in token units (e.g. 1.5).
+        amount: f64,
+    },
+    /// Quote a configured instrument's amount grid once and print the profitable trades.
+    /// Nothing is submitted.
+    Simulate {
+        /// Instrument mint or symbol.
+        instrument: String,
+    },
+    /// Time one Jupiter quote round trip and one swap build.
+    EstimateTiming,
+    /// SOL and instrument token balances of the wallet.
+    Balances,
+    /// Durable nonce account tools.
+    Nonce {
+        #[command(subcommand)]
+        command: NonceCommand,
+    },
+    /// Net results and recent rows from the SQLite ledger.
+    Ledger {
+        /// Report period in days.
+        #[arg(long, default_value_t = 7)]
+        days: i64,
+        /// Number of recent opportunities and submissions to list.
+        #[arg(long, default_value_t = 10)]
+        recent: usize,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum NonceCommand {
+    /// Create a nonce account with the wallet as payer and authority.
+    Create,
+    /// Print a nonce account's authority, current blockhash and balance.
+    Show {
+        /// Nonce account [default: every configured nonce account].
+        address: Option<String>,
+    },
+    /// Transfer SOL from the wallet to a nonce account.
+    Fund {
+        address: String,
+        /// Amount in SOL.
+        amount: f64,
+    },
+    /// Hand a nonce account controlled by the wallet to another authority.
+    Authorize {
+        address: String,
+        new_authority: String,
+    },
+    /// Withdraw a nonce account's whole balance to the wallet (or `--to`), closing it.
+    Close {
+        address: String,
+        /// Recipient of the balance [default: the wallet].
+        #[arg(long)]
+        to: Option<String>,
+    },
+}
@@ -0,0 +1,85 @@
+//! `nonce create|show|fund|authorize|close`: durable nonce account lifecycle.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::native_token::LAMPORTS_PER_SOL;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::Keypair;
+
+use super::NonceCommand;
+
+fn parse_pubkey(what: &str, value: &str) -> Result<Pubkey, anyhow::Error> {
+    Pubkey::from_str(value).map_err(|e| anyhow::anyhow!("Invalid {} {:?}: {}", what, value, e))
+}
+
+fn sol(lamports: u64) -> f64 {
+    lamports as f64 / LAMPORTS_PER_SOL as f64
+}
+
+pub async fn nonce(config_path: &Path, command: NonceCommand) -> Result<(), anyhow::Error> {
+    let config = Config::load(config_path)?;
+    match command {
+        NonceCommand::Create => {
+            let engine = Engine::from_config(config)?;
+            let (address, signature) = create_nonce_account(&engine).await?;
+            println!("Created nonce account {} ({})", address, signature);
+            println!("Authority: {}", engine.pubkey);
+            println!("Set [strategy] nonce_account_pubkey = \"{}\" in {}", address, config_path.display());
+        }
+        NonceCommand::Show { address } => {
+            let addresses = match address {
+                Some(address) => vec![parse_pubkey("nonce account", &address)?],
+                None => std::iter::once(&config.strategy.nonce_account)
+                    .chain(&config.strategy.nonce_pool)
+                    .filter(|address| !address.is_empty())
+                    .map(|address| parse_pubkey("nonce account", address))
+                    .collect::<Result<_, _>>()?,
+            };
+            // Reading needs no signer
+            let engine = Engine::new(config, Keypair::new())?;
+            for (i, address) in addresses.iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                let info = inspect_nonce_account(&engine, address).await?;
+                println!("Nonce account:      {}", address);
+                println!("Authority:          {}", info.data.authority);
+                println!("Blockhash:          {}", info.data.blockhash());
+                println!("Lamports/signature: {}", info.data.get_lamports_per_signature());
+                println!("Balance:            {} SOL", sol(info.lamports));
+            }
+        }
+        NonceCommand::Fund { address, amount } => {
+            let address = parse_pubkey("nonce account", &address)?;
+            if !amount.is_finite() || amount <= 0.0 {
+                return Err(anyhow::anyhow!("Amount must be a positive number of SOL, got {}", amount));
+            }
+            let lamports = (amount * LAMPORTS_PER_SOL as f64).round() as u64;
+            let engine = Engine::from_config(config)?;
+            let signature = fund_nonce_account(&engine, &address, lamports).await?;
+            println!("Sent {} SOL to {} ({})", sol(lamports), address, signature);
+        }
+        NonceCommand::Authorize { address, new_authority } => {
+            let address = parse_pubkey("nonce account", &address)?;
+            let new_authority = parse_pubkey("authority", &new_authority)?;
+            let engine = Engine::from_config(config)?;
+            let signature = authorize_nonce_account(&engine, &address, &new_authority).await?;
+            println!("Authority of {} is now {} ({})", address, new_authority, signature);
+        }
+        NonceCommand::Close { address, to } => {
+            let address = parse_pubkey("nonce account", &address)?;
+            let to = to.map(|to| parse_pubkey("recipient", &to)).transpose()?;
+            let configured = address.to_string() == config.strategy.nonce_account
+                || config.strategy.nonce_pool.contains(&address.to_string());
+            let engine = Engine::from_config(config)?;
+            let (lamports, signature) = close_nonce_account(&engine, &address, to).await?;
+            println!("Closed {} and withdrew {} SOL ({})", address, sol(lamports), signature);
+            if configured {
+                println!("Remove it from [strategy] in {} before the next run", config_path.display());
+            }
+        }
+    }
+    Ok(())
+}
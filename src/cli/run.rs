@@ -0,0 +1,303 @@
+//! `run`: the arbitrage bot itself.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use futures::{SinkExt, StreamExt};
+use jupiter_arbitrage_bot_offchain::*;
+use tokio::time::Duration;
+use tracing::{error, info, warn};
+use yellowstone_grpc_proto::geyser::{
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeRequestFilterTransactions,
+};
+
+/// Start every enabled mode from the config at `config_path` and run until stopped.
+pub async fn run(config_path: &Path) -> Result<(), anyhow::Error> {
+    info!("Using RPC only for transaction submission");
+
+    let config = Config::load(config_path)?;
+    config.check()?;
+    let engine = Arc::new(Engine::from_config(config)?);
+    let config = engine.config();
+
+    match config.strategy.tx_lifetime {
+        TxLifetimeMode::Nonce => {
+            // Fail fast on nonce accounts that are missing or not controlled by the signer
+            check_nonce_accounts(&engine).await?;
+            info!(accounts = engine.nonces.len(), authority = %engine.pubkey, "Nonce accounts checked");
+
+            // Start nonce fetcher
+            tokio::spawn({
+                let engine = engine.clone();
+                async move {
+                    fetch_nonce(&engine).await;
+                }
+            });
+        }
+        TxLifetimeMode::Blockhash => {
+            // Fail fast on an unreachable RPC, then keep the blockhash fresh
+            let latest = refresh_blockhash(&engine).await?;
+            info!(
+                blockhash = %latest.blockhash,
+                last_valid_block_height = latest.last_valid_block_height,
+                "Signing with recent blockhashes"
+            );
+            tokio::spawn(run_blockhash_refresher(engine.clone()));
+        }
+    }
+
+    // Start SOL price fetcher
+    tokio::spawn({
+        let fallback_price = config.tx_cost.sol_usd;
+        async move {
+            start_sol_price_fetcher(fallback_price).await;
+        }
+    });
+
+    info!(pubkey = %engine.pubkey, "Wallet loaded");
+
+    // Decimals and token program of every instrument, before anything is quoted
+    let mints: Vec<String> = config.strategy.base_tokens.iter().map(|t| t.mint.clone()).collect();
+    for token in engine.tokens.resolve_all(&mints).await? {
+        info!(mint = %token.mint, symbol = %token.symbol, decimals = token.decimals, program = %token.program_id, "Instrument token");
+    }
+
+    info!(
+        big_trades = config.strategy.watch_flows,
+        pool_watch = config.strategy.watch_pools,
+        continuous_polling = config.strategy.poll_quotes,
+        polling_interval_ms = config.strategy.poll_interval_ms,
+        submit_transactions = config.strategy.live_trading,
+        flash_loan = config.flash_loan.enabled,
+        "Arbitrage mode configuration"
+    );
+
+    // Log flash loan status
+    if config.flash_loan.enabled {
+        match engine.flash_loan_mints() {
+            Some(mints) => info!(reserves = ?mints, "Flash loans enabled"),
+            None => warn!("Flash loans enabled in config but no valid reserves loaded"),
+        }
+    }
+
+    // Load local pool state; kept fresh by the RPC refresher and/or the Yellowstone feed
+    if engine.pools.enabled() {
+        match refresh_pools(&engine).await {
+            Ok(_) => info!(
+                pools = engine.pools.len(),
+                configured = engine.pools.pool_addresses().len(),
+                "Local pool state loaded"
+            ),
+            Err(e) => warn!(error = ?e, "Local pool state load failed"),
+        }
+        tokio::spawn(run_pool_refresher(engine.clone()));
+    }
+
+    // Compute unit price from recent prioritization fees
+    if config.priority_fee.enabled {
+        tokio::spawn(run_priority_fee_estimator(engine.clone()));
+    }
+
+    // Prometheus endpoint and continuous Jupiter latency probe
+    if config.metrics.enabled {
+        let metrics_engine = engine.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_metrics(metrics_engine).await {
+                error!(error = ?e, "Metrics endpoint stopped");
+            }
+        });
+        tokio::spawn(run_jupiter_latency_probe(engine.clone()));
+    }
+
+    // Re-read the config file on change ([strategy], [fees], [flash_loan])
+    if config.hot_reload.enabled {
+        tokio::spawn(watch_config_file(engine.clone(), config_path.to_path_buf()));
+    }
+
+    // Authenticated runtime control (pause/resume, per-instrument settings)
+    if config.admin.enabled {
+        let admin_engine = engine.clone();
+        tokio::spawn(async move {
+            if let Err(e) = serve_admin(admin_engine).await {
+                error!(error = ?e, "Admin API stopped");
+            }
+        });
+    }
+
+    match estimate_jupiter_timing(&engine).await {
+        Ok(t) => info!(
+            quote_ms = t.quote_ms,
+            swap_build_ms = t.swap_build_ms,
+            total_ms = t.quote_ms + t.swap_build_ms,
+            "Jupiter timing estimate"
+        ),
+        Err(e) => warn!(         
+            error = ?e,
+            "Jupiter timing estimate failed"
+        ),
+    }
+
+    // Start continuous polling if enabled
+    if config.strategy.poll_quotes {
+        let polling_interval = config.strategy.poll_interval_ms;
+        let engine = engine.clone();
+        tokio::spawn(async move {
+            continuous_polling_loop(engine, polling_interval).await;
+        });
+    }
+
+    // Start pool watcher if enabled
+    if config.strategy.watch_pools {
+        let engine = engine.clone();
+        tokio::spawn(async move {
+            if let Err(e) = run_pool_watcher(engine).await {
+                error!(error = ?e, "Pool watcher stopped");
+            }
+        });
+    }
+
+    // Run big trades monitor if enabled
+    if config.strategy.watch_flows {
+        run_big_trades_monitor(engine).await?;
+    } else if config.strategy.poll_quotes || config.strategy.watch_pools {
+        info!("Big trades monitor disabled; running polling / pool watcher only");
+        loop {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        }
+    } else {
+        warn!(config = %config_path.display(), "All modes disabled; enable at least one in the config");
+    }
+
+    Ok(())
+}
+
+// =============================================================================
+// BIG TRADES MONITOR MODE
+// =============================================================================
+
+/// Transaction filters for the enabled instruments' mints, plus pool accounts for
+/// local pool math (empty if disabled; the pool watcher has its own subscription).
+fn big_trades_request(engine: &Engine, mints: &[String]) -> SubscribeRequest {
+    let mut transactions_map = HashMap::new();
+    for (idx, mint) in mints.iter().enumerate() {
+        let filter = SubscribeRequestFilterTransactions {
+            vote: Some(false),
+            failed: Some(false),
+            account_include: vec![mint.clone()],
+            account_exclude: vec![],
+            account_required: vec![],
+            signature: None,
+        };
+        transactions_map.insert(format!("tx_{}", idx), filter);
+    }
+
+    SubscribeRequest {
+        slots: HashMap::new(),
+        accounts: if engine.config().strategy.watch_pools {
+            HashMap::new()
+        } else {
+            pool_accounts_filters(&engine.pools)
+        },
+        transactions: transactions_map,
+        transactions_status: HashMap::new(),
+        blocks: HashMap::new(),
+        blocks_meta: HashMap::new(),
+        accounts_data_slice: vec![],
+        commitment: Some(0),
+        ping: None,
+        entry: HashMap::new(),
+        from_slot: None,
+    }
+}
+
+fn instrument_mints(engine: &Engine) -> Vec<String> {
+    engine.base_tokens().into_iter().map(|t| t.mint).collect()
+}
+
+async fn run_big_trades_monitor(engine: Arc<Engine>) -> Result<(), anyhow::Error> {
+    let config = engine.config();
+    let yellowstone_endpoint = config.node.geyser_url.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("yellowstone_grpc_endpoint not configured"))?;
+    config.node.geyser_token.as_ref()
+        .ok_or_else(|| anyhow::anyhow!("yellowstone_grpc_token not configured"))?;
+
+    info!(endpoint = %yellowstone_endpoint, "Connecting to Yellowstone gRPC");
+
+    let mut config_updates = engine.subscribe_config();
+    let mut first_attempt = true;
+    loop {
+        if !first_attempt {
+            engine.metrics.yellowstone_reconnects.with_label_values(&["big_trades"]).inc();
+        }
+        first_attempt = false;
+        info!("Connecting and subscribing to Yellowstone");
+
+        let mut client = match connect_yellowstone(&engine).await {
+            Ok(c) => {
+                info!("Yellowstone connected");
+                c
+            }
+            Err(e) => {
+                error!(error = ?e, "Yellowstone connection error");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        config_updates.mark_unchanged();
+        let mut mints = instrument_mints(&engine);
+        let request = big_trades_request(&engine, &mints);
+
+        let (mut sink, mut stream) = match client.subscribe_with_request(Some(request)).await {
+            Ok((sink, stream)) => {
+                info!(instruments = mints.len(), "Yellowstone subscribed");
+                (sink, stream)
+            }
+            Err(e) => {
+                error!(error = ?e, "Yellowstone subscribe error");
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        loop {
+            let next = tokio::select! {
+                next = stream.next() => next,
+                Ok(()) = config_updates.changed() => {
+                    // A reload changed the instruments: replace the filters on the open stream
+                    let reloaded = instrument_mints(&engine);
+                    if reloaded != mints {
+                        mints = reloaded;
+                        info!(instruments = mints.len(), "Instruments changed; resubscribing to Yellowstone");
+                        if let Err(e) = sink.send(big_trades_request(&engine, &mints)).await {
+                            error!(error = ?e, "Yellowstone resubscribe failed");
+                            break;
+                        }
+                    }
+                    continue;
+                }
+            };
+            match next {
+                Some(Ok(update)) => {
+                    if let Some(UpdateOneof::Account(account)) = &update.update_oneof {
+                        apply_account_update(&engine.pools, account);
+                        continue;
+                    }
+                    let engine = engine.clone();
+                    tokio::spawn(async move {
+                        process_single_trade_yellowstone(engine, update).await;
+                    });
+                }
+                Some(Err(e)) => {
+                    error!(error = ?e, "Yellowstone stream error");
+                    break;
+                }
+                None => {
+                    info!("Yellowstone stream ended");
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! One-off tools: `check-config`, `quote`, `simulate`, `estimate-timing` and `balances`.
+//!
+//! `quote`, `simulate` and `estimate-timing` only read quotes, so they run with a
+//! throwaway signer and work without the wallet keypair.
+
+use std::path::Path;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::signer::keypair::Keypair;
+
+/// Validate the config file without starting the bot; exits 1 if it has problems.
+pub fn check_config(config_path: &Path) -> Result<(), anyhow::Error> {
+    let config = Config::load(config_path)?;
+    let problems = config.validate();
+    if problems.is_empty() {
+        println!("{}: OK", config_path.display());
+        return Ok(());
+    }
+    println!("{}: {} problem(s)", config_path.display(), problems.len());
+    for problem in &problems {
+        println!("  {}", problem);
+    }
+    std::process::exit(1);
+}
+
+/// Engine for read-only commands: the config's endpoints with a throwaway signer.
+fn read_only_engine(config_path: &Path) -> Result<Engine, anyhow::Error> {
+    Engine::new(Config::load(config_path)?, Keypair::new())
+}
+
+/// A token by mint address or symbol, with its decimals read from chain.
+async fn find_token(engine: &Engine, token: &str) -> Result<TokenMeta, anyhow::Error> {
+    let mint = engine
+        .tokens
+        .find_mint(token)
+        .ok_or_else(|| anyhow::anyhow!("Unknown token {:?}; use its mint address or add it to [tokens] symbols", token))?;
+    engine.tokens.resolve(&mint).await
+}
+
+/// `quote <input> <output> <amount>`: one round trip through [`get_quote_polling`].
+pub async fn quote(config_path: &Path, input: &str, output: &str, amount: f64) -> Result<(), anyhow::Error> {
+    let engine = read_only_engine(config_path)?;
+    let (input, output) = (find_token(&engine, input).await?, find_token(&engine, output).await?);
+    let scale = 10_f64.powf(input.decimals as f64);
+    let in_raw = (amount * scale) as u64;
+    if in_raw == 0 {
+        return Err(anyhow::anyhow!("Amount {} {} is zero in raw units", amount, input.symbol));
+    }
+
+    let (in_amount, out_amount, leg_1, _) = get_quote_polling(&engine, in_raw, &input.mint, &output.mint).await?;
+    let profit = out_amount as i64 - in_amount as i64;
+    println!(
+        "{} -> {}: {:.6} {} -> {:.6} {}",
+        input.symbol,
+        output.symbol,
+        in_amount as f64 / scale,
+        input.symbol,
+        leg_1.out_amount as f64 / 10_f64.powf(output.decimals as f64),
+        output.symbol
+    );
+    println!("{} -> {}: {:.6} {}", output.symbol, input.symbol, out_amount as f64 / scale, input.symbol);
+    println!(
+        "Round trip: {:+.6} {} ({:+.4}%), before fees",
+        profit as f64 / scale,
+        input.symbol,
+        profit as f64 / in_amount as f64 * 100.0
+    );
+    Ok(())
+}
+
+/// `simulate <instrument>`: one [`simulate_amount_in`] pass over a configured
+/// instrument's amount grid, printing the trades that clear its minimum profit.
+pub async fn simulate(config_path: &Path, instrument: &str) -> Result<(), anyhow::Error> {
+    let engine = read_only_engine(config_path)?;
+    let config = engine.config();
+    let token = find_token(&engine, instrument).await?;
+    let base_token = config
+        .strategy
+        .base_tokens
+        .iter()
+        .find(|t| t.mint == token.mint)
+        .ok_or_else(|| anyhow::anyhow!("{} is not a configured instrument", token.symbol))?;
+
+    let targets = polling_targets(&engine, &token.mint, &token.symbol);
+    let [min_range, max_range] = base_token.amount_range;
+    let trades = simulate_amount_in(
+        &engine,
+        token.mint.clone(),
+        token.decimals,
+        token.symbol.clone(),
+        targets.clone(),
+        min_range,
+        max_range,
+        base_token.steps as usize,
+        base_token.min_profit,
+        true,
+        instrument_quote_budget(&engine, &token.mint),
+    )
+    .await;
+
+    println!(
+        "{}: {} step(s) over [{}, {}] against {} target(s), min profit {} {}",
+        token.symbol,
+        base_token.steps,
+        min_range,
+        max_range,
+        targets.len(),
+        base_token.min_profit,
+        token.symbol
+    );
+    if trades.is_empty() {
+        println!("No profitable trades");
+        return Ok(());
+    }
+    let scale = 10_f64.powf(token.decimals as f64);
+    for (in_amount, out_amount, _, _, elapsed, target) in trades {
+        println!(
+            "{} -> {} -> {}: in={:.6} out={:.6} gross={:+.6} ({} ms)",
+            token.symbol,
+            engine.tokens.symbol(&target),
+            token.symbol,
+            in_amount as f64 / scale,
+            out_amount as f64 / scale,
+            (out_amount as i64 - in_amount as i64) as f64 / scale,
+            elapsed / 1000
+        );
+    }
+    Ok(())
+}
+
+/// `estimate-timing`: [`estimate_jupiter_timing`] for the first instrument.
+pub async fn estimate_timing(config_path: &Path) -> Result<(), anyhow::Error> {
+    let engine = read_only_engine(config_path)?;
+    let timing = estimate_jupiter_timing(&engine).await?;
+    println!("Quote round trip: {} ms", timing.quote_ms);
+    println!("Swap build:       {} ms", timing.swap_build_ms);
+    println!("Total:            {} ms", timing.total_ms);
+    Ok(())
+}
+
+/// `balances`: the wallet's SOL balance and its associated token account balance for
+/// every configured instrument.
+pub async fn balances(config_path: &Path) -> Result<(), anyhow::Error> {
+    let engine = Engine::from_config(Config::load(config_path)?)?;
+    let lamports = engine.rpc_client.get_balance(&engine.pubkey).await?;
+    println!("Wallet {}", engine.pubkey);
+    println!("{:<10} {:>20}", "SOL", format!("{:.9}", lamports as f64 / 1e9));
+
+    let mints: Vec<String> = engine.config().strategy.base_tokens.iter().map(|t| t.mint.clone()).collect();
+    for token in engine.tokens.resolve_all(&mints).await? {
+        let ata = token.associated_token_address(&engine.pubkey);
+        let balance = match engine.rpc_client.get_token_account_balance(&ata).await {
+            Ok(amount) => amount.ui_amount_string,
+            Err(_) => "no token account".to_string(),
+        };
+        println!("{:<10} {:>20}  {}", token.symbol, balance, ata);
+    }
+    Ok(())
+}
@@ -0,0 +1,293 @@
+//! Admin API endpoints.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use jupiter_swap_api_client::quote::{QuoteRequest, QuoteResponse};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use tracing::info;
+
+use super::ApiError;
+use crate::app::config::BaseTokenConfig;
+use crate::*;
+
+/// Default and maximum number of ledger rows returned by the list endpoints.
+const DEFAULT_LIST_LIMIT: usize = 20;
+const MAX_LIST_LIMIT: usize = 500;
+
+/// Runtime-adjustable view of one instrument.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstrumentView {
+    pub token_mint: String,
+    pub enabled: bool,
+    pub min_profit_quote_units: f64,
+    pub notional_range: [f64; 2],
+    pub grid_steps: u64,
+}
+
+impl From<&BaseTokenConfig> for InstrumentView {
+    fn from(token: &BaseTokenConfig) -> Self {
+        Self {
+            token_mint: token.mint.clone(),
+            enabled: token.enabled,
+            min_profit_quote_units: token.min_profit,
+            notional_range: token.amount_range,
+            grid_steps: token.steps,
+        }
+    }
+}
+
+/// Body of `PATCH /instruments/:mint`; omitted fields are left unchanged.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstrumentUpdate {
+    pub min_profit_quote_units: Option<f64>,
+    pub notional_range: Option<[f64; 2]>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListQuery {
+    pub limit: Option<usize>,
+}
+
+/// Body of `POST /quote`: a single swap quote, amounts in raw units.
+#[derive(Debug, Deserialize)]
+pub struct QuoteBody {
+    pub input_mint: String,
+    pub output_mint: String,
+    pub amount: u64,
+}
+
+/// Body of `POST /simulate`: a `mint -> target -> mint` round trip of `amount` raw units.
+#[derive(Debug, Deserialize)]
+pub struct SimulateBody {
+    pub mint: String,
+    pub target: String,
+    pub amount: u64,
+}
+
+fn parse_mint(field: &str, value: &str) -> Result<Pubkey, ApiError> {
+    Pubkey::from_str(value).map_err(|e| ApiError::bad_request(format!("invalid {} {:?}: {}", field, value, e)))
+}
+
+/// Override settings of instrument `mint` with `change` and return its new view.
+fn modify_instrument(
+    engine: &Engine,
+    mint: &str,
+    change: impl FnOnce(&mut InstrumentOverride),
+) -> Result<Json<InstrumentView>, ApiError> {
+    if !engine.config().strategy.base_tokens.iter().any(|t| t.mint == mint) {
+        return Err(ApiError::not_found(format!("unknown instrument {}", mint)));
+    }
+    let config = engine
+        .override_config(|overrides| change(overrides.instruments.entry(mint.to_string()).or_default()))
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let token = config
+        .strategy
+        .base_tokens
+        .iter()
+        .find(|t| t.mint == mint)
+        .ok_or_else(|| ApiError::not_found(format!("unknown instrument {}", mint)))?;
+    Ok(Json(InstrumentView::from(token)))
+}
+
+fn set_execution(engine: &Engine, enabled: bool) -> Result<Json<Value>, ApiError> {
+    engine
+        .override_config(|overrides| overrides.execution_enabled = Some(enabled))
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    info!(execution_enabled = enabled, "Execution toggled via admin API");
+    Ok(Json(json!({ "execution_enabled": enabled })))
+}
+
+/// `GET /status`: execution state and all instruments.
+pub async fn status(State(engine): State<Arc<Engine>>) -> Json<Value> {
+    let config = engine.config();
+    let instruments: Vec<InstrumentView> = config.strategy.base_tokens.iter().map(InstrumentView::from).collect();
+    Json(json!({
+        "execution_enabled": config.strategy.live_trading,
+        "instruments": instruments,
+    }))
+}
+
+/// `POST /execution/pause`: stop submitting; discovery and the ledger keep running.
+pub async fn pause_execution(State(engine): State<Arc<Engine>>) -> Result<Json<Value>, ApiError> {
+    set_execution(&engine, false)
+}
+
+/// `POST /execution/resume`
+pub async fn resume_execution(State(engine): State<Arc<Engine>>) -> Result<Json<Value>, ApiError> {
+    set_execution(&engine, true)
+}
+
+/// `GET /instruments/:mint`
+pub async fn get_instrument(
+    State(engine): State<Arc<Engine>>,
+    Path(mint): Path<String>,
+) -> Result<Json<InstrumentView>, ApiError> {
+    let config = engine.config();
+    let token = config
+        .strategy
+        .base_tokens
+        .iter()
+        .find(|t| t.mint == mint)
+        .ok_or_else(|| ApiError::not_found(format!("unknown instrument {}", mint)))?;
+    Ok(Json(InstrumentView::from(token)))
+}
+
+/// `POST /instruments/:mint/enable`
+pub async fn enable_instrument(
+    State(engine): State<Arc<Engine>>,
+    Path(mint): Path<String>,
+) -> Result<Json<InstrumentView>, ApiError> {
+    info!(%mint, "Instrument enabled via admin API");
+    modify_instrument(&engine, &mint, |token| token.enabled = Some(true))
+}
+
+/// `POST /instruments/:mint/disable`: the instrument is no longer polled or watched.
+pub async fn disable_instrument(
+    State(engine): State<Arc<Engine>>,
+    Path(mint): Path<String>,
+) -> Result<Json<InstrumentView>, ApiError> {
+    info!(%mint, "Instrument disabled via admin API");
+    modify_instrument(&engine, &mint, |token| token.enabled = Some(false))
+}
+
+/// `PATCH /instruments/:mint`: change `min_profit_quote_units` and/or `notional_range`.
+pub async fn update_instrument(
+    State(engine): State<Arc<Engine>>,
+    Path(mint): Path<String>,
+    Json(update): Json<InstrumentUpdate>,
+) -> Result<Json<InstrumentView>, ApiError> {
+    if let Some(min_profit) = update.min_profit_quote_units {
+        if !min_profit.is_finite() || min_profit < 0.0 {
+            return Err(ApiError::bad_request("min_profit_quote_units must be a finite number >= 0"));
+        }
+    }
+    if let Some([lo, hi]) = update.notional_range {
+        if !(lo.is_finite() && hi.is_finite() && lo > 0.0 && lo <= hi) {
+            return Err(ApiError::bad_request(format!(
+                "notional_range must satisfy 0 < min <= max, got [{}, {}]",
+                lo, hi
+            )));
+        }
+    }
+
+    info!(%mint, ?update, "Instrument updated via admin API");
+    modify_instrument(&engine, &mint, |token| {
+        if let Some(min_profit) = update.min_profit_quote_units {
+            token.min_profit = Some(min_profit);
+        }
+        if let Some(range) = update.notional_range {
+            token.notional_range = Some(range);
+        }
+    })
+}
+
+fn list_limit(query: &ListQuery) -> usize {
+    query.limit.unwrap_or(DEFAULT_LIST_LIMIT).min(MAX_LIST_LIMIT)
+}
+
+fn ledger_error(e: anyhow::Error) -> ApiError {
+    ApiError::new(StatusCode::SERVICE_UNAVAILABLE, e.to_string())
+}
+
+/// Run `read` on the blocking pool: a ledger read waits for the queued writes and
+/// queries SQLite, which must not hold up the runtime the trading loop shares.
+async fn read_ledger<T, F>(engine: Arc<Engine>, read: F) -> Result<T, ApiError>
+where
+    T: Send + 'static,
+    F: FnOnce(&Ledger) -> Result<T, anyhow::Error> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || read(&engine.ledger))
+        .await
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, format!("ledger read failed: {}", e)))?
+        .map_err(ledger_error)
+}
+
+/// `GET /opportunities?limit=N`: most recent ledger opportunities first.
+pub async fn recent_opportunities(
+    State(engine): State<Arc<Engine>>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<Vec<OpportunityRow>>, ApiError> {
+    let limit = list_limit(&query);
+    let rows = read_ledger(engine, move |ledger| ledger.recent_opportunities(limit)).await?;
+    Ok(Json(rows))
+}
+
+/// `GET /submissions?limit=N`: most recent submissions with their landing status.
+pub async fn recent_submissions(
+    State(engine): State<Arc<Engine>>,
+    Query(query): Query<ListQuery>,
+) -> Result<Json<Vec<SubmissionRow>>, ApiError> {
+    let limit = list_limit(&query);
+    let rows = read_ledger(engine, move |ledger| ledger.recent_submissions(limit)).await?;
+    Ok(Json(rows))
+}
+
+/// `POST /quote`: one quote from the input mint's quote provider.
+pub async fn quote(
+    State(engine): State<Arc<Engine>>,
+    Json(body): Json<QuoteBody>,
+) -> Result<Json<QuoteResponse>, ApiError> {
+    let request = QuoteRequest {
+        amount: body.amount,
+        input_mint: parse_mint("input_mint", &body.input_mint)?,
+        output_mint: parse_mint("output_mint", &body.output_mint)?,
+        slippage_bps: 0,
+        ..QuoteRequest::default()
+    };
+    let response = engine.quote_provider(&body.input_mint).quote(&request).await?;
+    Ok(Json(response))
+}
+
+/// `POST /simulate`: quote a round trip, build its transaction and run it through
+/// `simulateTransaction`. Nothing is submitted.
+pub async fn simulate(
+    State(engine): State<Arc<Engine>>,
+    Json(body): Json<SimulateBody>,
+) -> Result<Json<Value>, ApiError> {
+    let base_mint = parse_mint("mint", &body.mint)?;
+    parse_mint("target", &body.target)?;
+
+    let (in_amount, out_amount, leg_1, leg_2) =
+        get_quote_polling(&engine, body.amount, &body.mint, &body.target).await?;
+    let cycle = CycleQuote {
+        in_amount,
+        out_amount,
+        legs: vec![leg_1, leg_2],
+        elapsed: 0,
+    };
+
+    // Require only break-even so an unprofitable route still shows what it would do
+    let ix = get_cycle_swap_ix(&engine, &cycle.legs, 0, true).await?;
+    let mut ixs = ix.setup_instructions;
+    ixs.push(ix.swap_instruction);
+    let alts = fetch_alt(&engine, ix.address_lookup_table_addresses).await;
+    let config = engine.config();
+    let compute_unit_price = engine.priority_fees.price(&config);
+    // Leased only while simulating, so live submissions are not signed with the same value
+    let lifetime = engine.acquire_lifetime().ok_or_else(|| {
+        ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "no free nonce account or recent blockhash")
+    })?;
+    let tx = build_transaction(&engine, &lifetime, ixs, &alts, config.tx_cost.compute_units as u32, compute_unit_price)?;
+    let report = simulate_trade_transaction(&engine, &tx, &base_mint).await?;
+
+    Ok(Json(json!({
+        "route": cycle.label(&engine.tokens),
+        "in_amount": cycle.in_amount,
+        "out_amount": cycle.out_amount,
+        "quoted_profit": cycle.profit(),
+        "simulation": {
+            "succeeded": report.succeeded(),
+            "error": report.err,
+            "units_consumed": report.units_consumed,
+            "realized_profit": report.realized_profit,
+            "logs": report.logs,
+        },
+    })))
+}
@@ -0,0 +1,117 @@
+//! Local HTTP/JSON admin API.
+//!
+//! Runtime control of a running engine: pause and resume execution, enable, disable
+//! and retune instruments, list what the ledger recorded, and run a one-off quote or
+//! simulation. Changes are runtime overrides ([`Engine::override_config`]): they apply
+//! from the next polling pass, survive hot reloads unless the file changes the same
+//! setting, and are lost on restart (edit `settings.toml` to keep them).
+//!
+//! Every request must carry `Authorization: Bearer <[admin] auth_token>`.
+
+use std::sync::Arc;
+
+use axum::extract::{Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::json;
+use solana_sdk::hash::hash;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use crate::Engine;
+
+pub mod handlers;
+
+use handlers::*;
+
+/// Router for the admin API, requiring `Authorization: Bearer <auth_token>`.
+pub fn admin_router(engine: Arc<Engine>, auth_token: String) -> Router {
+    Router::new()
+        .route("/status", get(status))
+        .route("/execution/pause", post(pause_execution))
+        .route("/execution/resume", post(resume_execution))
+        .route("/instruments/:mint", get(get_instrument).patch(update_instrument))
+        .route("/instruments/:mint/enable", post(enable_instrument))
+        .route("/instruments/:mint/disable", post(disable_instrument))
+        .route("/opportunities", get(recent_opportunities))
+        .route("/submissions", get(recent_submissions))
+        .route("/quote", post(quote))
+        .route("/simulate", post(simulate))
+        .layer(middleware::from_fn_with_state(Arc::new(auth_token), require_token))
+        .with_state(engine)
+}
+
+async fn require_token(State(token): State<Arc<String>>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|presented| !token.is_empty() && token_matches(presented, &token));
+    if !authorized {
+        return ApiError::new(StatusCode::UNAUTHORIZED, "missing or invalid bearer token").into_response();
+    }
+    next.run(request).await
+}
+
+/// `presented == token` in time that does not depend on where they differ. Both are
+/// hashed first, so neither the length nor a common prefix of the token leaks.
+fn token_matches(presented: &str, token: &str) -> bool {
+    let (presented, token) = (hash(presented.as_bytes()).to_bytes(), hash(token.as_bytes()).to_bytes());
+    let diff = presented.iter().zip(token.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b));
+    std::hint::black_box(diff) == 0
+}
+
+/// Serve the admin API on `[admin] listen` until the process exits.
+pub async fn serve_admin(engine: Arc<Engine>) -> Result<(), anyhow::Error> {
+    let admin = engine.config().admin.clone();
+    if admin.auth_token.is_empty() {
+        return Err(anyhow::anyhow!("[admin] auth_token must be set when the admin API is enabled"));
+    }
+    let listener = TcpListener::bind(&admin.listen)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to bind admin API {}: {}", admin.listen, e))?;
+    info!(listen = %admin.listen, "Serving admin API");
+    axum::serve(listener, admin_router(engine, admin.auth_token)).await?;
+    Ok(())
+}
+
+/// Error response: `{"error": message}` with a status code.
+#[derive(Debug)]
+pub struct ApiError {
+    pub status: StatusCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Failures of downstream calls (quote provider, RPC) surface as 502.
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::new(StatusCode::BAD_GATEWAY, e.to_string())
+    }
+}
@@ -0,0 +1,545 @@
+//! Injectable engine context.
+//!
+//! [`Engine`] owns everything the arbitrage pipeline needs — config, signer, RPC and
+//! Jupiter clients, flash loan contexts, local pool state, the SQLite ledger, metrics, token metadata,
+//! transaction cost state, the durable nonce pool and the recent blockhash — so that several
+//! strategies can run in one process and the library can be driven without a `settings.toml` or keypair file on disk. The `Lazy`
+//! globals in `app::settings` (`RPC_CLIENT`, `PUBKEY`, `FEES`, ...) remain as a convenience layer
+//! over [`ENGINE`].
+
+use jupiter_swap_api_client::JupiterSwapApiClient;
+use once_cell::sync::Lazy;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    nonce::state::Data as NonceData,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer},
+};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::app::config::{
+    BaseTokenConfig, Config, StrategyConfig, TxCostConfig, TxLifetimeMode, DEFAULT_QUOTE_PROVIDER,
+};
+use crate::{
+    build_flash_loan_contexts, build_quote_provider, load_keypair, BlockEngine, BlockhashCache, ComputeUnitCache,
+    CostModel, FlashLoanContext, Ledger, Metrics, NonceLease, NoncePool, PoolCache, PriorityFeeEstimator, QuoteProvider,
+    SubmitEndpoints, TokenRegistry, TxLifetime, WSOL,
+};
+
+/// Config that can change while the engine runs, swapped as one unit.
+struct LiveConfig {
+    config: Arc<Config>,
+    /// Built from `config.flash_loan`; None if disabled.
+    flash_loan_contexts: Option<Arc<HashMap<String, FlashLoanContext>>>,
+    /// Config as last read from the file (or passed to [`Engine::new`]), without
+    /// `overrides`; reloads are compared against it.
+    file: Arc<Config>,
+    overrides: RuntimeOverrides,
+}
+
+/// Settings changed at runtime (admin API), kept on top of the config file across
+/// reloads until the file itself changes the same setting.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuntimeOverrides {
+    /// `execution_enabled`, set by pause / resume.
+    pub execution_enabled: Option<bool>,
+    /// Instrument settings by mint.
+    pub instruments: HashMap<String, InstrumentOverride>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct InstrumentOverride {
+    pub enabled: Option<bool>,
+    pub min_profit: Option<f64>,
+    pub notional_range: Option<[f64; 2]>,
+}
+
+impl RuntimeOverrides {
+    /// Set every overridden value in `config`.
+    pub fn apply(&self, config: &mut Config) {
+        if let Some(enabled) = self.execution_enabled {
+            config.strategy.live_trading = enabled;
+        }
+        for token in &mut config.strategy.base_tokens {
+            let Some(o) = self.instruments.get(&token.mint) else {
+                continue;
+            };
+            if let Some(enabled) = o.enabled {
+                token.enabled = enabled;
+            }
+            if let Some(min_profit) = o.min_profit {
+                token.min_profit = min_profit;
+            }
+            if let Some(range) = o.notional_range {
+                token.amount_range = range;
+            }
+        }
+    }
+
+    /// Drop the overrides of settings the file changed from `old` to `new`: the later
+    /// edit wins.
+    fn forget_changed(&mut self, old: &StrategyConfig, new: &StrategyConfig) {
+        if old.live_trading != new.live_trading {
+            self.execution_enabled = None;
+        }
+        for (mint, o) in &mut self.instruments {
+            let find = |strategy: &StrategyConfig| strategy.base_tokens.iter().find(|t| &t.mint == mint).cloned();
+            let (a, b) = (find(old), find(new));
+            if a.as_ref().map(|t| t.enabled) != b.as_ref().map(|t| t.enabled) {
+                o.enabled = None;
+            }
+            if a.as_ref().map(|t| t.min_profit) != b.as_ref().map(|t| t.min_profit) {
+                o.min_profit = None;
+            }
+            if a.as_ref().map(|t| t.amount_range) != b.as_ref().map(|t| t.amount_range) {
+                o.notional_range = None;
+            }
+        }
+    }
+}
+
+/// Sections applied by [`Engine::reload_config`].
+const RELOADABLE_SECTIONS: &[&str] = &["strategy", "fees", "flash_loan"];
+
+/// What [`Engine::reload_config`] did with a new config file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigReload {
+    /// Reloadable sections that changed and are now live.
+    pub applied: Vec<&'static str>,
+    /// Sections that changed but only take effect on restart.
+    pub needs_restart: Vec<&'static str>,
+}
+
+/// Shared dependencies of the arbitrage engine.
+pub struct Engine {
+    /// Current config and what is derived from it, see [`Engine::config`].
+    live: RwLock<LiveConfig>,
+    /// Publishes every new config, see [`Engine::subscribe_config`].
+    config_updates: watch::Sender<Arc<Config>>,
+    pub keypair: Keypair,
+    pub pubkey: Pubkey,
+    /// Client for reading chain state (quotes, nonce, ALTs, simulation).
+    pub rpc_client: Arc<RpcClient>,
+    /// Client of the primary submit endpoint, see [`SubmitEndpoints::primary`].
+    pub submit_client: Arc<RpcClient>,
+    /// Every submit endpoint, see [`crate::broadcast_transaction`].
+    pub submit_endpoints: SubmitEndpoints,
+    /// Jito block engine and tip accounts, used when `[jito]` is enabled.
+    pub block_engine: BlockEngine,
+    pub jupiter_client: JupiterSwapApiClient,
+    pub jupiter_endpoint: String,
+    /// Primary nonce account, `[strategy] nonce_account_pubkey` (default if unset in blockhash mode).
+    pub nonce_addr: Pubkey,
+    /// Every nonce account with its current value and lease, see [`Engine::acquire_nonce`].
+    pub nonces: NoncePool,
+    /// Latest blockhash with `transaction_lifetime = "blockhash"`, see [`Engine::acquire_lifetime`].
+    pub blockhash: BlockhashCache,
+    /// Locally decoded AMM pools (empty unless `[pools]` is enabled).
+    pub pools: PoolCache,
+    /// Opportunity and trade ledger (records nothing unless `[ledger]` is enabled).
+    pub ledger: Ledger,
+    /// Prometheus metrics, served on `/metrics` when `[metrics]` is enabled.
+    pub metrics: Metrics,
+    /// Decimals, token program and symbol per mint.
+    pub tokens: TokenRegistry,
+    /// SOL prices and known token accounts used by [`crate::trade_cost`].
+    pub costs: CostModel,
+    /// Compute unit price sampled from recent prioritization fees, see `[priority_fee]`.
+    pub priority_fees: PriorityFeeEstimator,
+    /// Compute unit limits learned per route, see `[compute_units]`.
+    pub compute_units: ComputeUnitCache,
+    /// Quote providers by name; always contains [`DEFAULT_QUOTE_PROVIDER`].
+    quote_providers: HashMap<String, Arc<dyn QuoteProvider>>,
+}
+
+impl Engine {
+    /// Build an engine from a parsed config and an already loaded signer.
+    pub fn new(config: Config, keypair: Keypair) -> Result<Self, anyhow::Error> {
+        // Blockhash mode needs no nonce account
+        let mut nonce_accounts = Vec::new();
+        if config.strategy.tx_lifetime == TxLifetimeMode::Nonce || !config.strategy.nonce_account.is_empty() {
+            nonce_accounts.push(Pubkey::from_str(&config.strategy.nonce_account).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid strategy.nonce_account_pubkey {:?}: {}",
+                    config.strategy.nonce_account,
+                    e
+                )
+            })?);
+        }
+        for (i, account) in config.strategy.nonce_pool.iter().enumerate() {
+            let account = Pubkey::from_str(account)
+                .map_err(|e| anyhow::anyhow!("Invalid strategy.nonce_account_pubkeys[{}] {:?}: {}", i, account, e))?;
+            if !nonce_accounts.contains(&account) {
+                nonce_accounts.push(account);
+            }
+        }
+        let nonce_addr = nonce_accounts.first().copied().unwrap_or_default();
+
+        let rpc_client = Arc::new(RpcClient::new_with_commitment(
+            config.node.rpc_url.clone(),
+            CommitmentConfig::processed(),
+        ));
+        let submit_endpoints = SubmitEndpoints::new(&config.node, &config.submit);
+        let submit_client = submit_endpoints.primary().client.clone();
+        let block_engine = BlockEngine::new(&config.jito, &config.services.jito_api_key)?;
+
+        let jupiter_endpoint = config.services.jupiter_endpoint.clone();
+        let jupiter_client = JupiterSwapApiClient::new(jupiter_endpoint.clone());
+
+        let flash_loan_contexts = build_flash_loan_contexts(&config.flash_loan).map(Arc::new);
+        let pools = PoolCache::new(&config.pools)?;
+        let ledger = Ledger::new(&config.ledger)?;
+        let tokens = TokenRegistry::new(&config.tokens, rpc_client.clone());
+
+        let mut quote_providers: HashMap<String, Arc<dyn QuoteProvider>> = HashMap::new();
+        quote_providers.insert(
+            DEFAULT_QUOTE_PROVIDER.to_string(),
+            Arc::new(JupiterSwapApiClient::new(jupiter_endpoint.clone())),
+        );
+        for (name, provider_config) in &config.quote_providers {
+            quote_providers.insert(name.clone(), build_quote_provider(provider_config));
+        }
+        check_quote_providers(&config, &quote_providers)?;
+
+        let config = Arc::new(config);
+        let (config_updates, _) = watch::channel(config.clone());
+
+        Ok(Self {
+            pubkey: keypair.pubkey(),
+            keypair,
+            rpc_client,
+            submit_client,
+            submit_endpoints,
+            block_engine,
+            jupiter_client,
+            jupiter_endpoint,
+            nonce_addr,
+            nonces: NoncePool::new(nonce_accounts),
+            blockhash: BlockhashCache::new(),
+            pools,
+            ledger,
+            metrics: Metrics::new(),
+            tokens,
+            costs: CostModel::new(),
+            priority_fees: PriorityFeeEstimator::new(),
+            compute_units: ComputeUnitCache::new(),
+            quote_providers,
+            live: RwLock::new(LiveConfig {
+                file: config.clone(),
+                config,
+                flash_loan_contexts,
+                overrides: RuntimeOverrides::default(),
+            }),
+            config_updates,
+        })
+    }
+
+    /// Build an engine from a parsed config, loading the signer from `signer_keypair_path`.
+    pub fn from_config(config: Config) -> Result<Self, anyhow::Error> {
+        let keypair = load_keypair(&config.node.keypair_path)?;
+        Self::new(config, keypair)
+    }
+
+    /// Current config. Cheap to call; hold the snapshot when reading several values
+    /// that must be consistent with each other.
+    pub fn config(&self) -> Arc<Config> {
+        self.live.read().unwrap().config.clone()
+    }
+
+    /// Receiver that sees every config made current by [`Engine::update_config`],
+    /// [`Engine::override_config`] or [`Engine::reload_config`], e.g. to resubscribe a
+    /// stream when instruments change.
+    pub fn subscribe_config(&self) -> watch::Receiver<Arc<Config>> {
+        self.config_updates.subscribe()
+    }
+
+    /// Apply `change` to a copy of the config and make it current if it succeeds.
+    ///
+    /// Flash loan contexts are rebuilt when `[flash_loan]` changed. Clients, signer,
+    /// nonce account, pools and quote providers were built from the original config
+    /// and are not rebuilt. A reload of a changed section replaces the change; use
+    /// [`Engine::override_config`] for changes that must survive reloads.
+    pub fn update_config(
+        &self,
+        change: impl FnOnce(&mut Config) -> Result<(), anyhow::Error>,
+    ) -> Result<Arc<Config>, anyhow::Error> {
+        let mut live = self.live.write().unwrap();
+        let mut next = Config::clone(&live.config);
+        change(&mut next)?;
+        self.make_current(&mut live, next)
+    }
+
+    /// Change the runtime overrides and apply them to the live config. They are
+    /// re-applied after every [`Engine::reload_config`], see [`RuntimeOverrides`].
+    pub fn override_config(&self, change: impl FnOnce(&mut RuntimeOverrides)) -> Result<Arc<Config>, anyhow::Error> {
+        let mut live = self.live.write().unwrap();
+        change(&mut live.overrides);
+        let mut next = Config::clone(&live.config);
+        live.overrides.apply(&mut next);
+        self.make_current(&mut live, next)
+    }
+
+    fn make_current(&self, live: &mut LiveConfig, next: Config) -> Result<Arc<Config>, anyhow::Error> {
+        check_quote_providers(&next, &self.quote_providers)?;
+        if next.flash_loan != live.config.flash_loan {
+            live.flash_loan_contexts = build_flash_loan_contexts(&next.flash_loan).map(Arc::new);
+        }
+        live.config = Arc::new(next);
+        self.config_updates.send_replace(live.config.clone());
+        Ok(live.config.clone())
+    }
+
+    /// Apply a re-read config file: `[strategy]`, `[fees]` and `[flash_loan]` are
+    /// swapped in together; changes to other sections are reported as needing a restart.
+    /// The file is compared with the previous file, and runtime overrides are applied on
+    /// top unless the file changed the same setting.
+    ///
+    /// Rejected (nothing applied) if the new config does not pass [`Config::validate`], or
+    /// if `[connection]`, the nonce accounts or the transaction lifetime changed, since the
+    /// signer, clients, nonce state and background tasks were set up for them.
+    pub fn reload_config(&self, next: Config) -> Result<ConfigReload, anyhow::Error> {
+        next.check()?;
+        let mut live = self.live.write().unwrap();
+        let current = live.file.clone();
+        let rejected = connection_changes(&current, &next);
+        if !rejected.is_empty() {
+            return Err(anyhow::anyhow!(
+                "{} cannot change while the bot is running; restart to apply",
+                rejected.join(", ")
+            ));
+        }
+
+        let changed = changed_sections(&current, &next);
+        let mut reload = ConfigReload::default();
+        for section in changed {
+            if RELOADABLE_SECTIONS.contains(&section) {
+                reload.applied.push(section);
+            } else {
+                reload.needs_restart.push(section);
+            }
+        }
+        if !reload.applied.is_empty() {
+            let mut overrides = live.overrides.clone();
+            overrides.forget_changed(&current.strategy, &next.strategy);
+            let mut config = Config::clone(&live.config);
+            let mut file = Config::clone(&current);
+            for target in [&mut config, &mut file] {
+                target.strategy = next.strategy.clone();
+                target.tx_cost = next.tx_cost.clone();
+                target.flash_loan = next.flash_loan.clone();
+            }
+            overrides.apply(&mut config);
+            self.make_current(&mut live, config)?;
+            live.file = Arc::new(file);
+            live.overrides = overrides;
+        }
+        Ok(reload)
+    }
+
+    pub fn fees(&self) -> TxCostConfig {
+        self.config().tx_cost.clone()
+    }
+
+    /// Enabled instruments.
+    pub fn base_tokens(&self) -> Vec<BaseTokenConfig> {
+        self.config()
+            .strategy
+            .base_tokens
+            .iter()
+            .filter(|t| t.enabled)
+            .cloned()
+            .collect()
+    }
+
+    /// Whether profitable trades are submitted (`execution_enabled`, pausable at runtime).
+    pub fn execution_enabled(&self) -> bool {
+        self.config().strategy.live_trading
+    }
+
+    /// Quote mint used as the arbitrage target for non-SOL base tokens.
+    pub fn target_token(&self) -> String {
+        self.config()
+            .strategy
+            .quote_mint
+            .clone()
+            .unwrap_or_else(|| WSOL.to_string())
+    }
+
+    /// Flash loan context for `mint`, if flash loans are enabled and a reserve is configured.
+    pub fn flash_loan_context(&self, mint: &str) -> Option<FlashLoanContext> {
+        self.live
+            .read()
+            .unwrap()
+            .flash_loan_contexts
+            .as_ref()
+            .and_then(|ctxs| ctxs.get(mint).cloned())
+    }
+
+    /// Mints with a flash loan reserve; None if flash loans are disabled or no reserve is valid.
+    pub fn flash_loan_mints(&self) -> Option<Vec<String>> {
+        self.live
+            .read()
+            .unwrap()
+            .flash_loan_contexts
+            .as_ref()
+            .map(|ctxs| ctxs.keys().cloned().collect())
+    }
+
+    /// Quote provider for instrument `mint`: its own `quote_provider`, else the strategy default.
+    pub fn quote_provider(&self, mint: &str) -> &dyn QuoteProvider {
+        let config = self.config();
+        let name = config
+            .strategy
+            .base_tokens
+            .iter()
+            .find(|t| t.mint == mint)
+            .and_then(|t| t.quote_provider.as_deref())
+            .unwrap_or(&config.strategy.quote_provider);
+        self.quote_providers
+            .get(name)
+            .or_else(|| self.quote_providers.get(DEFAULT_QUOTE_PROVIDER))
+            .map(Arc::as_ref)
+            .expect("default quote provider is always registered")
+    }
+
+    /// Register (or replace) a named quote provider, e.g. a recorded-fixture provider in tests.
+    pub fn register_quote_provider(&mut self, name: impl Into<String>, provider: Arc<dyn QuoteProvider>) {
+        self.quote_providers.insert(name.into(), provider);
+    }
+
+    /// Store the value of the primary nonce account.
+    pub fn set_nonce(&self, new_nonce: NonceData) {
+        self.nonces.set(&self.nonce_addr, new_nonce);
+    }
+
+    /// Current value of the primary nonce account (default until first read).
+    pub fn get_nonce(&self) -> NonceData {
+        self.nonces.get(&self.nonce_addr).unwrap_or_default()
+    }
+
+    /// Lease a nonce account for one transaction, see [`NoncePool`]. None when every
+    /// account is in use by an in-flight transaction or not read yet.
+    pub fn acquire_nonce(&self) -> Option<NonceLease<'_>> {
+        let lease_timeout = Duration::from_secs(self.config().strategy.nonce_lease_secs);
+        self.nonces.acquire(lease_timeout)
+    }
+
+    /// What the next transaction is signed against: a leased nonce account, or the latest
+    /// blockhash with `[strategy] transaction_lifetime = "blockhash"`. None when every
+    /// nonce account is in use, or no blockhash was fetched in the last `blockhash_max_age_ms`.
+    pub fn acquire_lifetime(&self) -> Option<TxLifetime<'_>> {
+        let config = self.config();
+        match config.strategy.tx_lifetime {
+            TxLifetimeMode::Nonce => self.acquire_nonce().map(TxLifetime::Nonce),
+            TxLifetimeMode::Blockhash => {
+                // A stale one means the refresher is failing; it may expire before landing
+                if self.blockhash.age()? > Duration::from_millis(config.strategy.blockhash_max_age_ms) {
+                    return None;
+                }
+                self.blockhash.get().map(TxLifetime::Blockhash)
+            }
+        }
+    }
+}
+
+/// Every quote provider named in `[strategy]` or an instrument must be registered.
+fn check_quote_providers(
+    config: &Config,
+    quote_providers: &HashMap<String, Arc<dyn QuoteProvider>>,
+) -> Result<(), anyhow::Error> {
+    let selected = std::iter::once((None, &config.strategy.quote_provider)).chain(
+        config
+            .strategy
+            .base_tokens
+            .iter()
+            .filter_map(|t| t.quote_provider.as_ref().map(|p| (Some(&t.mint), p))),
+    );
+    for (mint, name) in selected {
+        if !quote_providers.contains_key(name) {
+            return Err(anyhow::anyhow!(
+                "Unknown quote_provider {:?}{}; define it under [quote_providers.{}]",
+                name,
+                mint.map(|m| format!(" for instrument {}", m)).unwrap_or_default(),
+                name
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Settings the running engine cannot pick up, by TOML path.
+fn connection_changes(current: &Config, next: &Config) -> Vec<&'static str> {
+    let (a, b) = (&current.node, &next.node);
+    let mut changed = Vec::new();
+    if a.keypair_path != b.keypair_path {
+        changed.push("connection.signer_keypair_path");
+    }
+    if a.rpc_url != b.rpc_url {
+        changed.push("connection.rpc_endpoint");
+    }
+    if a.submit_url != b.submit_url {
+        changed.push("connection.submit_endpoint");
+    }
+    if a.geyser_url != b.geyser_url {
+        changed.push("connection.geyser_endpoint");
+    }
+    if a.geyser_token != b.geyser_token {
+        changed.push("connection.geyser_auth_token");
+    }
+    if a.laser_token != b.laser_token {
+        changed.push("connection.laser_token");
+    }
+    if current.strategy.nonce_account != next.strategy.nonce_account {
+        changed.push("strategy.nonce_account_pubkey");
+    }
+    if current.strategy.nonce_pool != next.strategy.nonce_pool {
+        changed.push("strategy.nonce_account_pubkeys");
+    }
+    if current.strategy.tx_lifetime != next.strategy.tx_lifetime {
+        changed.push("strategy.transaction_lifetime");
+    }
+    changed
+}
+
+/// Top-level sections (by TOML name) that differ between two configs.
+fn changed_sections(current: &Config, next: &Config) -> Vec<&'static str> {
+    let sections = [
+        ("dex_api", current.services != next.services),
+        ("strategy", current.strategy != next.strategy),
+        ("fees", current.tx_cost != next.tx_cost),
+        ("flash_loan", current.flash_loan != next.flash_loan),
+        ("priority_fee", current.priority_fee != next.priority_fee),
+        ("compute_units", current.compute_units != next.compute_units),
+        ("submit", current.submit != next.submit),
+        ("jito", current.jito != next.jito),
+        ("simulation", current.simulation != next.simulation),
+        ("landing", current.landing != next.landing),
+        ("ledger", current.ledger != next.ledger),
+        ("metrics", current.metrics != next.metrics),
+        ("admin", current.admin != next.admin),
+        ("hot_reload", current.hot_reload != next.hot_reload),
+        ("sizing", current.sizing != next.sizing),
+        ("pools", current.pools != next.pools),
+        ("tokens", current.tokens != next.tokens),
+        ("quote_providers", current.quote_providers != next.quote_providers),
+    ];
+    sections
+        .into_iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Process-wide engine built from [`crate::CONFIG`].
+/// Panics on first use if the config or keypair cannot be loaded.
+pub static ENGINE: Lazy<Arc<Engine>> = Lazy::new(|| {
+    Arc::new(
+        Engine::from_config(crate::app::config::CONFIG.clone())
+            .unwrap_or_else(|e| panic!("Failed to initialize engine: {}", e)),
+    )
+});
@@ -0,0 +1,290 @@
+//! Multi-leg cycle discovery (base -> A -> B -> ... -> base).
+//!
+//! `simulate_amount_in` covers two-leg cycles through one target. Here every ordered
+//! sequence of distinct intermediate tokens up to `max_cycle_legs` legs is quoted leg
+//! by leg, so triangular routes such as USDC -> SOL -> JitoSOL -> USDC become visible.
+
+use std::str::FromStr;
+use std::time::Instant;
+
+use futures::future::join_all;
+use jupiter_swap_api_client::quote::{QuoteRequest, QuoteResponse};
+use solana_sdk::pubkey::Pubkey;
+use tracing::warn;
+
+use crate::app::config::BaseTokenConfig;
+use crate::engine::discovery::simulate_amount_in::{passes_local_prefilter, write_log};
+use crate::*;
+
+/// A fully quoted cycle for one input amount.
+#[derive(Debug, Clone)]
+pub struct CycleQuote {
+    pub in_amount: u64,
+    /// Base tokens returned to the wallet, net of Token-2022 transfer fees.
+    pub out_amount: u64,
+    /// One quote per leg; leg `i` outputs the input mint of leg `i + 1`.
+    pub legs: Vec<QuoteResponse>,
+    /// Time taken to quote all legs, in microseconds.
+    pub elapsed: u128,
+}
+
+impl CycleQuote {
+    /// Gross profit in raw base-token units.
+    pub fn profit(&self) -> i64 {
+        self.out_amount as i64 - self.in_amount as i64
+    }
+
+    /// Token symbols along the cycle, e.g. "USDC -> WSOL -> JitoSOL -> USDC".
+    pub fn label(&self, tokens: &TokenRegistry) -> String {
+        let mut symbols: Vec<String> = self
+            .legs
+            .iter()
+            .map(|leg| tokens.symbol(&leg.input_mint.to_string()))
+            .collect();
+        if let Some(last) = self.legs.last() {
+            symbols.push(tokens.symbol(&last.output_mint.to_string()));
+        }
+        symbols.join(" -> ")
+    }
+}
+
+/// Intermediate-token sequences for cycles of 3..=`max_legs` legs starting and ending at
+/// `base`, shortest first, truncated to `limit`. Tokens never repeat within a cycle.
+pub fn enumerate_cycles(base: &str, tokens: &[String], max_legs: usize, limit: usize) -> Vec<Vec<String>> {
+    let tokens: Vec<&String> = tokens.iter().filter(|t| t.as_str() != base).collect();
+    let mut cycles = Vec::new();
+    let mut frontier: Vec<Vec<String>> = vec![Vec::new()];
+
+    // A cycle with n intermediates has n + 1 legs.
+    for intermediates in 1..max_legs {
+        let mut next = Vec::new();
+        for prefix in &frontier {
+            for token in &tokens {
+                if prefix.contains(*token) {
+                    continue;
+                }
+                let mut path = prefix.clone();
+                path.push((*token).clone());
+                next.push(path);
+            }
+        }
+        if intermediates >= 2 {
+            for path in &next {
+                if cycles.len() >= limit {
+                    return cycles;
+                }
+                cycles.push(path.clone());
+            }
+        }
+        frontier = next;
+    }
+    cycles
+}
+
+/// Intermediate tokens for 3+ leg cycles: `cycle_tokens`, or `INTEMEDIATE_TOKEN` if empty.
+pub fn cycle_tokens(engine: &Engine) -> Vec<String> {
+    let config = engine.config();
+    let strategy = &config.strategy;
+    if strategy.cycle_tokens.is_empty() {
+        INTEMEDIATE_TOKEN.iter().map(|t| t.to_string()).collect()
+    } else {
+        strategy.cycle_tokens.clone()
+    }
+}
+
+/// `target` label of cycle quotes in the quote metrics: one series for all cycles, as
+/// their number grows with `cycle_tokens` and `max_cycle_legs`.
+pub const CYCLE_QUOTE_TARGET: &str = "cycle";
+
+/// Quote `base -> intermediates... -> base` for `input_amount`, one leg at a time.
+pub async fn quote_cycle(
+    engine: &Engine,
+    input_amount: u64,
+    base: &str,
+    intermediates: &[String],
+) -> Result<CycleQuote, anyhow::Error> {
+    let start = Instant::now();
+    let provider = engine.quote_provider(base);
+
+    let mut hops: Vec<&str> = vec![base];
+    hops.extend(intermediates.iter().map(|t| t.as_str()));
+    hops.push(base);
+
+    let route = intermediates.join(",");
+    let mut legs = Vec::with_capacity(hops.len() - 1);
+    let mut amount = input_amount;
+    for pair in hops.windows(2) {
+        // cycle_tokens come from the config file: a bad mint fails this cycle, not the process
+        let parse = |mint: &str| {
+            Pubkey::from_str(mint).map_err(|e| anyhow::anyhow!("Invalid mint {:?} in cycle {}: {}", mint, route, e))
+        };
+        let (input_mint, output_mint) = match (parse(pair[0]), parse(pair[1])) {
+            (Ok(input_mint), Ok(output_mint)) => (input_mint, output_mint),
+            (Err(e), _) | (_, Err(e)) => {
+                engine.metrics.record_quote(CYCLE_QUOTE_TARGET, false);
+                return Err(e);
+            }
+        };
+        // Same request shape as polling mode: multi-hop allowed, exact quotes.
+        let request = QuoteRequest {
+            amount,
+            input_mint,
+            output_mint,
+            slippage_bps: 0,
+            ..QuoteRequest::default()
+        };
+        let response = match provider.quote(&request).await {
+            Ok(response) => response,
+            Err(e) => {
+                engine.metrics.record_quote(CYCLE_QUOTE_TARGET, false);
+                return Err(e);
+            }
+        };
+        amount = response.out_amount;
+        legs.push(response);
+    }
+    engine.metrics.record_quote(CYCLE_QUOTE_TARGET, true);
+
+    Ok(CycleQuote {
+        in_amount: input_amount,
+        out_amount: amount_after_transfer_fees(&engine.tokens, &legs),
+        legs,
+        elapsed: start.elapsed().as_micros(),
+    })
+}
+
+/// 3+ leg cycles worth quoting for the instrument: [`enumerate_cycles`] over
+/// [`cycle_tokens`], minus those local pools show losing over its amount grid.
+pub fn instrument_cycles(engine: &Engine, base_config: &BaseTokenConfig, mother_token_decimal: u8) -> Vec<Vec<String>> {
+    let mother_token = base_config.mint.as_str();
+    let config = engine.config();
+    let strategy = &config.strategy;
+    if strategy.max_cycle_legs < 3 {
+        return Vec::new();
+    }
+
+    let [from_f, to_f] = base_config.amount_range;
+    let amounts = amount_grid(from_f, to_f, base_config.steps as usize, mother_token_decimal);
+    enumerate_cycles(mother_token, &cycle_tokens(engine), strategy.max_cycle_legs, strategy.max_cycles_per_poll)
+        .into_iter()
+        .filter(|intermediates| {
+            let mut hops = vec![mother_token];
+            hops.extend(intermediates.iter().map(String::as_str));
+            hops.push(mother_token);
+            passes_local_prefilter(engine, &hops, &amounts)
+        })
+        .collect()
+}
+
+/// Quote `cycles` (see [`instrument_cycles`]) for the instrument and return the ones
+/// clearing the transaction cost and `min_profit_quote_units`. In `golden_section` mode
+/// the searches share `quote_budget` cycle quotes.
+pub async fn simulate_cycles(
+    engine: &Engine,
+    base_config: &BaseTokenConfig,
+    mother_token_decimal: u8,
+    mother_token_symbol: &str,
+    cycles: Vec<Vec<String>>,
+    quote_budget: usize,
+) -> Vec<CycleQuote> {
+    if cycles.is_empty() {
+        return Vec::new();
+    }
+    let mother_token = base_config.mint.as_str();
+    let config = engine.config();
+    let sizing = &config.sizing;
+
+    // Transfer fees are read from the registry, so every hop must be resolved first
+    if let Err(e) = engine.tokens.resolve_all(&cycle_tokens(engine)).await {
+        warn!(error = %e, "Could not resolve cycle tokens; transfer fees may be missed");
+    }
+    let [from_f, to_f] = base_config.amount_range;
+    let steps = base_config.steps as usize;
+
+    let sim_start = Instant::now();
+
+    let results: Vec<Result<CycleQuote, anyhow::Error>> = match sizing.mode {
+        SizingMode::Grid => {
+            let mut futures = Vec::new();
+            for &in_amount in &amount_grid(from_f, to_f, steps, mother_token_decimal) {
+                for intermediates in &cycles {
+                    futures.push(quote_cycle(engine, in_amount, mother_token, intermediates));
+                }
+            }
+            join_all(futures).await
+        }
+        SizingMode::GoldenSection => {
+            let (searched, per_cycle_budget) = split_quote_budget(quote_budget, cycles.len());
+            if searched < cycles.len() {
+                warn!(
+                    symbol = %mother_token_symbol,
+                    quote_budget,
+                    cycles = cycles.len(),
+                    searched,
+                    "Quote budget too small for every cycle; searching the first ones only"
+                );
+            }
+            let scale = 10_f64.powf(mother_token_decimal as f64);
+            let (lo, hi) = ((from_f * scale) as u64, (to_f * scale) as u64);
+
+            let searches = cycles[..searched].iter().map(|intermediates| {
+                golden_section_search(lo, hi, steps, per_cycle_budget, sizing.tolerance, move |in_amount| async move {
+                    quote_cycle(engine, in_amount, mother_token, intermediates)
+                        .await
+                        .ok()
+                        .map(|cycle| (cycle.profit(), cycle))
+                })
+            });
+            join_all(searches).await.into_iter().flatten().map(Ok).collect()
+        }
+    };
+    let total_quotes = results.len();
+    let ok_results: Vec<CycleQuote> = results.into_iter().filter_map(|r| r.ok()).collect();
+
+    write_log(&format!(
+        "[CYCLES] ⏱ {} cycles × {} steps for {} took {} ms ({} failed)",
+        cycles.len(),
+        steps,
+        mother_token_symbol,
+        sim_start.elapsed().as_millis(),
+        total_quotes - ok_results.len()
+    ));
+
+    if ok_results.is_empty() {
+        return Vec::new();
+    }
+
+    // Same cost model as simulate_amount_in
+    for leg in ok_results.iter().flat_map(|cycle| &cycle.legs) {
+        engine.costs.observe(leg);
+        engine.priority_fees.observe_quote(leg);
+    }
+    let cost = match trade_cost(engine, mother_token, mother_token_decimal).await {
+        Ok(cost) => cost,
+        Err(e) => {
+            warn!(error = %e, symbol = %mother_token_symbol, "Transaction cost unknown; skipping cycles");
+            return Vec::new();
+        }
+    };
+    let scale = 10_f64.powf(mother_token_decimal as f64);
+    let min_profit_raw = (base_config.min_profit * scale) as i64;
+
+    ok_results
+        .into_iter()
+        .filter(|cycle| {
+            let trade_cost = cost.for_trade(cycle.in_amount, cycle.profit());
+            let net_profit = cycle.profit() - trade_cost.for_amount(cycle.in_amount) as i64;
+            if net_profit - min_profit_raw <= 0 {
+                return false;
+            }
+            write_log(&format!(
+                "[CYCLES] ✅ Profitable: {}: in={:.6} {}, out={:.6} {}, net_profit={:.6} {}",
+                cycle.label(&engine.tokens),
+                cycle.in_amount as f64 / scale, mother_token_symbol,
+                cycle.out_amount as f64 / scale, mother_token_symbol,
+                net_profit as f64 / scale, mother_token_symbol
+            ));
+            true
+        })
+        .collect()
+}
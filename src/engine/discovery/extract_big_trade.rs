@@ -15,6 +15,7 @@ pub struct TokenChange {
 }
 
 pub fn extract_big_trade(
+    engine: &Engine,
     update: &SubscribeUpdate,
 ) -> Option<(
     (String, u8, [f64; 2], u64, f64, String),
@@ -163,7 +164,7 @@ pub fn extract_big_trade(
         .collect();
 
     // --- Collect unique tokens excluding all mother tokens
-    let mother_addrs: HashSet<_> = BASE_TOKENS.iter().map(|f| f.mint.clone()).collect();
+    let mother_addrs: HashSet<_> = engine.base_tokens().iter().map(|f| f.mint.clone()).collect();
 
     let mut owner_changes: Vec<TokenChange> = changes
         .iter()
@@ -181,10 +182,11 @@ pub fn extract_big_trade(
     
     // Replace WSOL with TARGET_TOKEN if WSOL is present
     let wsol_addr = "So11111111111111111111111111111111111111112";
+    let target_token = engine.target_token();
     if unique_tokens.contains(&wsol_addr.to_string()) {
         unique_tokens.retain(|x| x != wsol_addr);
-        if !unique_tokens.contains(&TARGET_TOKEN) {
-            unique_tokens.push(TARGET_TOKEN.clone());
+        if !unique_tokens.contains(&target_token) {
+            unique_tokens.push(target_token.clone());
         }
     }
 
@@ -194,7 +196,7 @@ pub fn extract_big_trade(
             let post_balance = (*meta.post_balances.first().unwrap() as f64) / 1_000_000_000_f64;
 
             owner_changes.push(TokenChange {
-                mint: TARGET_TOKEN.clone(),
+                mint: target_token.clone(),
                 delta: post_balance - pre_balance,
                 owner: account_keys.first().unwrap().to_string(),
                 post_balance,
@@ -204,23 +206,22 @@ pub fn extract_big_trade(
 
         let mother_token_addr: Option<(String, u8, [f64; 2], u64, f64, String)> =
             owner_changes.iter().find_map(|c| {
-                BASE_TOKENS
+                engine
+                    .base_tokens()
                     .iter()
                     .find(|f| c.mint == f.mint && c.delta.abs() > f.threshold)
                     .and_then(|f| {
-                        POPULAR_TOKEN_INFO
-                            .iter()
-                            .find(|t| t.mint == f.mint)
-                            .map(|token_info| {
-                                (
-                                    token_info.mint.to_string(),
-                                    token_info.decimals,
-                                    f.amount_range.clone(),
-                                    f.steps,
-                                    f.min_profit,
-                                    token_info.symbol.to_string(),
-                                )
-                            })
+                        // Instruments are resolved at startup and on reload
+                        engine.tokens.get(&f.mint).map(|token| {
+                            (
+                                token.mint,
+                                token.decimals,
+                                f.amount_range.clone(),
+                                f.steps,
+                                f.min_profit,
+                                token.symbol,
+                            )
+                        })
                     })
             });
 
@@ -0,0 +1,34 @@
+//! Yellowstone gRPC connection shared by the streaming discovery modes.
+
+use tonic::service::Interceptor;
+use yellowstone_grpc_client::{ClientTlsConfig, GeyserGrpcClient};
+
+use crate::Engine;
+
+/// Connect to the engine's `geyser_endpoint`, authenticating with `geyser_auth_token`.
+pub async fn connect_yellowstone(
+    engine: &Engine,
+) -> Result<GeyserGrpcClient<impl Interceptor>, anyhow::Error> {
+    let config = engine.config();
+    let endpoint = config
+        .node
+        .geyser_url
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("yellowstone_grpc_endpoint not configured"))?;
+    let token = config
+        .node
+        .geyser_token
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("yellowstone_grpc_token not configured"))?;
+
+    let client = GeyserGrpcClient::build_from_shared(endpoint.clone())
+        .map_err(|e| anyhow::anyhow!("Yellowstone builder error: {:?}", e))?
+        .tls_config(ClientTlsConfig::new())
+        .map_err(|e| anyhow::anyhow!("Yellowstone TLS config error: {:?}", e))?
+        .x_token(Some(token.clone()))
+        .map_err(|e| anyhow::anyhow!("Yellowstone X-Token error: {:?}", e))?
+        .connect()
+        .await
+        .map_err(|e| anyhow::anyhow!("Yellowstone connection error: {:?}", e))?;
+    Ok(client)
+}
@@ -1,4 +1,14 @@
+pub mod cycles;
 pub mod extract_big_trade;
+pub mod geyser;
+pub mod optimize_size;
+pub mod polling;
+pub mod pool_watch;
 pub mod simulate_amount_in;
+pub use cycles::*;
 pub use extract_big_trade::*;
+pub use geyser::*;
+pub use optimize_size::*;
+pub use polling::*;
+pub use pool_watch::*;
 pub use simulate_amount_in::*;
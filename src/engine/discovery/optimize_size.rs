@@ -0,0 +1,159 @@
+//! Adaptive trade-size search.
+//!
+//! Round-trip profit as a function of input size is roughly unimodal: small trades
+//! don't cover fees, large trades eat their own price impact. A coarse geometric grid
+//! locates the peak, then golden-section search narrows the bracket around it in log
+//! space. Every quote counts against a fixed budget so latency stays predictable.
+
+use std::collections::HashMap;
+use std::future::Future;
+
+use futures::future::join_all;
+
+/// 1 / golden ratio.
+const INV_PHI: f64 = 0.618_033_988_749_895;
+
+/// Points in the smallest coarse pass of a search.
+pub const MIN_COARSE_STEPS: usize = 3;
+
+/// Geometric grid of `steps` raw amounts from `lo` to `hi`, inclusive.
+///
+/// `steps == 1` yields `lo` only, `steps == 0` nothing.
+pub fn geometric_amounts(lo: f64, hi: f64, steps: usize) -> Vec<f64> {
+    match steps {
+        0 => Vec::new(),
+        1 => vec![lo],
+        _ => {
+            let ratio = (hi / lo).powf(1.0 / (steps as f64 - 1.0));
+            (0..steps).map(|i| lo * ratio.powi(i as i32)).collect()
+        }
+    }
+}
+
+/// Share a quote `budget` between `routes` searches: how many of the routes (the first
+/// ones) are searched, and the budget of each. Routes are dropped rather than searched
+/// with less than a [`MIN_COARSE_STEPS`] coarse pass, unless the budget is smaller still.
+pub fn split_quote_budget(budget: usize, routes: usize) -> (usize, usize) {
+    if budget == 0 || routes == 0 {
+        return (0, 0);
+    }
+    let searched = (budget / MIN_COARSE_STEPS).clamp(1, routes);
+    (searched, budget / searched)
+}
+
+/// Evaluations made by a search: profit per raw amount (None = quote failed).
+struct Evaluations<T> {
+    profits: HashMap<u64, Option<i64>>,
+    results: Vec<T>,
+    used: usize,
+}
+
+impl<T> Evaluations<T> {
+    fn record(&mut self, amount: u64, outcome: Option<(i64, T)>) -> Option<i64> {
+        self.used += 1;
+        let profit = outcome.map(|(profit, result)| {
+            self.results.push(result);
+            profit
+        });
+        self.profits.insert(amount, profit);
+        profit
+    }
+}
+
+/// Search `[lo, hi]` (raw units) for the input amount maximizing `evaluate`'s profit.
+///
+/// `evaluate(amount)` returns `Some((profit, result))` or `None` if the quote failed.
+/// A coarse grid of `coarse_steps` (at least 3) points is quoted concurrently, then a
+/// golden-section search refines around the best point until `budget` evaluations are
+/// used or the bracket is narrower than `tolerance` (fraction of the amount). Returns
+/// the result of every successful evaluation.
+pub async fn golden_section_search<T, F, Fut>(
+    lo: u64,
+    hi: u64,
+    coarse_steps: usize,
+    budget: usize,
+    tolerance: f64,
+    evaluate: F,
+) -> Vec<T>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Option<(i64, T)>>,
+{
+    let mut evals = Evaluations {
+        profits: HashMap::new(),
+        results: Vec::new(),
+        used: 0,
+    };
+    if budget == 0 || lo == 0 || hi < lo {
+        return evals.results;
+    }
+
+    // Coarse pass.
+    let mut grid: Vec<u64> = geometric_amounts(lo as f64, hi as f64, coarse_steps.max(MIN_COARSE_STEPS).min(budget))
+        .into_iter()
+        .map(|a| a as u64)
+        .collect();
+    grid.dedup();
+    let outcomes = join_all(grid.iter().map(|&amount| evaluate(amount))).await;
+    for (&amount, outcome) in grid.iter().zip(outcomes) {
+        evals.record(amount, outcome);
+    }
+
+    let best = grid
+        .iter()
+        .enumerate()
+        .filter_map(|(i, a)| evals.profits.get(a).copied().flatten().map(|p| (i, p)))
+        .max_by_key(|&(_, p)| p)
+        .map(|(i, _)| i);
+    let Some(best) = best else {
+        return evals.results;
+    };
+    if evals.used >= budget {
+        return evals.results;
+    }
+
+    // Refine between the best point's neighbours, in log space.
+    let mut ua = (grid[best.saturating_sub(1)] as f64).ln();
+    let mut ub = (grid[(best + 1).min(grid.len() - 1)] as f64).ln();
+    let min_width = (1.0 + tolerance.max(1e-9)).ln();
+
+    let mut uc = ub - INV_PHI * (ub - ua);
+    let mut ud = ua + INV_PHI * (ub - ua);
+    let mut fc = evaluate_cached(&mut evals, &evaluate, budget, uc).await;
+    let mut fd = evaluate_cached(&mut evals, &evaluate, budget, ud).await;
+
+    while evals.used < budget && ub - ua > min_width {
+        if fc >= fd {
+            ub = ud;
+            ud = uc;
+            fd = fc;
+            uc = ub - INV_PHI * (ub - ua);
+            fc = evaluate_cached(&mut evals, &evaluate, budget, uc).await;
+        } else {
+            ua = uc;
+            uc = ud;
+            fc = fd;
+            ud = ua + INV_PHI * (ub - ua);
+            fd = evaluate_cached(&mut evals, &evaluate, budget, ud).await;
+        }
+    }
+
+    evals.results
+}
+
+/// Profit at `exp(u)`, quoting only if that raw amount was not evaluated yet and budget is left.
+async fn evaluate_cached<T, F, Fut>(evals: &mut Evaluations<T>, evaluate: &F, budget: usize, u: f64) -> i64
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = Option<(i64, T)>>,
+{
+    let amount = u.exp().round() as u64;
+    if let Some(profit) = evals.profits.get(&amount) {
+        return profit.unwrap_or(i64::MIN);
+    }
+    if evals.used >= budget {
+        return i64::MIN;
+    }
+    let outcome = evaluate(amount).await;
+    evals.record(amount, outcome).unwrap_or(i64::MIN)
+}
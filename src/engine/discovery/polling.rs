@@ -0,0 +1,223 @@
+//! Continuous polling mode: quote every configured instrument on a timer and submit
+//! the best profitable cycle (two-leg round trip or, if enabled, a longer cycle).
+//! [`poll_instrument`] is also what the pool watcher runs for instruments a pool change affects.
+
+use std::sync::Arc;
+
+use solana_sdk::signature::Signature;
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Duration};
+use tracing::{debug, info, warn};
+
+use crate::app::config::BaseTokenConfig;
+use crate::*;
+
+/// Poll all instruments every `interval_ms`, forever.
+///
+/// Each pass reads the current instruments, so a config reload takes effect on the
+/// next pass; a reloaded `poll_interval_ms` replaces `interval_ms`.
+pub async fn continuous_polling_loop(engine: Arc<Engine>, mut interval_ms: u64) {
+    info!(interval_ms, "Starting continuous polling");
+
+    let mut ticker = interval(Duration::from_millis(interval_ms));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    // Run first poll immediately, then use interval for subsequent polls
+    let mut is_first = true;
+    loop {
+        // Wait for interval only after first poll
+        if !is_first {
+            ticker.tick().await;
+        }
+        is_first = false;
+
+        poll_once(&engine).await;
+
+        let configured = engine.config().strategy.poll_interval_ms;
+        if configured != interval_ms && configured > 0 {
+            info!(from = interval_ms, to = configured, "Polling interval changed");
+            interval_ms = configured;
+            ticker = interval(Duration::from_millis(interval_ms));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // A fresh interval's first tick completes immediately
+            ticker.tick().await;
+        }
+    }
+}
+
+/// Run one polling pass over all instruments.
+///
+/// Submissions are spawned so a slow send does not delay the next pass; their
+/// handles are returned for callers (e.g. tests) that want to wait on them.
+pub async fn poll_once(engine: &Arc<Engine>) -> Vec<JoinHandle<Option<Signature>>> {
+    let mut submissions = Vec::new();
+    for base_token_config in &engine.base_tokens() {
+        submissions.extend(poll_instrument(engine, base_token_config).await);
+    }
+    submissions
+}
+
+/// Target tokens quoted for an instrument: USDC and USDT for SOL, the configured
+/// target token for everything else.
+pub fn polling_targets(engine: &Engine, mother_token: &str, symbol: &str) -> Vec<String> {
+    if symbol == "SOL" || symbol == "WSOL" || mother_token == "So11111111111111111111111111111111111111112" {
+        // For SOL: Use stablecoins (USDC, USDT) as target tokens
+        vec![
+            "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v".to_string(), // USDC
+            "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB".to_string(), // USDT
+        ]
+    } else {
+        // For other tokens: Use configured target token
+        vec![engine.target_token()]
+    }
+}
+
+/// Quote one instrument and submit its best profitable cycle, if any.
+pub async fn poll_instrument(
+    engine: &Arc<Engine>,
+    base_token_config: &BaseTokenConfig,
+) -> Option<JoinHandle<Option<Signature>>> {
+    let mother_token = base_token_config.mint.clone();
+
+    // Decimals from the mint account (cached after the first pass)
+    let token = match engine.tokens.resolve(&mother_token).await {
+        Ok(token) => token,
+        Err(e) => {
+            warn!(mint = %mother_token, error = %e, "Skipping instrument: token metadata unavailable");
+            return None;
+        }
+    };
+    let (decimal, symbol) = (token.decimals, token.symbol.as_str());
+
+    let target_tokens = polling_targets(engine, &mother_token, symbol);
+
+    let min_range = base_token_config.amount_range[0];
+    let max_range = base_token_config.amount_range[1];
+
+    // Round trips, then cycles, share the instrument's quote budget (golden_section mode)
+    let cycles = instrument_cycles(engine, base_token_config, decimal);
+    let routes = target_tokens.len() + cycles.len();
+    let (searched, per_route) = split_quote_budget(instrument_quote_budget(engine, &mother_token), routes);
+    let round_trip_budget = per_route * searched.min(target_tokens.len());
+    let cycle_budget = per_route * searched.saturating_sub(target_tokens.len());
+
+    let sim_start = std::time::Instant::now();
+    let quote_data = simulate_amount_in(
+        engine,
+        mother_token.clone(),
+        decimal,
+        symbol.to_string(),
+        target_tokens,
+        min_range,
+        max_range,
+        base_token_config.steps as usize,
+        base_token_config.min_profit,
+        true, // is_polling_mode = true for polling mode
+        round_trip_budget,
+    )
+    .await;
+    let sim_elapsed_ms = sim_start.elapsed().as_millis();
+
+    if sim_elapsed_ms > 100 {
+        debug!(elapsed_ms = %sim_elapsed_ms, %symbol, "simulate_amount_in slow");
+    }
+
+    // Cycles of three legs and more (no-op unless max_cycle_legs >= 3)
+    let cycle_data = simulate_cycles(engine, base_token_config, decimal, symbol, cycles, cycle_budget).await;
+
+    if quote_data.is_empty() && cycle_data.is_empty() {
+        return None;
+    }
+
+    let count = quote_data.len() + cycle_data.len();
+    engine.metrics.opportunities.with_label_values(&["polling"]).inc_by(count as u64);
+    info!(count, %symbol, "Found profitable opportunities");
+
+    // Full transaction cost in the base token (see simulate_amount_in)
+    let cost = match trade_cost(engine, &mother_token, decimal).await {
+        Ok(cost) => cost,
+        Err(e) => {
+            warn!(error = %e, %symbol, "Transaction cost unknown; skipping opportunity");
+            return None;
+        }
+    };
+    // Ranked net of costs, which grow with the trade size (flash loan fee, priority fee)
+    let net_profit = |cycle: &CycleQuote| {
+        cycle.profit() - cost.for_trade(cycle.in_amount, cycle.profit()).for_amount(cycle.in_amount) as i64
+    };
+
+    let opportunities: Vec<CycleQuote> = quote_data
+        .into_iter()
+        .map(|(in_amount, out_amount, in_res, out_res, elapsed, _)| CycleQuote {
+            in_amount,
+            out_amount,
+            legs: vec![in_res, out_res],
+            elapsed,
+        })
+        .chain(cycle_data)
+        .collect();
+    // Every opportunity found goes to the ledger, not only the one traded
+    for opportunity in &opportunities {
+        let route = opportunity.label(&engine.tokens);
+        engine.ledger.record_opportunity("polling", opportunity, &route, net_profit(opportunity), decimal, None);
+    }
+
+    let best = opportunities.into_iter().max_by_key(net_profit)?;
+    let route = best.label(&engine.tokens);
+
+    let in_human = best.in_amount as f64 / 10_f64.powf(decimal as f64);
+    let out_human = best.out_amount as f64 / 10_f64.powf(decimal as f64);
+    let profit_human = best.profit() as f64 / 10_f64.powf(decimal as f64);
+    info!(
+        %symbol,
+        %route,
+        in = %in_human,
+        out = %out_human,
+        profit = %profit_human,
+        "Best opportunity"
+    );
+
+    let min_profit = base_token_config.min_profit;
+
+    let cost = cost.for_trade(best.in_amount, best.profit());
+    let tx_cost_raw = cost.for_amount(best.in_amount);
+    let net_profit_raw = best.profit() - tx_cost_raw as i64;
+    let total_tx_cost_in_token = tx_cost_raw as f64 / 10_f64.powf(decimal as f64);
+    let real_profit = net_profit_raw as f64 / 10_f64.powf(decimal as f64);
+
+    if real_profit < min_profit || !engine.execution_enabled() {
+        return None;
+    }
+
+    // Check if flash loan is available for this token
+    let compute_unit_price = cost.plan.compute_unit_price;
+    let engine = engine.clone();
+    if let Some(flash_ctx) = engine.flash_loan_context(&mother_token) {
+        info!(
+            %symbol,
+            %route,
+            in = %in_human,
+            out = %out_human,
+            real_profit = %real_profit,
+            tx_cost = %total_tx_cost_in_token,
+            min_profit = %min_profit,
+            "Submitting flash-loan trade"
+        );
+        Some(tokio::spawn(async move {
+            submit_flash_loan_cycle(&engine, best.legs, min_profit, decimal, compute_unit_price, &flash_ctx).await
+        }))
+    } else {
+        info!(
+            %symbol,
+            %route,
+            in = %in_human,
+            out = %out_human,
+            real_profit = %real_profit,
+            tx_cost = %total_tx_cost_in_token,
+            min_profit = %min_profit,
+            "Submitting trade"
+        );
+        Some(tokio::spawn(async move {
+            submit_cycle_trade(&engine, best.legs, min_profit, decimal, compute_unit_price, "polling").await
+        }))
+    }
+}
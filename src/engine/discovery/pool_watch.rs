@@ -0,0 +1,156 @@
+//! Pool watcher mode: stream the `[pools]` state, vault and config accounts over
+//! Yellowstone and re-evaluate every affected instrument whenever one of them changes.
+//!
+//! Unlike the big-trades monitor, which only sees transactions touching a base mint,
+//! this reacts to a price move on a watched pool from any source. Changes are debounced
+//! by `[pools] watch_debounce_ms` and evaluated with the polling pipeline, so the local
+//! pool prefilter runs on the freshly updated state before Jupiter is asked.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use futures::StreamExt;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration};
+use tracing::{debug, error, info};
+use yellowstone_grpc_proto::geyser::{subscribe_update::UpdateOneof, SubscribeRequest};
+
+use crate::*;
+
+/// Watch the configured pools forever, reconnecting on stream errors.
+pub async fn run_pool_watcher(engine: Arc<Engine>) -> Result<(), anyhow::Error> {
+    if !engine.pools.enabled() {
+        return Err(anyhow::anyhow!(
+            "pool_watch_enabled requires [pools] enabled with at least one address"
+        ));
+    }
+    let config = engine.config();
+    if config.node.geyser_url.is_none() || config.node.geyser_token.is_none() {
+        return Err(anyhow::anyhow!(
+            "pool_watch_enabled requires geyser_endpoint and geyser_auth_token"
+        ));
+    }
+    // Vault and config accounts are only known once the pools are decoded
+    if engine.pools.is_empty() {
+        refresh_pools(&engine).await?;
+    }
+
+    let (changes_tx, changes_rx) = unbounded_channel();
+    tokio::spawn(evaluate_changes(engine.clone(), changes_rx));
+
+    let mut first_attempt = true;
+    loop {
+        if !first_attempt {
+            engine.metrics.yellowstone_reconnects.with_label_values(&["pool_watch"]).inc();
+        }
+        first_attempt = false;
+        let mut client = match connect_yellowstone(&engine).await {
+            Ok(client) => client,
+            Err(e) => {
+                error!(error = ?e, "Pool watcher connection failed");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+
+        let request = SubscribeRequest {
+            accounts: pool_accounts_filters(&engine.pools),
+            commitment: Some(0),
+            ..SubscribeRequest::default()
+        };
+        let (_sink, mut stream) = match client.subscribe_with_request(Some(request)).await {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                error!(error = ?e, "Pool watcher subscribe error");
+                sleep(Duration::from_secs(5)).await;
+                continue;
+            }
+        };
+        info!(
+            pools = engine.pools.len(),
+            accounts = engine.pools.watched_accounts().len(),
+            "Pool watcher subscribed"
+        );
+
+        while let Some(message) = stream.next().await {
+            let update = match message {
+                Ok(update) => update,
+                Err(e) => {
+                    error!(error = ?e, "Pool watcher stream error");
+                    break;
+                }
+            };
+            let Some(UpdateOneof::Account(account)) = &update.update_oneof else {
+                continue;
+            };
+            if !apply_account_update(&engine.pools, account) {
+                continue;
+            }
+            let Some(address) = account
+                .account
+                .as_ref()
+                .and_then(|a| Pubkey::try_from(a.pubkey.as_slice()).ok())
+            else {
+                continue;
+            };
+            for mints in engine.pools.mints_affected_by(&address) {
+                let _ = changes_tx.send(mints);
+            }
+        }
+        info!("Pool watcher stream ended; reconnecting");
+    }
+}
+
+/// Debounce pool changes and re-evaluate the affected instruments, one batch at a time.
+async fn evaluate_changes(engine: Arc<Engine>, mut changes: UnboundedReceiver<(Pubkey, Pubkey)>) {
+    let debounce = Duration::from_millis(engine.config().pools.watch_debounce_ms);
+    while let Some(first) = changes.recv().await {
+        sleep(debounce).await;
+        let mut changed = vec![first];
+        while let Ok(mints) = changes.try_recv() {
+            changed.push(mints);
+        }
+        changed.sort();
+        changed.dedup();
+        debug!(pools = changed.len(), "Re-evaluating after pool changes");
+        evaluate_pool_changes(&engine, &changed).await;
+    }
+}
+
+/// Re-evaluate every instrument a change to pools trading the `changed` mint pairs can
+/// affect, returning the spawned submissions.
+///
+/// A pool affects an instrument if it trades the instrument's mint (every leg of a
+/// two-leg cycle does), or, with `max_cycle_legs >= 3`, if both its mints are cycle
+/// tokens (the middle legs of longer cycles).
+pub async fn evaluate_pool_changes(
+    engine: &Arc<Engine>,
+    changed: &[(Pubkey, Pubkey)],
+) -> Vec<JoinHandle<Option<Signature>>> {
+    let cycle_mints: HashSet<Pubkey> = if engine.config().strategy.max_cycle_legs >= 3 {
+        cycle_tokens(engine)
+            .iter()
+            .filter_map(|t| Pubkey::from_str(t).ok())
+            .collect()
+    } else {
+        HashSet::new()
+    };
+
+    let mut submissions = Vec::new();
+    for base_token_config in &engine.base_tokens() {
+        let Ok(mint) = Pubkey::from_str(&base_token_config.mint) else {
+            continue;
+        };
+        let affected = changed.iter().any(|(a, b)| {
+            *a == mint || *b == mint || (cycle_mints.contains(a) && cycle_mints.contains(b))
+        });
+        if affected {
+            submissions.extend(poll_instrument(engine, base_token_config).await);
+        }
+    }
+    submissions
+}
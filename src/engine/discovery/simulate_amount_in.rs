@@ -1,3 +1,4 @@
+use std::str::FromStr;
 use std::time::Instant;
 use std::fs::OpenOptions;
 use std::io::Write;
@@ -5,6 +6,8 @@ use std::sync::Mutex;
 
 use futures::future::join_all;
 use jupiter_swap_api_client::quote::QuoteResponse;
+use solana_sdk::pubkey::Pubkey;
+use tracing::warn;
 
 use crate::*;
 
@@ -12,7 +15,7 @@ use crate::*;
 static LOG_MUTEX: Mutex<()> = Mutex::new(());
 
 /// Write a log message to logs.txt (thread-safe, opens file in append mode for each write)
-fn write_log(message: &str) {
+pub(crate) fn write_log(message: &str) {
     let _guard = LOG_MUTEX.lock().unwrap();
     if let Ok(mut file) = OpenOptions::new()
         .create(true)
@@ -27,7 +30,65 @@ fn write_log(message: &str) {
     // Silently fail if file can't be opened - don't spam console
 }
 
+/// Geometric grid of `steps` raw input amounts from `from_f` to `to_f` (token units).
+/// A single step quotes `from_f` only.
+pub fn amount_grid(from_f: f64, to_f: f64, steps: usize, decimal: u8) -> Vec<u64> {
+    geometric_amounts(from_f, to_f, steps)
+        .into_iter()
+        .map(|amount| (amount * 10_f64.powf(decimal as f64)) as u64)
+        .collect()
+}
+
+/// Local pool prefilter for the cycle `hops` (mint strings, first == last): false if
+/// local pool math prices every leg and the round trip loses at all `amounts`.
+pub(crate) fn passes_local_prefilter(engine: &Engine, hops: &[&str], amounts: &[u64]) -> bool {
+    if !engine.pools.enabled() {
+        return true;
+    }
+    let Ok(mints) = hops.iter().map(|h| Pubkey::from_str(h)).collect::<Result<Vec<_>, _>>() else {
+        return true;
+    };
+    let passes = engine.pools.passes_prefilter(&mints, amounts);
+    if !passes {
+        let symbols: Vec<String> = hops.iter().map(|h| engine.tokens.symbol(h)).collect();
+        write_log(&format!("[POOLS] ⏭ Skipping {}: unprofitable on local pools", symbols.join(" -> ")));
+    }
+    passes
+}
+
+/// Quotes per poll for the instrument `mint` in `golden_section` mode: its
+/// `quote_budget`, else `[sizing] max_quotes`.
+pub fn instrument_quote_budget(engine: &Engine, mint: &str) -> usize {
+    engine
+        .base_tokens()
+        .iter()
+        .find(|t| t.mint == mint)
+        .and_then(|t| t.quote_budget)
+        .unwrap_or(engine.config().sizing.max_quotes)
+}
+
+/// One round-trip quote through `output_token`, timed.
+async fn quote_round_trip(
+    engine: &Engine,
+    in_amount: u64,
+    mother_token: &str,
+    output_token: &str,
+    is_polling: bool,
+) -> Result<(u64, u64, QuoteResponse, QuoteResponse, u128, String), anyhow::Error> {
+    let start = Instant::now();
+    let quoted = if is_polling {
+        get_quote_polling(engine, in_amount, mother_token, output_token).await
+    } else {
+        get_quote_big_trade(engine, in_amount, mother_token, output_token).await
+    };
+    engine.metrics.record_quote(output_token, quoted.is_ok());
+    let (in_amount, out_amount, in_res, out_res) = quoted?;
+    let elapsed = start.elapsed().as_micros();
+    Ok((in_amount, out_amount, in_res, out_res, elapsed, output_token.to_string()))
+}
+
 pub async fn simulate_amount_in(
+    engine: &Engine,
     mother_token: String,
     mother_token_decimal: u8,
     mother_token_symbol: String,
@@ -37,51 +98,87 @@ pub async fn simulate_amount_in(
     steps: usize,
     min_profit_amount: f64,
     is_polling_mode: bool,
+    quote_budget: usize,
 ) -> Vec<(u64, u64, QuoteResponse, QuoteResponse, u128, String)> {
     let sim_start = Instant::now();
-    let ratio = (to_f / from_f).powf(1.0 / (steps as f64 - 1.0));
 
     let min_profit_amount = (min_profit_amount * 10_f64.powf(mother_token_decimal as f64)) as u64;
 
-    let amounts: Vec<u64> = (0..steps)
-        .map(|i| {
-            let sol = from_f * ratio.powi(i as i32);
-            (sol * 10_f64.powf(mother_token_decimal as f64)) as u64
-        })
-        .collect();
+    // Transfer fees are read from the registry, so every leg's mint must be resolved first
+    let mut mints = target_tokens.clone();
+    mints.push(mother_token.clone());
+    if let Err(e) = engine.tokens.resolve_all(&mints).await {
+        warn!(error = %e, "Could not resolve target tokens; transfer fees may be missed");
+    }
 
-    // Create futures for all combinations of amounts and target tokens
-    let mut futures = Vec::new();
-    for &in_amount in &amounts {
-        for output_token in &target_tokens {
-            let mother_token = mother_token.clone();
-            let output_token = output_token.clone();
-            let is_polling = is_polling_mode;
-            futures.push(async move {
-                let start = Instant::now();
-                let quote_result = if is_polling {
-                    get_quote_polling(in_amount, &mother_token, &output_token).await
-                } else {
-                    get_quote_big_trade(in_amount, &mother_token, &output_token).await
-                };
-                match quote_result {
-                    Ok((in_amount, out_amount, in_res, out_res)) => {
-                        let elapsed = start.elapsed().as_micros();
-                        Ok::<_, anyhow::Error>((in_amount, out_amount, in_res, out_res, elapsed, output_token))
-                    }
-                    Err(e) => {
-                        // Silently fail for individual quotes - we'll log summary
-                        Err(e)
+    // Only ask Jupiter about targets that look profitable on local pools. Polling only:
+    // in big-trade mode the pool accounts may still lag the triggering transaction.
+    let target_tokens: Vec<String> = if is_polling_mode {
+        let probe = amount_grid(from_f, to_f, steps, mother_token_decimal);
+        target_tokens
+            .into_iter()
+            .filter(|t| passes_local_prefilter(engine, &[mother_token.as_str(), t.as_str(), mother_token.as_str()], &probe))
+            .collect()
+    } else {
+        target_tokens
+    };
+
+    let config = engine.config();
+    let sizing = &config.sizing;
+    let results: Vec<Result<(u64, u64, QuoteResponse, QuoteResponse, u128, String), anyhow::Error>> =
+        match sizing.mode {
+            SizingMode::Grid => {
+                let amounts = amount_grid(from_f, to_f, steps, mother_token_decimal);
+
+                // Create futures for all combinations of amounts and target tokens
+                let mut futures = Vec::new();
+                for &in_amount in &amounts {
+                    for output_token in &target_tokens {
+                        futures.push(quote_round_trip(
+                            engine,
+                            in_amount,
+                            &mother_token,
+                            output_token,
+                            is_polling_mode,
+                        ));
                     }
                 }
-            });
-        }
-    }
+                join_all(futures).await
+            }
+            SizingMode::GoldenSection => {
+                // `quote_budget` is shared across targets; those it can't cover are not searched
+                let (searched, per_target_budget) = split_quote_budget(quote_budget, target_tokens.len());
+                if searched < target_tokens.len() {
+                    warn!(
+                        symbol = %mother_token_symbol,
+                        quote_budget,
+                        targets = target_tokens.len(),
+                        searched,
+                        "Quote budget too small for every target; searching the first ones only"
+                    );
+                }
+                let scale = 10_f64.powf(mother_token_decimal as f64);
+                let (lo, hi) = ((from_f * scale) as u64, (to_f * scale) as u64);
 
-    let results: Vec<Result<(u64, u64, QuoteResponse, QuoteResponse, u128, String), anyhow::Error>> =
-        join_all(futures).await;
+                let searches = target_tokens[..searched].iter().map(|output_token| {
+                    golden_section_search(lo, hi, steps, per_target_budget, sizing.tolerance, {
+                        let mother_token = &mother_token;
+                        move |in_amount| async move {
+                            quote_round_trip(engine, in_amount, mother_token, output_token, is_polling_mode)
+                                .await
+                                .ok()
+                                .map(|r| (r.1 as i64 - r.0 as i64, r))
+                        }
+                    })
+                });
+                join_all(searches).await.into_iter().flatten().map(Ok).collect()
+            }
+        };
 
-    let sim_elapsed_ms = sim_start.elapsed().as_millis();
+    let sim_elapsed = sim_start.elapsed();
+    let mode = if is_polling_mode { "polling" } else { "big_trade" };
+    engine.metrics.simulate_latency.with_label_values(&[mode]).observe(sim_elapsed.as_secs_f64());
+    let sim_elapsed_ms = sim_elapsed.as_millis();
     write_log(&format!(
         "[SIMULATE] ⏱ simulate_amount_in took {} ms ({} steps × {} targets)",
         sim_elapsed_ms,
@@ -90,8 +187,17 @@ pub async fn simulate_amount_in(
     ));
 
     let total_quotes = results.len();
-    let ok_results: Vec<(u64, u64, QuoteResponse, QuoteResponse, u128, String)> =
-        results.into_iter().filter_map(|r| r.ok()).collect();
+    // out_amount is what reaches the wallet, net of Token-2022 transfer fees on either leg
+    let ok_results: Vec<(u64, u64, QuoteResponse, QuoteResponse, u128, String)> = results
+        .into_iter()
+        .filter_map(|r| r.ok())
+        .map(|(in_amount, _, in_res, out_res, elapsed, target)| {
+            let legs = [in_res, out_res];
+            let out_amount = amount_after_transfer_fees(&engine.tokens, &legs);
+            let [in_res, out_res] = legs;
+            (in_amount, out_amount, in_res, out_res, elapsed, target)
+        })
+        .collect();
     
     let successful_quotes = ok_results.len();
     let failed_quotes = total_quotes - successful_quotes;
@@ -104,10 +210,24 @@ pub async fn simulate_amount_in(
         ));
     }
 
-    // Calculate actual transaction cost in raw token units
-    // Formula: (base_tx_fee + third_party_fee) * sol_price_usdc
-    let total_tx_cost_usdc = crate::engine::runtime::calculate_tx_cost_usdc(&FEES).await;
-    let total_tx_cost = (total_tx_cost_usdc * 10_f64.powf(mother_token_decimal as f64)) as i64;
+    if ok_results.is_empty() {
+        return Vec::new();
+    }
+
+    // Full cost of the transaction that would be submitted, in raw base-token units.
+    // Legs through WSOL price SOL in the base token for free; their pools are sampled
+    // for the priority fee estimate.
+    for leg in ok_results.iter().flat_map(|(_, _, in_res, out_res, _, _)| [in_res, out_res]) {
+        engine.costs.observe(leg);
+        engine.priority_fees.observe_quote(leg);
+    }
+    let cost = match trade_cost(engine, &mother_token, mother_token_decimal).await {
+        Ok(cost) => cost,
+        Err(e) => {
+            warn!(error = %e, symbol = %mother_token_symbol, "Transaction cost unknown; skipping opportunities");
+            return Vec::new();
+        }
+    };
     
     // Log all trades (both profitable and unprofitable) and filter profitable ones
     let mut profitable_trades: Vec<(u64, u64, QuoteResponse, QuoteResponse, u128, String)> = Vec::new();
@@ -115,16 +235,14 @@ pub async fn simulate_amount_in(
     
     for (in_amount, out_amount, in_res, out_res, elapsed, target_token) in ok_results {
         let gross_profit = out_amount as i64 - in_amount as i64;
-        // Deduct actual transaction costs (base fee + third party fee + priority fee)
+        // Deduct the transaction cost (fees, tip, rent and flash loan fee), with the
+        // priority fee capped to what this trade can afford
+        let total_tx_cost = cost.for_trade(in_amount, gross_profit).for_amount(in_amount) as i64;
         let net_profit = gross_profit - total_tx_cost;
         let profit_after_min = net_profit - min_profit_amount as i64;
         
         // Find target token symbol for better logging
-        let target_symbol = POPULAR_TOKEN_INFO
-            .iter()
-            .find(|t| t.mint == target_token)
-            .map(|t| t.symbol)
-            .unwrap_or("UNKNOWN");
+        let target_symbol = engine.tokens.symbol(&target_token);
         
         // Convert amounts to human-readable format
         let in_human = in_amount as f64 / 10_f64.powf(mother_token_decimal as f64);
@@ -0,0 +1,181 @@
+//! Submission fan-out over several endpoints.
+//!
+//! Every signed transaction is sent to the `[submit] endpoints` in parallel (all enabled
+//! ones, or `fanout` of them picked by weight), without preflight simulation, and the
+//! submission goes on as soon as one endpoint accepts it. Per endpoint, the metrics record how long
+//! it took to accept a transaction, how often it failed, and how often it was the first
+//! to accept one — overall and among transactions that landed. The copies are identical,
+//! so the chain cannot tell which one landed; the first endpoint to accept a landed
+//! transaction is credited with it.
+
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use futures::stream::{FuturesUnordered, StreamExt};
+use solana_client::client_error::Result as ClientResult;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, signature::Signature, transaction::VersionedTransaction};
+use solana_transaction_status_client_types::UiTransactionEncoding;
+use tracing::{debug, warn};
+
+use crate::app::config::{NodeConfig, SubmitConfig};
+use crate::Engine;
+
+/// Endpoint name used for `[connection] submit_endpoint` when `[submit] endpoints` is empty.
+pub const DEFAULT_SUBMIT_ENDPOINT: &str = "default";
+
+/// One submit endpoint and its client.
+pub struct SubmitEndpoint {
+    pub name: String,
+    pub url: String,
+    pub weight: u32,
+    pub client: Arc<RpcClient>,
+}
+
+/// The configured submit endpoints and the weighted round-robin state used with `fanout`.
+pub struct SubmitEndpoints {
+    endpoints: Vec<SubmitEndpoint>,
+    fanout: usize,
+    /// Smooth weighted round-robin credit per endpoint.
+    credit: Mutex<Vec<i64>>,
+}
+
+impl SubmitEndpoints {
+    pub fn new(node: &NodeConfig, config: &SubmitConfig) -> Self {
+        let endpoint = |name: &str, url: &str, weight| SubmitEndpoint {
+            name: name.to_string(),
+            url: url.to_string(),
+            weight,
+            client: Arc::new(RpcClient::new_with_commitment(url.to_string(), CommitmentConfig::processed())),
+        };
+        let endpoints: Vec<SubmitEndpoint> = if config.endpoints.is_empty() {
+            vec![endpoint(DEFAULT_SUBMIT_ENDPOINT, &node.submit_url, 1)]
+        } else {
+            config.endpoints.iter().map(|e| endpoint(&e.name, &e.url, e.weight)).collect()
+        };
+        Self {
+            credit: Mutex::new(vec![0; endpoints.len()]),
+            endpoints,
+            fanout: config.fanout,
+        }
+    }
+
+    pub fn endpoints(&self) -> &[SubmitEndpoint] {
+        &self.endpoints
+    }
+
+    /// First enabled endpoint (the only one without `[submit] endpoints`).
+    pub fn primary(&self) -> &SubmitEndpoint {
+        self.endpoints
+            .iter()
+            .find(|e| e.weight > 0)
+            .unwrap_or(&self.endpoints[0])
+    }
+
+    /// Endpoints the next transaction goes to: every enabled one, or `fanout` of them
+    /// chosen by smooth weighted round-robin, so over many transactions each endpoint gets
+    /// a share in proportion to its weight.
+    pub fn pick(&self) -> Vec<&SubmitEndpoint> {
+        let enabled: Vec<usize> = (0..self.endpoints.len()).filter(|&i| self.endpoints[i].weight > 0).collect();
+        if self.fanout == 0 || self.fanout >= enabled.len() {
+            return enabled.into_iter().map(|i| &self.endpoints[i]).collect();
+        }
+
+        let mut credit = self.credit.lock().unwrap();
+        let mut candidates = enabled;
+        let mut picked = Vec::with_capacity(self.fanout);
+        for _ in 0..self.fanout {
+            let total: i64 = candidates.iter().map(|&i| self.endpoints[i].weight as i64).sum();
+            for &i in &candidates {
+                credit[i] += self.endpoints[i].weight as i64;
+            }
+            let best = (0..candidates.len())
+                .max_by_key(|&c| (credit[candidates[c]], std::cmp::Reverse(candidates[c])))
+                .expect("fanout is below the number of enabled endpoints");
+            let i = candidates.remove(best);
+            credit[i] -= total;
+            picked.push(&self.endpoints[i]);
+        }
+        picked
+    }
+}
+
+/// A transaction accepted by at least one endpoint.
+#[derive(Debug, Clone)]
+pub struct Broadcast {
+    pub signature: Signature,
+    /// Endpoint that accepted the transaction first.
+    pub first_endpoint: String,
+    /// Endpoints it was sent to.
+    pub sent_to: usize,
+}
+
+/// Send `tx` to the endpoints picked by [`SubmitEndpoints::pick`] in parallel, skipping
+/// preflight, and return once the first one accepts it. The other endpoints' answers are
+/// recorded in the metrics by a background task. An error only if every endpoint rejected it.
+pub async fn broadcast_transaction(
+    engine: &Arc<Engine>,
+    tx: &VersionedTransaction,
+) -> Result<Broadcast, anyhow::Error> {
+    // The simulation gate, when enabled, already ran; a preflight per copy only adds latency
+    let config = RpcSendTransactionConfig {
+        skip_preflight: true,
+        encoding: Some(UiTransactionEncoding::Base64),
+        ..RpcSendTransactionConfig::default()
+    };
+    let targets = engine.submit_endpoints.pick();
+    let sent_to = targets.len();
+    let started = Instant::now();
+    let mut sends: FuturesUnordered<_> = targets
+        .into_iter()
+        .map(|endpoint| {
+            let (name, client, tx) = (endpoint.name.clone(), endpoint.client.clone(), tx.clone());
+            async move { (name, client.send_transaction_with_config(&tx, config).await) }
+        })
+        .collect();
+
+    let mut errors = Vec::new();
+    while let Some((endpoint, result)) = sends.next().await {
+        match record_send(engine, &endpoint, started, result) {
+            Ok(signature) => {
+                engine.metrics.submit_first_accepted.with_label_values(&[&endpoint]).inc();
+                let engine = engine.clone();
+                tokio::spawn(async move {
+                    while let Some((endpoint, result)) = sends.next().await {
+                        let _ = record_send(&engine, &endpoint, started, result);
+                    }
+                });
+                return Ok(Broadcast {
+                    signature,
+                    first_endpoint: endpoint,
+                    sent_to,
+                });
+            }
+            Err(e) => errors.push(format!("{}: {}", endpoint, e)),
+        }
+    }
+    Err(anyhow::anyhow!("Every submit endpoint failed: {}", errors.join("; ")))
+}
+
+/// Count one endpoint's answer to a send started at `started` in the metrics.
+fn record_send(
+    engine: &Engine,
+    endpoint: &str,
+    started: Instant,
+    result: ClientResult<Signature>,
+) -> ClientResult<Signature> {
+    let metrics = &engine.metrics;
+    match &result {
+        Ok(signature) => {
+            let latency = started.elapsed();
+            metrics.submit_latency.with_label_values(&[endpoint]).observe(latency.as_secs_f64());
+            debug!(%endpoint, ?latency, %signature, "Submit endpoint accepted the transaction");
+        }
+        Err(e) => {
+            metrics.submit_errors.with_label_values(&[endpoint]).inc();
+            warn!(%endpoint, error = %e, "Submit endpoint rejected the transaction");
+        }
+    }
+    result
+}
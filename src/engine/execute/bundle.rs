@@ -0,0 +1,279 @@
+//! Submission as a Jito bundle.
+//!
+//! With `[jito] enabled`, a trade's transaction ends with a SOL transfer to one of the
+//! `tip_accounts` (taken in turn) and goes to the block engine with `sendBundle` instead
+//! of the submit endpoints. The tip is `tip_profit_share` of the profit the trade is
+//! expected to keep after every other cost, within `min_tip_lamports` and
+//! `max_tip_lamports`. A sent bundle is polled with `getInflightBundleStatuses` until it
+//! lands, fails or `status_timeout_secs` passes. The block engine shows up as the
+//! `"jito"` endpoint in the submit endpoint metrics.
+
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use jupiter_swap_api_client::quote::QuoteResponse;
+use serde_json::{json, Value};
+use solana_sdk::{
+    instruction::Instruction, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, system_instruction,
+    transaction::VersionedTransaction,
+};
+use tracing::{debug, info, warn};
+
+use crate::app::config::JitoConfig;
+use crate::{broadcast_transaction, trade_cost, Broadcast, Engine, TradeCost};
+
+/// Endpoint name of the block engine in the submit endpoint metrics.
+pub const JITO_ENDPOINT: &str = "jito";
+
+/// Status of a sent bundle as reported by `getInflightBundleStatuses`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BundleStatus {
+    Pending,
+    Landed,
+    Failed,
+    /// Unknown to the block engine (not sent, or more than five minutes ago).
+    Invalid,
+}
+
+impl BundleStatus {
+    fn parse(status: &str) -> Self {
+        match status {
+            "Pending" => BundleStatus::Pending,
+            "Landed" => BundleStatus::Landed,
+            "Failed" => BundleStatus::Failed,
+            _ => BundleStatus::Invalid,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BundleStatus::Pending => "pending",
+            BundleStatus::Landed => "landed",
+            BundleStatus::Failed => "failed",
+            BundleStatus::Invalid => "invalid",
+        }
+    }
+}
+
+/// JSON-RPC client of the block engine and the tip account rotation.
+pub struct BlockEngine {
+    client: reqwest::Client,
+    url: String,
+    /// Sent as `x-jito-auth` when not empty (`[dex_api] jito_api_key`).
+    auth_token: String,
+    tip_accounts: Vec<Pubkey>,
+    next_tip_account: AtomicUsize,
+}
+
+impl BlockEngine {
+    pub fn new(config: &JitoConfig, auth_token: &str) -> Result<Self, anyhow::Error> {
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .timeout(Duration::from_secs(10))
+            .build()?;
+        Ok(Self {
+            client,
+            url: config.block_engine_url.trim_end_matches('/').to_string(),
+            auth_token: auth_token.to_string(),
+            // Invalid accounts are reported by Config::validate
+            tip_accounts: config.tip_accounts.iter().filter_map(|a| Pubkey::from_str(a).ok()).collect(),
+            next_tip_account: AtomicUsize::new(0),
+        })
+    }
+
+    /// Tip account for the next bundle, in turn; None if none is configured.
+    pub fn next_tip_account(&self) -> Option<Pubkey> {
+        if self.tip_accounts.is_empty() {
+            return None;
+        }
+        let i = self.next_tip_account.fetch_add(1, Ordering::Relaxed);
+        Some(self.tip_accounts[i % self.tip_accounts.len()])
+    }
+
+    async fn call(&self, path: &str, method: &str, params: Value) -> Result<Value, anyhow::Error> {
+        let url = format!("{}{}", self.url, path);
+        let mut request = self
+            .client
+            .post(&url)
+            .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }));
+        if !self.auth_token.is_empty() {
+            request = request.header("x-jito-auth", &self.auth_token);
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("{} to {} failed: {}", method, url, e))?;
+        let status = response.status();
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| anyhow::anyhow!("{} returned HTTP {} without a JSON body: {}", method, status, e))?;
+        if let Some(error) = body.get("error") {
+            let message = error.get("message").and_then(Value::as_str).map(str::to_string);
+            return Err(anyhow::anyhow!("{} failed: {}", method, message.unwrap_or_else(|| error.to_string())));
+        }
+        if !status.is_success() {
+            return Err(anyhow::anyhow!("{} returned HTTP {}", method, status));
+        }
+        body.get("result")
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{} returned no result", method))
+    }
+
+    /// `sendBundle` with `transactions` (executed in order, all or none); returns the bundle ID.
+    pub async fn send_bundle(&self, transactions: &[VersionedTransaction]) -> Result<String, anyhow::Error> {
+        let mut encoded = Vec::with_capacity(transactions.len());
+        for tx in transactions {
+            encoded.push(bs58::encode(bincode::serialize(tx)?).into_string());
+        }
+        let result = self.call("/api/v1/bundles", "sendBundle", json!([encoded])).await?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| anyhow::anyhow!("sendBundle returned {} instead of a bundle ID", result))
+    }
+
+    /// Status of a bundle sent in the last five minutes.
+    pub async fn bundle_status(&self, bundle_id: &str) -> Result<BundleStatus, anyhow::Error> {
+        let result = self
+            .call("/api/v1/getInflightBundleStatuses", "getInflightBundleStatuses", json!([[bundle_id]]))
+            .await?;
+        let status = result["value"]
+            .as_array()
+            .and_then(|statuses| statuses.iter().find(|s| s["bundle_id"] == bundle_id))
+            .and_then(|s| s["status"].as_str())
+            .unwrap_or("Invalid");
+        Ok(BundleStatus::parse(status))
+    }
+}
+
+/// Tip transfer added as the last instruction of a bundled transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BundleTip {
+    pub account: Pubkey,
+    pub lamports: u64,
+}
+
+impl BundleTip {
+    pub fn instruction(&self, payer: &Pubkey) -> Instruction {
+        system_instruction::transfer(payer, &self.account, self.lamports)
+    }
+}
+
+/// Tip in lamports for a trade of `in_amount` expected to return `gross_profit` (raw base
+/// units) at `cost`: `tip_profit_share` of the profit left after every other cost, within
+/// `min_tip_lamports` and `max_tip_lamports` (0 = no upper bound). The tip planned in
+/// `cost` is not deducted; it is the floor this tip replaces.
+pub fn bundle_tip_lamports(config: &JitoConfig, cost: &TradeCost, in_amount: u64, gross_profit: i64) -> u64 {
+    if cost.sol_price <= 0.0 {
+        return config.min_tip_lamports;
+    }
+    let planned_tip = cost.lamports.tip as f64 * cost.sol_price / LAMPORTS_PER_SOL as f64;
+    let other_costs = cost.for_amount(in_amount) as f64 - planned_tip;
+    let left = (gross_profit as f64 - other_costs).max(0.0);
+    let tip = (left * LAMPORTS_PER_SOL as f64 / cost.sol_price * config.tip_profit_share) as u64;
+    let tip = tip.max(config.min_tip_lamports);
+    match config.max_tip_lamports {
+        0 => tip,
+        max => tip.min(max),
+    }
+}
+
+/// Tip for a trade on `legs` (base-token `decimals`) when `[jito]` is enabled; None sends
+/// the trade to the submit endpoints instead.
+pub async fn bundle_tip(engine: &Engine, legs: &[QuoteResponse], decimals: u8) -> Option<BundleTip> {
+    let config = engine.config();
+    if !config.jito.enabled {
+        return None;
+    }
+    let (first, last) = (legs.first()?, legs.last()?);
+    let account = engine.block_engine.next_tip_account()?;
+    let gross_profit = last.out_amount as i64 - first.in_amount as i64;
+    let lamports = match trade_cost(engine, &first.input_mint.to_string(), decimals).await {
+        Ok(cost) => {
+            let cost = cost.for_trade(first.in_amount, gross_profit);
+            bundle_tip_lamports(&config.jito, &cost, first.in_amount, gross_profit)
+        }
+        Err(e) => {
+            warn!(error = %e, "Transaction cost unknown; tipping the minimum");
+            config.jito.min_tip_lamports
+        }
+    };
+    Some(BundleTip { account, lamports })
+}
+
+/// Send a signed trade: as a one-transaction bundle when it pays `tip` (see
+/// [`bundle_tip`]), else to the submit endpoints with [`broadcast_transaction`].
+pub async fn submit_transaction(
+    engine: &Arc<Engine>,
+    tx: &VersionedTransaction,
+    tip: Option<&BundleTip>,
+) -> Result<Broadcast, anyhow::Error> {
+    let Some(tip) = tip else {
+        return broadcast_transaction(engine, tx).await;
+    };
+
+    let metrics = &engine.metrics;
+    let started = Instant::now();
+    let bundle_id = match engine.block_engine.send_bundle(std::slice::from_ref(tx)).await {
+        Ok(bundle_id) => bundle_id,
+        Err(e) => {
+            metrics.submit_errors.with_label_values(&[JITO_ENDPOINT]).inc();
+            metrics.bundles.with_label_values(&["rejected"]).inc();
+            warn!(error = %e, "Block engine rejected the bundle");
+            return Err(e);
+        }
+    };
+    metrics
+        .submit_latency
+        .with_label_values(&[JITO_ENDPOINT])
+        .observe(started.elapsed().as_secs_f64());
+    metrics.submit_first_accepted.with_label_values(&[JITO_ENDPOINT]).inc();
+    metrics.bundles.with_label_values(&["sent"]).inc();
+    metrics.bundle_tips.inc_by(tip.lamports);
+    info!(%bundle_id, tip_account = %tip.account, tip_lamports = tip.lamports, "Bundle sent");
+
+    tokio::spawn(track_bundle(engine.clone(), bundle_id));
+    Ok(Broadcast {
+        signature: tx.signatures[0],
+        first_endpoint: JITO_ENDPOINT.to_string(),
+        sent_to: 1,
+    })
+}
+
+/// Poll a sent bundle until it lands, fails or `[jito] status_timeout_secs` passes, and
+/// count the outcome ("landed", "failed" or "timeout") in the `bundles` metric.
+pub async fn track_bundle(engine: Arc<Engine>, bundle_id: String) -> BundleStatus {
+    let config = engine.config().jito.clone();
+    let interval = Duration::from_millis(config.status_poll_interval_ms.max(1));
+    let deadline = Instant::now() + Duration::from_secs(config.status_timeout_secs);
+    let mut status = BundleStatus::Pending;
+    loop {
+        match engine.block_engine.bundle_status(&bundle_id).await {
+            Ok(BundleStatus::Landed) => {
+                status = BundleStatus::Landed;
+                break;
+            }
+            Ok(BundleStatus::Failed) => {
+                status = BundleStatus::Failed;
+                break;
+            }
+            Ok(pending) => status = pending,
+            Err(e) => debug!(%bundle_id, error = %e, "Bundle status unavailable"),
+        }
+        if Instant::now() >= deadline {
+            break;
+        }
+        tokio::time::sleep(interval).await;
+    }
+
+    let outcome = match status {
+        BundleStatus::Landed | BundleStatus::Failed => status.as_str(),
+        BundleStatus::Pending | BundleStatus::Invalid => "timeout",
+    };
+    engine.metrics.bundles.with_label_values(&[outcome]).inc();
+    info!(%bundle_id, outcome, last_status = status.as_str(), "Bundle settled");
+    status
+}
@@ -0,0 +1,401 @@
+//! Transaction landing tracker.
+//!
+//! Follows each submitted signature through processed → confirmed → finalized, or
+//! until it fails or expires. Once the transaction is seen on chain (or can no longer
+//! land: its durable nonce advanced, or the chain passed its blockhash's last valid
+//! block height), `getTransaction` is used to read the wallet's actual
+//! base-token balance change. Every outcome is appended to a JSON-lines ledger so
+//! expected and realized profit can be compared per instrument and per route, and
+//! recorded as a confirmation in the SQLite [`Ledger`](crate::Ledger) when enabled.
+
+use chrono::{DateTime, Utc};
+use jupiter_swap_api_client::quote::QuoteResponse;
+use serde::Serialize;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey, signature::Signature,
+};
+use solana_transaction_status_client_types::{
+    TransactionConfirmationStatus, UiTransactionEncoding, UiTransactionStatusMeta,
+    UiTransactionTokenBalance,
+};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::{info, warn};
+
+use crate::{opportunity_id, Engine, TxLifetime, WSOL};
+
+// Mutex to ensure thread-safe ledger appends
+static LEDGER_MUTEX: Mutex<()> = Mutex::new(());
+
+/// Number of polls to keep looking for a signature after its nonce has advanced or its
+/// blockhash expired.
+const LIFETIME_ENDED_GRACE_POLLS: u32 = 5;
+
+/// Final (or last observed) state of a submitted transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LandingStatus {
+    Submitted,
+    Processed,
+    Confirmed,
+    Finalized,
+    Expired,
+    Failed,
+}
+
+impl LandingStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LandingStatus::Submitted => "submitted",
+            LandingStatus::Processed => "processed",
+            LandingStatus::Confirmed => "confirmed",
+            LandingStatus::Finalized => "finalized",
+            LandingStatus::Expired => "expired",
+            LandingStatus::Failed => "failed",
+        }
+    }
+}
+
+/// A transaction that has been sent and should be tracked until it settles.
+#[derive(Debug, Clone)]
+pub struct PendingTrade {
+    pub signature: Signature,
+    /// Ledger ID of the opportunity, see [`opportunity_id`].
+    pub opportunity_id: String,
+    /// Execution path: "polling", "big_trade" or "flash_loan".
+    pub path: String,
+    /// Base token mint the trade starts and ends in.
+    pub instrument: Pubkey,
+    /// DEX labels of the route, in execution order.
+    pub route: String,
+    /// Nonce account the transaction advances; None when signed with a recent blockhash.
+    pub nonce_account: Option<Pubkey>,
+    /// Blockhash (or durable nonce value) the transaction was signed with.
+    pub blockhash: Hash,
+    /// Last block height a transaction signed with a recent blockhash can land in.
+    pub last_valid_block_height: Option<u64>,
+    /// Submit endpoint that accepted the transaction first, see [`crate::broadcast_transaction`].
+    pub first_endpoint: Option<String>,
+    /// Quoted profit in raw base-token units (out - in).
+    pub expected_profit: i64,
+    pub submitted_at: DateTime<Utc>,
+}
+
+impl PendingTrade {
+    /// Describe a trade from its two quote legs, before it is signed and sent.
+    pub fn new(path: &str, in_res: &QuoteResponse, out_res: &QuoteResponse) -> Self {
+        Self::from_legs(path, &[in_res.clone(), out_res.clone()])
+    }
+
+    /// Describe a cycle trade (2 legs and up), before it is signed and sent.
+    pub fn from_legs(path: &str, legs: &[QuoteResponse]) -> Self {
+        let first = &legs[0];
+        let last = &legs[legs.len() - 1];
+        Self {
+            signature: Signature::default(),
+            opportunity_id: opportunity_id(legs),
+            path: path.to_string(),
+            instrument: first.input_mint,
+            route: route_label(&legs.iter().collect::<Vec<_>>()),
+            nonce_account: None,
+            blockhash: Hash::default(),
+            last_valid_block_height: None,
+            first_endpoint: None,
+            expected_profit: last.out_amount as i64 - first.in_amount as i64,
+            submitted_at: Utc::now(),
+        }
+    }
+
+    /// Stamp the signature and lifetime (nonce or blockhash) of the transaction that was sent.
+    pub fn sent(mut self, signature: Signature, lifetime: &TxLifetime) -> Self {
+        self.signature = signature;
+        self.nonce_account = lifetime.nonce_account();
+        self.blockhash = lifetime.blockhash();
+        self.last_valid_block_height = lifetime.last_valid_block_height();
+        self.submitted_at = Utc::now();
+        self
+    }
+
+    /// Record the submit endpoint that accepted the transaction first.
+    pub fn accepted_by(mut self, endpoint: &str) -> Self {
+        self.first_endpoint = Some(endpoint.to_string());
+        self
+    }
+}
+
+/// DEX labels of all route steps joined with " -> ".
+pub fn route_label(quotes: &[&QuoteResponse]) -> String {
+    quotes
+        .iter()
+        .flat_map(|q| q.route_plan.iter().map(|step| step.swap_info.label.clone()))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+/// One ledger line.
+#[derive(Debug, Clone, Serialize)]
+pub struct LandingRecord {
+    pub recorded_at: String,
+    pub submitted_at: String,
+    pub signature: String,
+    pub opportunity_id: String,
+    pub path: String,
+    pub instrument: String,
+    pub route: String,
+    pub status: LandingStatus,
+    pub slot: Option<u64>,
+    pub expected_profit: i64,
+    pub realized_profit: Option<i64>,
+    pub fee_lamports: Option<u64>,
+    pub error: Option<String>,
+}
+
+fn append_ledger(ledger_path: &str, record: &LandingRecord) {
+    let line = match serde_json::to_string(record) {
+        Ok(line) => line,
+        Err(e) => {
+            warn!(error = %e, "Failed to serialize landing record");
+            return;
+        }
+    };
+    let _guard = LEDGER_MUTEX.lock().unwrap();
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(ledger_path)
+    {
+        if writeln!(file, "{}", line).is_ok() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Sum of the raw amounts held by `owner` in `mint` token accounts.
+fn owner_token_amount(balances: &[UiTransactionTokenBalance], owner: &str, mint: &str) -> i128 {
+    balances
+        .iter()
+        .filter(|b| b.mint == mint)
+        .filter(|b| Option::<&String>::from(b.owner.as_ref()).map(|o| o.as_str()) == Some(owner))
+        .filter_map(|b| b.ui_token_amount.amount.parse::<i128>().ok())
+        .sum()
+}
+
+/// Realized base-token change of the wallet from a landed transaction's meta.
+/// For WSOL the fee payer's lamport change (which already includes the fee) is added.
+fn realized_profit(meta: &UiTransactionStatusMeta, instrument: &Pubkey, owner: &Pubkey) -> i64 {
+    let owner = owner.to_string();
+    let mint = instrument.to_string();
+    let pre: Vec<UiTransactionTokenBalance> =
+        Option::from(meta.pre_token_balances.clone()).unwrap_or_default();
+    let post: Vec<UiTransactionTokenBalance> =
+        Option::from(meta.post_token_balances.clone()).unwrap_or_default();
+
+    let mut delta = owner_token_amount(&post, &owner, &mint) - owner_token_amount(&pre, &owner, &mint);
+    if *instrument == WSOL {
+        let pre_lamports = meta.pre_balances.first().copied().unwrap_or(0) as i128;
+        let post_lamports = meta.post_balances.first().copied().unwrap_or(0) as i128;
+        delta += post_lamports - pre_lamports;
+    }
+    delta as i64
+}
+
+/// Fetch the landed transaction and return (slot, realized profit, fee, error).
+async fn fetch_landed(
+    engine: &Engine,
+    trade: &PendingTrade,
+) -> Result<(u64, i64, u64, Option<String>), anyhow::Error> {
+    let config = RpcTransactionConfig {
+        encoding: Some(UiTransactionEncoding::Json),
+        commitment: Some(CommitmentConfig::confirmed()),
+        max_supported_transaction_version: Some(0),
+    };
+    let tx = engine
+        .rpc_client
+        .get_transaction_with_config(&trade.signature, config)
+        .await?;
+    let meta = tx
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow::anyhow!("transaction meta missing"))?;
+    let realized = realized_profit(&meta, &trade.instrument, &engine.pubkey);
+    let err = meta.err.as_ref().map(|e| format!("{:?}", e));
+    Ok((tx.slot, realized, meta.fee, err))
+}
+
+/// Why `trade` can no longer land, if so: its nonce advanced, or the chain is past the
+/// last block height its blockhash is valid for.
+async fn lifetime_ended(engine: &Engine, trade: &PendingTrade) -> Option<&'static str> {
+    if let Some(nonce_account) = &trade.nonce_account {
+        let nonce = engine.nonces.get(nonce_account).unwrap_or_default();
+        return (nonce.blockhash() != trade.blockhash).then_some("nonce advanced without this signature landing");
+    }
+    let last_valid_block_height = trade.last_valid_block_height?;
+    match engine.rpc_client.get_block_height().await {
+        Ok(height) => {
+            (height > last_valid_block_height).then_some("blockhash expired without this signature landing")
+        }
+        Err(e) => {
+            warn!(signature = %trade.signature, error = %e, "getBlockHeight failed");
+            None
+        }
+    }
+}
+
+/// Track `trade` until it settles and append the outcome to the ledgers.
+pub async fn track_landing(engine: Arc<Engine>, trade: PendingTrade) {
+    let config = engine.config();
+    let landing = &config.landing;
+    let started = Instant::now();
+    let timeout = Duration::from_secs(landing.timeout_secs);
+    let poll_interval = Duration::from_millis(landing.poll_interval_ms);
+
+    let mut status = LandingStatus::Submitted;
+    let mut slot = None;
+    let mut error = None;
+    let mut landed: Option<(i64, u64)> = None;
+    let mut polls_since_lifetime_ended = 0;
+
+    loop {
+        sleep(poll_interval).await;
+
+        let observed = match engine.rpc_client.get_signature_statuses(&[trade.signature]).await {
+            Ok(response) => response.value.into_iter().next().flatten(),
+            Err(e) => {
+                warn!(signature = %trade.signature, error = %e, "getSignatureStatuses failed");
+                None
+            }
+        };
+
+        if let Some(tx_status) = observed {
+            slot = Some(tx_status.slot);
+            if let Some(err) = tx_status.err {
+                status = LandingStatus::Failed;
+                error = Some(format!("{:?}", err));
+            } else {
+                let next = match tx_status.confirmation_status {
+                    Some(TransactionConfirmationStatus::Finalized) => LandingStatus::Finalized,
+                    Some(TransactionConfirmationStatus::Confirmed) => LandingStatus::Confirmed,
+                    _ => LandingStatus::Processed,
+                };
+                if next != status {
+                    info!(signature = %trade.signature, status = ?next, slot = tx_status.slot, "Landing status");
+                    status = next;
+                }
+            }
+        } else if let Some(reason) = lifetime_ended(&engine, &trade).await {
+            // The transaction either landed already or never will.
+            polls_since_lifetime_ended += 1;
+            if polls_since_lifetime_ended >= LIFETIME_ENDED_GRACE_POLLS
+                && status == LandingStatus::Submitted
+            {
+                status = LandingStatus::Expired;
+                error = Some(reason.to_string());
+            }
+        }
+
+        // Read the actual balance deltas once the transaction is confirmed on chain.
+        if landed.is_none()
+            && matches!(
+                status,
+                LandingStatus::Confirmed | LandingStatus::Finalized | LandingStatus::Failed
+            )
+        {
+            match fetch_landed(&engine, &trade).await {
+                Ok((tx_slot, realized, fee, tx_err)) => {
+                    slot = Some(tx_slot);
+                    landed = Some((realized, fee));
+                    if tx_err.is_some() {
+                        error = tx_err;
+                    }
+                }
+                Err(e) => {
+                    warn!(signature = %trade.signature, error = %e, "getTransaction failed; will retry");
+                }
+            }
+        }
+
+        let settled = match status {
+            LandingStatus::Finalized | LandingStatus::Failed => landed.is_some(),
+            LandingStatus::Expired => true,
+            _ => false,
+        };
+        if settled {
+            break;
+        }
+
+        if started.elapsed() >= timeout {
+            if status == LandingStatus::Submitted {
+                status = LandingStatus::Expired;
+                error = Some(format!("not seen within {}s", landing.timeout_secs));
+            }
+            break;
+        }
+    }
+
+    // An expired transaction no longer needs its nonce account
+    if let (LandingStatus::Expired, Some(nonce_account)) = (status, &trade.nonce_account) {
+        engine.nonces.release(nonce_account, &trade.blockhash);
+    }
+
+    let record = LandingRecord {
+        recorded_at: Utc::now().to_rfc3339(),
+        submitted_at: trade.submitted_at.to_rfc3339(),
+        signature: trade.signature.to_string(),
+        opportunity_id: trade.opportunity_id.clone(),
+        path: trade.path.clone(),
+        instrument: trade.instrument.to_string(),
+        route: trade.route.clone(),
+        status,
+        slot,
+        expected_profit: trade.expected_profit,
+        realized_profit: landed.map(|(realized, _)| realized),
+        fee_lamports: landed.map(|(_, fee)| fee),
+        error,
+    };
+
+    info!(
+        signature = %record.signature,
+        status = ?record.status,
+        path = %record.path,
+        expected_profit = record.expected_profit,
+        realized_profit = ?record.realized_profit,
+        "Transaction settled"
+    );
+    append_ledger(&landing.ledger_path, &record);
+    engine.ledger.record_confirmation(&record);
+    record_landing_metrics(&engine, &record, trade.first_endpoint.as_deref());
+}
+
+fn record_landing_metrics(engine: &Engine, record: &LandingRecord, first_endpoint: Option<&str>) {
+    let metrics = &engine.metrics;
+    metrics
+        .landings
+        .with_label_values(&[&record.path, record.status.as_str()])
+        .inc();
+    // Seen on chain, failed or not
+    let landed = !matches!(record.status, LandingStatus::Submitted | LandingStatus::Expired);
+    if let (true, Some(endpoint)) = (landed, first_endpoint) {
+        metrics.submit_first_landed.with_label_values(&[endpoint]).inc();
+    }
+    // Every traded instrument was resolved before its transaction was built
+    if let (Some(realized), Some(token)) = (record.realized_profit, engine.tokens.get(&record.instrument)) {
+        metrics
+            .realized_pnl
+            .with_label_values(&[&record.instrument])
+            .add(realized as f64 / 10_f64.powf(token.decimals as f64));
+    }
+    if let Some(fee) = record.fee_lamports {
+        metrics.fees_paid.inc_by(fee);
+    }
+}
+
+/// Spawn a landing tracker for `trade` when `[landing] enabled = true`.
+pub fn spawn_landing_tracker(engine: &Arc<Engine>, trade: PendingTrade) {
+    if !engine.config().landing.enabled {
+        return;
+    }
+    tokio::spawn(track_landing(engine.clone(), trade));
+}
@@ -1,3 +1,13 @@
+pub mod broadcast;
+pub mod bundle;
+pub mod landing;
 mod process_update;
+pub mod simulate_tx;
+pub mod submit;
 
+pub use broadcast::*;
+pub use bundle::*;
+pub use landing::*;
 pub use process_update::*;
+pub use simulate_tx::*;
+pub use submit::*;
@@ -1,16 +1,11 @@
 use chrono::Utc;
 use futures::future::join_all;
-use solana_relayer_adapter_rust::{Tips, ultra_submit};
-use solana_sdk::{
-    system_instruction::advance_nonce_account,
-    transaction::Transaction,
-    bs58,
-};
+use solana_sdk::signature::Signature;
 
 use std::time::Instant;
 use std::fs::OpenOptions;
 use std::io::Write;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex};
 
 use crate::*;
 
@@ -31,10 +26,50 @@ fn write_big_trade_log(message: &str) {
     }
 }
 
+/// Record the result of a big-trade submission in big_trades.txt.
+///
+/// `[SUBMIT_SUCCESS]` is only written when the transaction was actually sent;
+/// trades rejected by the simulation gate or failing to build are `[SUBMIT_SKIPPED]`.
+fn write_submission_log(
+    signature: Option<Signature>,
+    service_name: &str,
+    token_symbol: &str,
+    in_amount: u64,
+    out_amount: u64,
+    total_tx_cost: i64,
+    original_tx_id: &str,
+) {
+    let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
+    let gross_profit = out_amount as i64 - in_amount as i64;
+    let net_profit = gross_profit - total_tx_cost;
+    let (tag, submitted) = match signature {
+        Some(sig) => ("SUBMIT_SUCCESS", sig.to_string()),
+        None => ("SUBMIT_SKIPPED", "-".to_string()),
+    };
+    let submission_log = format!(
+        "[{}] [{}] token={}, in_amount={}, out_amount={}, gross_profit={}, net_profit={}, tx_cost={}, service={}, original_tx_id={}, submitted_tx_signature={}",
+        timestamp,
+        tag,
+        token_symbol,
+        in_amount,
+        out_amount,
+        gross_profit,
+        net_profit,
+        total_tx_cost,
+        service_name,
+        original_tx_id,
+        submitted
+    );
+    write_big_trade_log(&submission_log);
+}
+
 /// Process a single trade update asynchronously Lil Jit
 /// Temporarily disabled - needs to be refactored for Yellowstone gRPC
 // async fn process_single_trade(sub_update: SubscribeUpdate) {
-pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto::prelude::SubscribeUpdate) {
+pub async fn process_single_trade_yellowstone(
+    engine: Arc<Engine>,
+    sub_update: yellowstone_grpc_proto::prelude::SubscribeUpdate,
+) {
     // Only process transaction updates (ignore account, slot, block, ping, etc.)
     match &sub_update.update_oneof {
         Some(yellowstone_grpc_proto::geyser::subscribe_update::UpdateOneof::Transaction(_)) => {
@@ -44,7 +79,7 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     };
     
     let (mother_token, changes, program_names, unique_tokens, tx_id) =
-        match extract_big_trade(&sub_update) {
+        match extract_big_trade(&engine, &sub_update) {
             Some(data) => data,
             None => {
                 // extract_big_trade filters out trades that don't meet criteria
@@ -61,11 +96,7 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     let change_lines: Vec<String> = changes
         .iter()
         .map(|c| {
-            let sym = POPULAR_TOKEN_INFO
-                .iter()
-                .find(|t| t.mint == c.mint)
-                .map(|t| t.symbol)
-                .unwrap_or("?");
+            let sym = engine.tokens.symbol(&c.mint);
             format!("    {}  delta: {:+.6}  pre: {:.6}  post: {:.6}", sym, c.delta, c.pre_balance, c.post_balance)
         })
         .collect();
@@ -102,58 +133,68 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     let target_tokens: Vec<String> = unique_tokens.clone();
 
     // Run simulation with original input amount range (big trade mode)
-    let mut quote_data = simulate_amount_in(
+    let quote_data = simulate_amount_in(
+        &engine,
         mother_token.clone().0,
         mother_token.clone().1,
         mother_token.clone().5,
-        target_tokens.clone(),
+        target_tokens,
         min_range,
         max_range,
         mother_token.3 as usize,
         mother_token.4,
         false, // is_polling_mode = false for big trade mode
+        instrument_quote_budget(&engine, &mother_token.0),
     )
     .await;
 
-    // If submit_transactions is enabled, run additional simulation with larger amounts (1-5000 USDC)
-    if CONFIG.strategy.live_trading {
-        let quote_data_large = simulate_amount_in(
-            mother_token.clone().0,
-            mother_token.clone().1,
-            mother_token.clone().5,
-            target_tokens,
-            min_range,
-            max_range,
-            mother_token.3 as usize,
-            mother_token.4,
-            false, // is_polling_mode = false for big trade mode
-        )
-        .await;
-        
-        // Combine results from both simulations
-        quote_data.extend(quote_data_large);
-    }
     let _simulate_elapsed = simulate_start.elapsed();
+    engine.metrics.opportunities.with_label_values(&["big_trade"]).inc_by(quote_data.len() as u64);
 
-    // If only simulating (not submitting), return early after simulation
-    if !CONFIG.strategy.live_trading {
+    if quote_data.is_empty() {
         return;
     }
-    
-    if quote_data.is_empty() {
+
+    // Full transaction cost in the base token, as in simulate_amount_in
+    let cost = match trade_cost(&engine, &mother_token.0, mother_token.1).await {
+        Ok(cost) => cost,
+        Err(e) => {
+            tracing::warn!(error = %e, symbol = %mother_token.5, "Transaction cost unknown; skipping big trade");
+            return;
+        }
+    };
+
+    // Every opportunity found goes to the ledger, not only the one traded
+    for (in_amount, out_amount, in_res, out_res, elapsed, _) in &quote_data {
+        let gross_profit = *out_amount as i64 - *in_amount as i64;
+        let net_profit = gross_profit - cost.for_trade(*in_amount, gross_profit).for_amount(*in_amount) as i64;
+        let opportunity = CycleQuote {
+            in_amount: *in_amount,
+            out_amount: *out_amount,
+            legs: vec![in_res.clone(), out_res.clone()],
+            elapsed: *elapsed,
+        };
+        engine.ledger.record_opportunity(
+            "big_trade",
+            &opportunity,
+            &opportunity.label(&engine.tokens),
+            net_profit,
+            mother_token.1,
+            Some(tx_id.as_str()),
+        );
+    }
+
+    // If only simulating (not submitting), return early after simulation
+    if !engine.execution_enabled() {
         return;
     }
 
     // Select only the most profitable trade (highest net profit after fees)
-    // Calculate actual transaction cost: base_tx_fee + third_party_fee
-    let total_tx_cost_usdc = crate::engine::runtime::calculate_tx_cost_usdc(&FEES).await;
-    let total_tx_cost = (total_tx_cost_usdc * 10_f64.powf(mother_token.1 as f64)) as i64;
-    
     let most_profitable = quote_data
         .into_iter()
         .max_by_key(|(in_amount, out_amount, _, _, _, _)| {
             let gross_profit = *out_amount as i64 - *in_amount as i64;
-            gross_profit - total_tx_cost  // Use net profit for comparison
+            gross_profit - cost.for_trade(*in_amount, gross_profit).for_amount(*in_amount) as i64  // Use net profit for comparison
         });
     
     let (in_amount, out_amount, in_res, out_res, _elapsed, _target_token) = match most_profitable {
@@ -163,6 +204,9 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     
     // Calculate profit accounting for actual transaction fees
     let gross_profit = out_amount as i64 - in_amount as i64;
+    let cost = cost.for_trade(in_amount, gross_profit);
+    let compute_unit_price = cost.plan.compute_unit_price;
+    let total_tx_cost = cost.for_amount(in_amount) as i64;
     let net_profit = gross_profit - total_tx_cost;
     
     // Log big trade with profitable opportunity found
@@ -197,7 +241,7 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     println!("{}", big_trade_log);
     write_big_trade_log(&big_trade_log);
 
-    // Human-readable submit log (token units + USD tx cost from SOL price)
+    // Human-readable submit log (token units + tx cost in lamports)
     let dec = mother_token.1;
     let pow = 10_f64.powf(dec as f64);
     let in_human = in_amount as f64 / pow;
@@ -206,14 +250,14 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     let net_human = net_profit as f64 / pow;
     let tx_cost_human = total_tx_cost as f64 / pow;
     println!(
-        "[{}] [SUBMIT] 🚀 Submitting most profitable trade:\n  in={:.6} {}  out={:.6} {}  gross_profit={:.6} {}  net_profit={:.6} {}  tx_cost={:.6} {} (≈ ${:.4} USD)",
+        "[{}] [SUBMIT] 🚀 Submitting most profitable trade:\n  in={:.6} {}  out={:.6} {}  gross_profit={:.6} {}  net_profit={:.6} {}  tx_cost={:.6} {} ({} lamports)",
         timestamp,
         in_human, token_symbol,
         out_human, token_symbol,
         gross_human, token_symbol,
         net_human, token_symbol,
         tx_cost_human, token_symbol,
-        total_tx_cost_usdc
+        cost.lamports.total()
     );
     
     // Capture values for logging
@@ -224,14 +268,12 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
     let log_mother_token_symbol = mother_token.5.clone();
     
     // Check if flash loan is available for this token
-    let flash_ctx = FLASH_LOAN_CONTEXTS
-        .as_ref()
-        .and_then(|ctxs| ctxs.get(&token_mint).cloned());
+    let flash_ctx = engine.flash_loan_context(&token_mint);
 
     tracing::info!(
         token_mint = %token_mint,
         flash_ctx_found = flash_ctx.is_some(),
-        flash_loan_contexts_loaded = FLASH_LOAN_CONTEXTS.is_some(),
+        flash_loan_contexts_loaded = engine.flash_loan_mints().is_some(),
         "SUBMIT STEP 1: Flash loan context lookup result"
     );
 
@@ -244,6 +286,7 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
             let log_tx_id = log_tx_id.clone();
             let log_mother_token_symbol = log_mother_token_symbol.clone();
             let flash_ctx = flash_ctx.clone();
+            let engine = engine.clone();
 
             tracing::info!(
                 token = %log_mother_token_symbol,
@@ -264,11 +307,13 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
                         in_amount = log_in_amount,
                         "Submitting flash-loan trade (Yellowstone)"
                     );
-                    submit_flash_loan_trade(
+                    let signature = submit_flash_loan_trade(
+                        &engine,
                         in_res,
                         out_res,
                         mother_token.4,
                         mother_token.1,
+                        compute_unit_price,
                         ctx,
                     )
                     .await;
@@ -277,21 +322,15 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
                         "SUBMIT STEP 4: submit_flash_loan_trade returned"
                     );
 
-                    let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
-                    let gross_profit = log_out_amount as i64 - log_in_amount as i64;
-                    let net_profit = gross_profit - log_total_tx_cost;
-                    let submission_log = format!(
-                        "[{}] [SUBMIT_SUCCESS] token={}, in_amount={}, out_amount={}, gross_profit={}, net_profit={}, tx_cost={}, service=FLASH_LOAN_RPC, original_tx_id={}",
-                        timestamp,
-                        log_mother_token_symbol,
+                    write_submission_log(
+                        signature,
+                        "FLASH_LOAN_RPC",
+                        &log_mother_token_symbol,
                         log_in_amount,
                         log_out_amount,
-                        gross_profit,
-                        net_profit,
                         log_total_tx_cost,
-                        log_tx_id,
+                        &log_tx_id,
                     );
-                    write_big_trade_log(&submission_log);
                     return;
                 }
 
@@ -301,93 +340,26 @@ pub async fn process_single_trade_yellowstone(sub_update: yellowstone_grpc_proto
                 );
 
                 // ── Regular (non-flash-loan) path ───────────────────────
-                let instr_advance_nonce_account = advance_nonce_account(&NONCE_ADDR, &PUBKEY);
-                let ix = get_swap_ix(
+                let signature = submit_regular_trade(
+                    &engine,
                     in_res,
                     out_res,
-                    (mother_token.4 * 10_f64.powf(mother_token.1 as f64)) as u64,
-                )
-                .await
-                .unwrap();
-
-                let mut raw_swap_ixs = Vec::new();
-                raw_swap_ixs.extend(ix.setup_instructions);
-                raw_swap_ixs.push(ix.swap_instruction);
-
-                let nonce_data = get_nonce();
-                let recent_blockhash = nonce_data.blockhash();
-
-                let alts = fetch_alt(ix.address_lookup_table_addresses).await;
-
-                // Build transaction to get signature before submitting
-                let mut all_instructions = vec![instr_advance_nonce_account.clone()];
-                all_instructions.extend(raw_swap_ixs.clone());
-
-                let mut tx = Transaction::new_with_payer(&all_instructions, Some(&PUBKEY));
-                tx.sign(&**SIGNERS, recent_blockhash);
-                let submitted_tx_signature = bs58::encode(tx.signatures[0]).into_string();
-
-                // RPC-only submission
-                let jito_client = None;
-                let lil_jit_client = None;
-                let astra_client = None;
-                let helius_client = None;
-                let zslot_client = None;
-                let nozomi_client = None;
-                let brazor_client = None;
-                let service_name = "RPC";
-
-                ultra_submit(
-                    Tips {
-                        tip_sol_amount: FEES.tip_sol,
-                        tip_addr_idx: 0,
-                        cu: Some(FEES.compute_units),
-                        priority_fee_micro_lamport: Some(FEES.priority_lamports),
-                        payer: *PUBKEY,
-                        pure_ix: raw_swap_ixs,
-                    },
-                    &*SIGNERS,
-                    recent_blockhash,
-                    instr_advance_nonce_account,
-                    alts,
-                    1,
-                    jito_client,
-                    lil_jit_client,
-                    astra_client,
-                    helius_client,
-                    None,  // NextBlock
-                    zslot_client,
-                    nozomi_client,
-                    brazor_client,
-                    None,  // BloxRoute
+                    mother_token.4,
+                    mother_token.1,
+                    compute_unit_price,
+                    "big_trade",
                 )
                 .await;
 
-                let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
-                let submit_msg = format!(
-                    "[{}] [SUBMIT] Transaction submission completed via {}",
-                    timestamp,
-                    service_name
-                );
-                println!("{}", submit_msg);
-
-                // Log successful submission with details including submitted tx signature
-                let gross_profit = log_out_amount as i64 - log_in_amount as i64;
-                let net_profit = gross_profit - log_total_tx_cost;
-                let submission_log = format!(
-                    "[{}] [SUBMIT_SUCCESS] token={}, in_amount={}, out_amount={}, gross_profit={}, net_profit={}, tx_cost={}, service={}, original_tx_id={}, submitted_tx_signature={}",
-                    timestamp,
-                    log_mother_token_symbol,
+                write_submission_log(
+                    signature,
+                    "RPC",
+                    &log_mother_token_symbol,
                     log_in_amount,
                     log_out_amount,
-                    gross_profit,
-                    net_profit,
                     log_total_tx_cost,
-                    service_name,
-                    log_tx_id,
-                    submitted_tx_signature
+                    &log_tx_id,
                 );
-                write_big_trade_log(&submission_log);
             })
         });
 
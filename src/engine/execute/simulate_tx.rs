@@ -0,0 +1,251 @@
+//! Pre-submit transaction simulation gate.
+//!
+//! Runs `simulateTransaction` on the fully built, signed transaction and reads the
+//! post-simulation state of the wallet's base-token account to get the realized
+//! profit. Submission is skipped when the simulation fails or the realized profit
+//! is below the instrument's minimum.
+
+use chrono::Utc;
+use solana_client::{
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    commitment_config::CommitmentConfig, pubkey::Pubkey, transaction::VersionedTransaction,
+};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::sync::Mutex;
+use tracing::{info, warn};
+
+use crate::{Engine, WSOL};
+
+// Mutex to ensure thread-safe file writes for simulation results
+static SIMULATION_LOG_MUTEX: Mutex<()> = Mutex::new(());
+
+/// Write a log message to the simulation log (thread-safe, opens file in append mode for each write)
+fn write_simulation_log(log_path: &str, message: &str) {
+    let _guard = SIMULATION_LOG_MUTEX.lock().unwrap();
+    if let Ok(mut file) = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)
+    {
+        if writeln!(file, "{}", message).is_ok() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Outcome of simulating one arbitrage transaction.
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    /// Transaction error reported by the simulation, if any.
+    pub err: Option<String>,
+    /// Program logs emitted during simulation.
+    pub logs: Vec<String>,
+    /// Compute units consumed by the simulated transaction.
+    pub units_consumed: Option<u64>,
+    /// Change of the wallet's base-token holdings in raw units (post - pre).
+    /// For WSOL the native lamport balance is included, so fees and rent count.
+    pub realized_profit: i64,
+}
+
+impl SimulationReport {
+    pub fn succeeded(&self) -> bool {
+        self.err.is_none()
+    }
+}
+
+/// Raw token amount of an SPL token account (`amount` lives at bytes 64..72).
+fn token_amount(data: &[u8]) -> u64 {
+    data.get(64..72)
+        .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+        .unwrap_or(0)
+}
+
+/// Base-token holdings of the wallet: ATA amount, plus lamports when the base is WSOL.
+fn holdings(base_mint: &Pubkey, ata: Option<(u64, Vec<u8>)>, wallet_lamports: u64) -> i128 {
+    let ata_amount = ata.map(|(_, data)| token_amount(&data)).unwrap_or(0) as i128;
+    if *base_mint == WSOL {
+        ata_amount + wallet_lamports as i128
+    } else {
+        ata_amount
+    }
+}
+
+/// Simulate `tx` and compute the realized profit in `base_mint` raw units.
+pub async fn simulate_trade_transaction(
+    engine: &Engine,
+    tx: &VersionedTransaction,
+    base_mint: &Pubkey,
+) -> Result<SimulationReport, anyhow::Error> {
+    let base_ata = engine
+        .tokens
+        .resolve(&base_mint.to_string())
+        .await?
+        .associated_token_address(&engine.pubkey);
+    let watched = [base_ata, engine.pubkey];
+
+    let pre_accounts = engine
+        .rpc_client
+        .get_multiple_accounts_with_commitment(&watched, CommitmentConfig::processed())
+        .await?
+        .value;
+    let pre = holdings(
+        base_mint,
+        pre_accounts[0].as_ref().map(|a| (a.lamports, a.data.clone())),
+        pre_accounts[1].as_ref().map(|a| a.lamports).unwrap_or(0),
+    );
+
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: false,
+        commitment: Some(CommitmentConfig::processed()),
+        accounts: Some(RpcSimulateTransactionAccountsConfig {
+            encoding: None, // base64
+            addresses: watched.iter().map(|k| k.to_string()).collect(),
+        }),
+        ..Default::default()
+    };
+
+    let RpcSimulateTransactionResult {
+        err,
+        logs,
+        accounts,
+        units_consumed,
+        ..
+    } = engine
+        .rpc_client
+        .simulate_transaction_with_config(tx, config)
+        .await?
+        .value;
+
+    let post_accounts = accounts.unwrap_or_default();
+    let post_ata = post_accounts
+        .first()
+        .cloned()
+        .flatten()
+        .and_then(|a| a.data.decode().map(|data| (a.lamports, data)));
+    let post_lamports = post_accounts
+        .get(1)
+        .cloned()
+        .flatten()
+        .map(|a| a.lamports)
+        .unwrap_or(0);
+    let post = holdings(base_mint, post_ata, post_lamports);
+
+    Ok(SimulationReport {
+        err: err.map(|e| format!("{:?}", e)),
+        logs: logs.unwrap_or_default(),
+        units_consumed,
+        realized_profit: (post - pre) as i64,
+    })
+}
+
+/// Decision of the pre-submit simulation gate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationGate {
+    /// `[simulation]` is disabled; nothing was simulated.
+    Disabled,
+    /// Simulated with a realized profit of at least the minimum.
+    Passed { units_consumed: Option<u64> },
+    /// The simulation failed, could not be run, or was not profitable enough.
+    Rejected,
+}
+
+impl SimulationGate {
+    /// Whether the transaction may be sent.
+    pub fn passed(&self) -> bool {
+        !matches!(self, SimulationGate::Rejected)
+    }
+
+    /// Compute units the passing simulation consumed, if one ran and reported them.
+    pub fn units_consumed(&self) -> Option<u64> {
+        match self {
+            SimulationGate::Passed { units_consumed } => *units_consumed,
+            _ => None,
+        }
+    }
+}
+
+/// Simulate `tx` when `[simulation] enabled = true` and decide whether it may be sent.
+///
+/// Passes when simulation is disabled, or when it succeeded with a realized profit of
+/// at least `min_profit_raw`. Every outcome is appended to the simulation log.
+pub async fn run_simulation_gate(
+    engine: &Engine,
+    tx: &VersionedTransaction,
+    base_mint: &Pubkey,
+    min_profit_raw: u64,
+    path: &str,
+) -> SimulationGate {
+    let config = engine.config();
+    let simulation = &config.simulation;
+    if !simulation.enabled {
+        return SimulationGate::Disabled;
+    }
+
+    let timestamp = Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
+    let signature = tx.signatures.first().copied().unwrap_or_default();
+
+    let report = match simulate_trade_transaction(engine, tx, base_mint).await {
+        Ok(report) => report,
+        Err(e) => {
+            warn!(error = %e, %path, "Simulation request failed; skipping submission");
+            write_simulation_log(
+                &simulation.log_path,
+                &format!(
+                    "[{}] [SIMULATION_ERROR] path={}, mint={}, signature={}, error={}",
+                    timestamp, path, base_mint, signature, e
+                ),
+            );
+            return SimulationGate::Rejected;
+        }
+    };
+
+    let passed = report.succeeded() && report.realized_profit >= min_profit_raw as i64;
+    let status = if !report.succeeded() {
+        "SIMULATION_FAILED"
+    } else if !passed {
+        "SIMULATION_UNPROFITABLE"
+    } else {
+        "SIMULATION_OK"
+    };
+
+    info!(
+        %path,
+        status,
+        realized_profit = report.realized_profit,
+        min_profit = min_profit_raw,
+        units_consumed = ?report.units_consumed,
+        error = ?report.err,
+        "Pre-submit simulation"
+    );
+
+    let log_lines: Vec<String> = report.logs.iter().map(|l| format!("    {}", l)).collect();
+    write_simulation_log(&simulation.log_path, &format!(
+        "[{}] [{}] path={}, mint={}, signature={}, realized_profit={}, min_profit={}, units_consumed={}, error={}\n  logs:\n{}",
+        timestamp,
+        status,
+        path,
+        base_mint,
+        signature,
+        report.realized_profit,
+        min_profit_raw,
+        report
+            .units_consumed
+            .map(|u| u.to_string())
+            .unwrap_or_else(|| "-".to_string()),
+        report.err.as_deref().unwrap_or("-"),
+        log_lines.join("\n")
+    ));
+
+    if passed {
+        SimulationGate::Passed {
+            units_consumed: report.units_consumed,
+        }
+    } else {
+        SimulationGate::Rejected
+    }
+}
@@ -0,0 +1,182 @@
+//! Regular (non-flash-loan) trade submission.
+//!
+//! Transaction layout:
+//!   [advance_nonce (durable nonce only), compute_unit_limit, compute_unit_price, ...setup_ixs, swap_ix,
+//!    tip]
+//!
+//! The tip is the `[jito]` tip for a bundle, else `[fees] relay_tip_sol` to
+//! `relay_tip_account` (left out when 0).
+
+use jupiter_swap_api_client::quote::QuoteResponse;
+use solana_sdk::{
+    compute_budget::ComputeBudgetInstruction,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, VersionedMessage},
+    native_token::LAMPORTS_PER_SOL,
+    pubkey::Pubkey,
+    signature::Signature,
+    system_instruction,
+    transaction::VersionedTransaction,
+};
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+
+use crate::*;
+
+/// Compile and sign a v0 transaction against `lifetime` (a leased durable nonce or a
+/// recent blockhash).
+///
+/// Prepends `advance_nonce_account` for a durable nonce and the compute budget
+/// instructions: `compute_unit_limit` (see [`right_sized_transaction`]) and
+/// `compute_unit_price` (micro-lamports, see [`TradeCost::for_trade`]).
+pub fn build_transaction(
+    engine: &Engine,
+    lifetime: &TxLifetime,
+    ixs: Vec<Instruction>,
+    alts: &[AddressLookupTableAccount],
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+) -> Result<VersionedTransaction, anyhow::Error> {
+    let mut all_ixs: Vec<Instruction> = lifetime.advance_nonce_instruction(&engine.pubkey).into_iter().collect();
+    all_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit));
+    all_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price));
+    all_ixs.extend(ixs);
+
+    let v0_msg = v0::Message::try_compile(&engine.pubkey, &all_ixs, alts, lifetime.blockhash())?;
+    let tx = VersionedTransaction::try_new(VersionedMessage::V0(v0_msg), &[&engine.keypair])?;
+    Ok(tx)
+}
+
+/// `[fees] relay_tip_sol` in lamports; 0 when no relay tip is paid.
+pub fn relay_tip_lamports(fees: &TxCostConfig) -> u64 {
+    (fees.tip_sol * LAMPORTS_PER_SOL as f64).round() as u64
+}
+
+/// Transfer of the relay tip to `[fees] relay_tip_account`, for trades sent to the
+/// submit endpoints; None when the tip is 0.
+pub fn relay_tip_instruction(engine: &Engine) -> Option<Instruction> {
+    let fees = engine.fees();
+    let lamports = relay_tip_lamports(&fees);
+    if lamports == 0 {
+        return None;
+    }
+    // Checked by Config::validate when the tip is set
+    let account = Pubkey::from_str(&fees.relay_tip_account).ok()?;
+    Some(system_instruction::transfer(&engine.pubkey, &account, lamports))
+}
+
+/// Build, optionally simulate, and submit a regular two-leg arbitrage trade.
+///
+/// `path` names the execution path (e.g. "polling", "big_trade") in logs.
+/// Returns the signature when the transaction was sent.
+pub async fn submit_regular_trade(
+    engine: &Arc<Engine>,
+    in_res: QuoteResponse,
+    out_res: QuoteResponse,
+    min_profit_amount: f64,
+    decimal: u8,
+    compute_unit_price: u64,
+    path: &str,
+) -> Option<Signature> {
+    submit_cycle_trade(engine, vec![in_res, out_res], min_profit_amount, decimal, compute_unit_price, path).await
+}
+
+/// Build, optionally simulate, and submit a cycle of any length (base -> ... -> base),
+/// paying `compute_unit_price` micro-lamports per compute unit.
+pub async fn submit_cycle_trade(
+    engine: &Arc<Engine>,
+    legs: Vec<QuoteResponse>,
+    min_profit_amount: f64,
+    decimal: u8,
+    compute_unit_price: u64,
+    path: &str,
+) -> Option<Signature> {
+    let base_mint = legs.first()?.input_mint;
+    let route = route_key(&legs, false);
+    let pending = PendingTrade::from_legs(path, &legs);
+    let min_profit_raw = (min_profit_amount * 10_f64.powf(decimal as f64)) as u64;
+
+    // A nonce lease is held until the nonce advances; released at once on any early return
+    let Some(lifetime) = engine.acquire_lifetime() else {
+        warn!(%path, "No free nonce account or recent blockhash; skipping");
+        engine.ledger.record_build(&pending.opportunity_id, path, BuildOutcome::Failed, Some("no free nonce account or recent blockhash"));
+        engine.metrics.record_submission(path, "no_nonce");
+        return None;
+    };
+
+    let ix = match get_cycle_swap_ix(engine, &legs, min_profit_raw, true).await {
+        Ok(ix) => ix,
+        Err(e) => {
+            error!(error = %e, %path, "Failed to get swap_ix");
+            engine.ledger.record_build(&pending.opportunity_id, path, BuildOutcome::Failed, Some(&e.to_string()));
+            engine.metrics.record_submission(path, "build_failed");
+            return None;
+        }
+    };
+
+    engine.priority_fees.observe_instruction(&ix.swap_instruction);
+
+    let mut raw_swap_ixs = Vec::new();
+    raw_swap_ixs.extend(ix.setup_instructions);
+    raw_swap_ixs.push(ix.swap_instruction);
+
+    // Sent as a Jito bundle when it carries a tip, else to the submit endpoints with the relay tip
+    let tip = bundle_tip(engine, &legs, decimal).await;
+    match &tip {
+        Some(tip) => raw_swap_ixs.push(tip.instruction(&engine.pubkey)),
+        None => raw_swap_ixs.extend(relay_tip_instruction(engine)),
+    }
+
+    let alts = fetch_alt(engine, ix.address_lookup_table_addresses).await;
+
+    let build = |compute_unit_limit| {
+        build_transaction(engine, &lifetime, raw_swap_ixs.clone(), &alts, compute_unit_limit, compute_unit_price)
+    };
+    let tx = match right_sized_transaction(engine, &route, build).await {
+        Ok(tx) => tx,
+        Err(e) => {
+            error!(error = %e, %path, "Failed to build transaction");
+            engine.ledger.record_build(&pending.opportunity_id, path, BuildOutcome::Failed, Some(&e.to_string()));
+            engine.metrics.record_submission(path, "build_failed");
+            return None;
+        }
+    };
+
+    let gate = run_simulation_gate(engine, &tx, &base_mint, min_profit_raw, path).await;
+    if !gate.passed() {
+        info!(%path, "Submission skipped by simulation gate");
+        engine.ledger.record_build(&pending.opportunity_id, path, BuildOutcome::Rejected, None);
+        engine.metrics.record_submission(path, "simulation_rejected");
+        return None;
+    }
+    let tx = right_size_simulated(engine, &route, tx, gate.units_consumed(), build);
+    engine.ledger.record_build(&pending.opportunity_id, path, BuildOutcome::Built, None);
+
+    info!(service = "RPC", %path, "Submitting transaction");
+
+    match submit_transaction(engine, &tx, tip.as_ref()).await {
+        Ok(broadcast) => {
+            let sig = broadcast.signature;
+            info!(
+                signature = %sig,
+                %path,
+                first_endpoint = %broadcast.first_endpoint,
+                sent_to = broadcast.sent_to,
+                "Transaction submitted"
+            );
+            let pending = pending.sent(sig, &lifetime).accepted_by(&broadcast.first_endpoint);
+            lifetime.sent();
+            engine.ledger.record_submission(&pending, None);
+            engine.metrics.record_submission(path, "sent");
+            spawn_landing_tracker(engine, pending);
+            Some(sig)
+        }
+        Err(e) => {
+            error!(error = %e, %path, "Transaction submission failed");
+            engine.ledger.record_submission(&pending, Some(&e.to_string()));
+            engine.metrics.record_submission(path, "send_failed");
+            None
+        }
+    }
+}
@@ -1,20 +1,24 @@
 use crate::{
-    JUPITER_EVENT_AUTH, JUPITER_PROGRAM_ADDR, PUBKEY, ROUTE_DISCRIMINATOR, RouteArgs,
-    TOKEN_PROGRAM_ID,
+    Engine, JUPITER_EVENT_AUTH, JUPITER_PROGRAM_ADDR, ROUTE_DISCRIMINATOR, RouteArgs,
 };
 use borsh::to_vec;
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
 };
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
 
+/// Jupiter `route` instruction for a cycle of `mother_token`, owned by `token_program`
+/// (SPL Token or Token-2022, see [`crate::TokenMeta::program_id`]).
 pub fn build_swap_ix(
+    engine: &Engine,
     route_args: RouteArgs,
     remaining_accounts: Vec<AccountMeta>,
     mother_token: Pubkey,
+    token_program: Pubkey,
 ) -> Instruction {
-    let user_source_token_account = get_associated_token_address(&PUBKEY.clone(), &mother_token);
+    let user_source_token_account =
+        get_associated_token_address_with_program_id(&engine.pubkey, &mother_token, &token_program);
 
     let mut data: Vec<u8> = Vec::new();
 
@@ -22,8 +26,8 @@ pub fn build_swap_ix(
     data.extend(to_vec(&route_args).unwrap());
 
     let mut accounts = vec![
-        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
-        AccountMeta::new(PUBKEY.clone(), true),
+        AccountMeta::new_readonly(token_program, false),
+        AccountMeta::new(engine.pubkey, true),
         AccountMeta::new(user_source_token_account, false),
         AccountMeta::new(user_source_token_account, false),
         AccountMeta::new_readonly(JUPITER_PROGRAM_ADDR, false),
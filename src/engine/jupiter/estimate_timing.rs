@@ -1,7 +1,14 @@
 //! Estimate Jupiter API latency: quote (2 calls) and build swap tx (swap_instructions).
+//!
+//! Every estimate is also observed in the `arb_jupiter_latency_seconds` histogram;
+//! [`run_jupiter_latency_probe`] repeats it on a timer when metrics are enabled.
 
+use std::sync::Arc;
 use std::time::Instant;
 
+use tokio::time::{interval, Duration};
+use tracing::debug;
+
 use crate::*;
 
 /// Estimated timings for one quote round-trip and one swap build (ms).
@@ -20,28 +27,21 @@ pub struct JupiterTimingEstimate {
 ///
 /// Uses: first base token from config, one target (USDC if base is SOL, else quote_mint),
 /// amount = geometric midpoint of amount_range, and min_profit for swap build.
-pub async fn estimate_jupiter_timing() -> Result<JupiterTimingEstimate, anyhow::Error> {
-    let base_config = BASE_TOKENS
+pub async fn estimate_jupiter_timing(engine: &Engine) -> Result<JupiterTimingEstimate, anyhow::Error> {
+    let base_tokens = engine.base_tokens();
+    let base_config = base_tokens
         .first()
         .ok_or_else(|| anyhow::anyhow!("No base token in config"))?;
     let mother_token = base_config.mint.as_str();
 
-    let (decimal, symbol) = POPULAR_TOKEN_INFO
-        .iter()
-        .find(|t| t.mint == mother_token)
-        .map(|t| (t.decimals, t.symbol))
-        .unwrap_or_else(|| {
-            if mother_token == "So11111111111111111111111111111111111111112" {
-                (9, "SOL")
-            } else {
-                (6, "UNKNOWN")
-            }
-        });
+    let token = engine.tokens.resolve(mother_token).await?;
+    let (decimal, symbol) = (token.decimals, token.symbol.as_str());
 
+    let configured_target = engine.target_token();
     let target_token = if symbol == "SOL" || symbol == "WSOL" || mother_token == "So11111111111111111111111111111111111111112" {
         "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v" // USDC
     } else {
-        TARGET_TOKEN.as_str()
+        configured_target.as_str()
     };
 
     let [min_f, max_f] = base_config.amount_range;
@@ -50,14 +50,20 @@ pub async fn estimate_jupiter_timing() -> Result<JupiterTimingEstimate, anyhow::
 
     // Time: one full quote (2 Jupiter quote API calls)
     let quote_start = Instant::now();
-    let (_, _, in_res, out_res) = get_quote_polling(amount, mother_token, target_token).await?;
-    let quote_ms = quote_start.elapsed().as_millis() as u64;
+    let (_, _, in_res, out_res) = get_quote_polling(engine, amount, mother_token, target_token).await?;
+    let quote_elapsed = quote_start.elapsed();
+    let quote_ms = quote_elapsed.as_millis() as u64;
 
     // Time: build swap instructions (1 Jupiter swap_instructions API call)
     let min_profit_raw = (base_config.min_profit * 10_f64.powf(decimal as f64)) as u64;
     let swap_start = Instant::now();
-    let _ = get_swap_ix(in_res, out_res, min_profit_raw).await?;
-    let swap_build_ms = swap_start.elapsed().as_millis() as u64;
+    let _ = get_swap_ix(engine, in_res, out_res, min_profit_raw).await?;
+    let swap_elapsed = swap_start.elapsed();
+    let swap_build_ms = swap_elapsed.as_millis() as u64;
+
+    let latency = &engine.metrics.jupiter_latency;
+    latency.with_label_values(&["quote_round_trip"]).observe(quote_elapsed.as_secs_f64());
+    latency.with_label_values(&["swap_instructions"]).observe(swap_elapsed.as_secs_f64());
 
     Ok(JupiterTimingEstimate {
         quote_ms,
@@ -65,3 +71,23 @@ pub async fn estimate_jupiter_timing() -> Result<JupiterTimingEstimate, anyhow::
         total_ms: quote_ms + swap_build_ms,
     })
 }
+
+/// Re-run [`estimate_jupiter_timing`] every `[metrics] jupiter_probe_interval_secs`
+/// (after the startup estimate), forever. Returns immediately if the interval is 0.
+pub async fn run_jupiter_latency_probe(engine: Arc<Engine>) {
+    let interval_secs = engine.config().metrics.jupiter_probe_interval_secs;
+    if interval_secs == 0 {
+        return;
+    }
+    let mut ticker = interval(Duration::from_secs(interval_secs));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately; the startup estimate already covered it
+    ticker.tick().await;
+    loop {
+        ticker.tick().await;
+        match estimate_jupiter_timing(&engine).await {
+            Ok(t) => debug!(quote_ms = t.quote_ms, swap_build_ms = t.swap_build_ms, "Jupiter latency probe"),
+            Err(e) => debug!(error = ?e, "Jupiter latency probe failed"),
+        }
+    }
+}
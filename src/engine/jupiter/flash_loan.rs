@@ -8,27 +8,29 @@
 //! the borsh-encoded u64.
 //!
 //! Transaction layout for flash-loan-wrapped arbitrage:
-//!   [advance_nonce, compute_unit_limit, compute_unit_price, create_ata,
-//!    flash_borrow, ...setup_ixs..., swap_ix, flash_payback]
+//!   [advance_nonce (durable nonce only), compute_unit_limit, compute_unit_price, create_ata,
+//!    flash_borrow, ...setup_ixs..., swap_ix, flash_payback, tip (Jito bundle only)]
 
 use solana_sdk::{
     compute_budget::ComputeBudgetInstruction,
     instruction::{AccountMeta, Instruction},
     message::{v0, VersionedMessage},
     pubkey::Pubkey,
+    signature::Signature,
     sysvar,
-    system_instruction::advance_nonce_account,
     transaction::VersionedTransaction,
 };
 use spl_associated_token_account::{
-    get_associated_token_address,
+    get_associated_token_address_with_program_id,
     instruction::create_associated_token_account_idempotent,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
+
+use std::sync::Arc;
 
 use crate::{
-    FEES, NONCE_ADDR, PRIVATE_KEY, PUBKEY, SUBMIT_CLIENT, TOKEN_PROGRAM_ID,
-    fetch_alt, get_nonce, get_swap_ix_flash_loan,
+    BuildOutcome, Engine, PendingTrade, bundle_tip, fetch_alt, get_cycle_swap_ix, right_size_simulated,
+    right_sized_transaction, route_key, run_simulation_gate, spawn_landing_tracker, submit_transaction,
 };
 
 /// Associated Token Program ID (constant from IDL).
@@ -96,6 +98,7 @@ fn build_flash_loan_accounts(
     ctx: &FlashLoanContext,
     user: &Pubkey,
     user_token_account: &Pubkey,
+    token_program: &Pubkey,
 ) -> Vec<AccountMeta> {
     vec![
         AccountMeta::new(*user, true),                                                    // signer
@@ -108,7 +111,7 @@ fn build_flash_loan_accounts(
         AccountMeta::new(ctx.reserve_info.vault, false),                                  // vault
         AccountMeta::new_readonly(ctx.reserve_info.liquidity, false),                     // liquidity
         AccountMeta::new_readonly(ctx.reserve_info.liquidity_program, false),             // liquidity_program
-        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),                               // token_program
+        AccountMeta::new_readonly(*token_program, false),                                 // token_program
         AccountMeta::new_readonly(ASSOCIATED_TOKEN_PROGRAM_ID, false),                    // associated_token_program
         AccountMeta::new_readonly(solana_sdk::system_program::ID, false),                  // system_program
         AccountMeta::new_readonly(sysvar::instructions::id(), false),                     // instruction_sysvar
@@ -126,17 +129,19 @@ fn build_flash_loan_data(discriminator: &[u8; 8], amount: u64) -> Vec<u8> {
 /// Build a `flashloan_borrow` instruction.
 ///
 /// Borrows `amount` of the reserve's token from the liquidity pool into the
-/// user's associated token account.
+/// user's associated token account under `token_program`, the mint's owner.
 pub fn build_flash_borrow_ix(
     ctx: &FlashLoanContext,
     amount: u64,
     user: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
-    let user_token_account = get_associated_token_address(user, &ctx.reserve_info.token_mint);
+    let user_token_account =
+        get_associated_token_address_with_program_id(user, &ctx.reserve_info.token_mint, token_program);
 
     Instruction {
         program_id: ctx.program_id,
-        accounts: build_flash_loan_accounts(ctx, user, &user_token_account),
+        accounts: build_flash_loan_accounts(ctx, user, &user_token_account, token_program),
         data: build_flash_loan_data(&FLASH_BORROW_DISCRIMINATOR, amount),
     }
 }
@@ -150,12 +155,14 @@ pub fn build_flash_payback_ix(
     ctx: &FlashLoanContext,
     amount: u64,
     user: &Pubkey,
+    token_program: &Pubkey,
 ) -> Instruction {
-    let user_token_account = get_associated_token_address(user, &ctx.reserve_info.token_mint);
+    let user_token_account =
+        get_associated_token_address_with_program_id(user, &ctx.reserve_info.token_mint, token_program);
 
     Instruction {
         program_id: ctx.program_id,
-        accounts: build_flash_loan_accounts(ctx, user, &user_token_account),
+        accounts: build_flash_loan_accounts(ctx, user, &user_token_account, token_program),
         data: build_flash_loan_data(&FLASH_PAYBACK_DISCRIMINATOR, amount),
     }
 }
@@ -170,7 +177,7 @@ pub fn build_flash_payback_ix(
 /// have precise control over instruction ordering:
 ///
 /// ```text
-/// [0] advance_nonce_account
+/// [0] advance_nonce_account     (durable nonce only; the rest shift down without it)
 /// [1] set_compute_unit_limit
 /// [2] set_compute_unit_price
 /// [3] create_ata_idempotent     (ensure user's ATA exists)
@@ -178,14 +185,32 @@ pub fn build_flash_payback_ix(
 /// [5..N-1] setup + swap ixs    (Jupiter arbitrage)
 /// [N] flash_payback
 /// ```
+///
+/// Returns the signature when the transaction was sent.
 pub async fn submit_flash_loan_trade(
+    engine: &Arc<Engine>,
     in_res: jupiter_swap_api_client::quote::QuoteResponse,
     out_res: jupiter_swap_api_client::quote::QuoteResponse,
     min_profit_amount: f64,
     decimal: u8,
+    compute_unit_price: u64,
+    flash_ctx: &FlashLoanContext,
+) -> Option<Signature> {
+    submit_flash_loan_cycle(engine, vec![in_res, out_res], min_profit_amount, decimal, compute_unit_price, flash_ctx).await
+}
+
+/// [`submit_flash_loan_trade`] for a cycle of any length (base -> ... -> base).
+pub async fn submit_flash_loan_cycle(
+    engine: &Arc<Engine>,
+    legs: Vec<jupiter_swap_api_client::quote::QuoteResponse>,
+    min_profit_amount: f64,
+    decimal: u8,
+    compute_unit_price: u64,
     flash_ctx: &FlashLoanContext,
-) {
-    let borrow_amount = in_res.in_amount;
+) -> Option<Signature> {
+    let borrow_amount = legs.first()?.in_amount;
+    let pending = PendingTrade::from_legs("flash_loan", &legs);
+    let min_profit_raw = (min_profit_amount * 10_f64.powf(decimal as f64)) as u64;
 
     info!(
         borrow_amount = borrow_amount,
@@ -194,19 +219,34 @@ pub async fn submit_flash_loan_trade(
         "STEP 1: Starting flash loan trade submission"
     );
 
+    // The borrowed mint's token program (SPL Token or Token-2022) owns the ATA
+    let token_program = match engine.tokens.resolve(&flash_ctx.reserve_info.token_mint.to_string()).await {
+        Ok(token) => token.program_id,
+        Err(e) => {
+            error!(error = ?e, "STEP 1 FAILED: token metadata for the borrowed mint unavailable");
+            engine.ledger.record_build(&pending.opportunity_id, &pending.path, BuildOutcome::Failed, Some(&e.to_string()));
+            engine.metrics.record_submission(&pending.path, "build_failed");
+            return None;
+        }
+    };
+
+    // A nonce lease is held until the nonce advances; released at once on any early return
+    let Some(lifetime) = engine.acquire_lifetime() else {
+        warn!("STEP 1 SKIPPED: no free nonce account or recent blockhash");
+        engine.ledger.record_build(&pending.opportunity_id, &pending.path, BuildOutcome::Failed, Some("no free nonce account or recent blockhash"));
+        engine.metrics.record_submission(&pending.path, "no_nonce");
+        return None;
+    };
+
     // Build swap instructions with wrap_and_unwrap_sol = false since the
     // flash loan provides tokens directly in SPL form.
-    let ix = match get_swap_ix_flash_loan(
-        in_res,
-        out_res,
-        (min_profit_amount * 10_f64.powf(decimal as f64)) as u64,
-    )
-    .await
-    {
+    let ix = match get_cycle_swap_ix(engine, &legs, min_profit_raw, false).await {
         Ok(ix) => ix,
         Err(e) => {
-            error!(error = ?e, "STEP 2 FAILED: get_swap_ix_flash_loan failed");
-            return;
+            error!(error = ?e, "STEP 2 FAILED: get_cycle_swap_ix failed");
+            engine.ledger.record_build(&pending.opportunity_id, &pending.path, BuildOutcome::Failed, Some(&e.to_string()));
+            engine.metrics.record_submission(&pending.path, "build_failed");
+            return None;
         }
     };
 
@@ -221,38 +261,40 @@ pub async fn submit_flash_loan_trade(
     let mut all_ixs: Vec<Instruction> = Vec::new();
 
     // [0] Advance nonce account
-    all_ixs.push(advance_nonce_account(&NONCE_ADDR, &PUBKEY));
-    info!(nonce_addr = %*NONCE_ADDR, "STEP 3a: Pushed advance_nonce_account ix");
+    if let Some(advance_nonce) = lifetime.advance_nonce_instruction(&engine.pubkey) {
+        all_ixs.push(advance_nonce);
+        info!(nonce_addr = ?lifetime.nonce_account(), "STEP 3a: Pushed advance_nonce_account ix");
+    }
 
-    // [1] Compute unit limit
+    // [1] Compute unit limit, sized when the transaction is compiled (STEP 7)
+    let compute_unit_limit_index = all_ixs.len();
     all_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(
-        FEES.compute_units as u32,
+        engine.fees().compute_units as u32,
     ));
-    info!(compute_units = FEES.compute_units, "STEP 3b: Pushed compute_unit_limit ix");
+    info!(compute_units = engine.fees().compute_units, "STEP 3b: Pushed compute_unit_limit ix");
 
     // [2] Compute unit price (priority fee)
-    all_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
-        FEES.priority_lamports,
-    ));
-    info!(priority_lamports = FEES.priority_lamports, "STEP 3c: Pushed compute_unit_price ix");
+    all_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(compute_unit_price));
+    info!(compute_unit_price, "STEP 3c: Pushed compute_unit_price ix");
 
     // [3] Ensure user's ATA for the borrowed token exists
     all_ixs.push(create_associated_token_account_idempotent(
-        &PUBKEY,                            // payer
-        &PUBKEY,                            // wallet (owner of the ATA)
+        &engine.pubkey,                     // payer
+        &engine.pubkey,                     // wallet (owner of the ATA)
         &flash_ctx.reserve_info.token_mint, // mint
-        &TOKEN_PROGRAM_ID,                  // token program
+        &token_program,                     // token program
     ));
     info!(token_mint = %flash_ctx.reserve_info.token_mint, "STEP 3d: Pushed create_ata_idempotent ix");
 
     // [4] Flash borrow
-    all_ixs.push(build_flash_borrow_ix(flash_ctx, borrow_amount, &PUBKEY));
+    all_ixs.push(build_flash_borrow_ix(flash_ctx, borrow_amount, &engine.pubkey, &token_program));
     info!(borrow_amount = borrow_amount, "STEP 3e: Pushed flash_borrow ix");
 
     // Destructure swap instruction response to avoid partial-move issues.
     let setup_instructions = ix.setup_instructions;
     let swap_instruction = ix.swap_instruction;
     let alt_addresses = ix.address_lookup_table_addresses;
+    engine.priority_fees.observe_instruction(&swap_instruction);
 
     // [5..N-1] Jupiter swap setup + swap
     let setup_count = setup_instructions.len();
@@ -264,41 +306,67 @@ pub async fn submit_flash_loan_trade(
     all_ixs.push(build_flash_payback_ix(
         flash_ctx,
         borrow_amount,
-        &PUBKEY,
+        &engine.pubkey,
+        &token_program,
     ));
     info!(borrow_amount = borrow_amount, "STEP 3g: Pushed flash_payback ix");
 
+    // [N+1] Jito tip, when sent as a bundle
+    let tip = bundle_tip(engine, &legs, decimal).await;
+    if let Some(tip) = &tip {
+        all_ixs.push(tip.instruction(&engine.pubkey));
+        info!(tip_account = %tip.account, tip_lamports = tip.lamports, "STEP 3h: Pushed Jito tip ix");
+    }
+
     // ── Build and sign VersionedTransaction ─────────────────────────────
 
     let total_ix_count = all_ixs.len();
     info!(total_ix_count = total_ix_count, "STEP 4: Assembled all instructions");
 
-    let nonce_data = get_nonce();
-    let recent_blockhash = nonce_data.blockhash();
-    info!(blockhash = %recent_blockhash, "STEP 5: Got nonce blockhash");
+    let recent_blockhash = lifetime.blockhash();
+    info!(blockhash = %recent_blockhash, "STEP 5: Got transaction blockhash");
 
-    let alts = fetch_alt(alt_addresses).await;
+    let alts = fetch_alt(engine, alt_addresses).await;
     info!(alt_count = alts.len(), "STEP 6: Fetched address lookup tables");
 
-    let v0_msg = match v0::Message::try_compile(&PUBKEY, &all_ixs, &alts, recent_blockhash) {
-        Ok(m) => m,
-        Err(e) => {
-            error!(error = %e, "STEP 7 FAILED: v0::Message::try_compile failed");
-            return;
-        }
+    // Compile and sign with the given compute unit limit
+    let build = |compute_unit_limit: u32| -> Result<VersionedTransaction, anyhow::Error> {
+        let mut ixs = all_ixs.clone();
+        ixs[compute_unit_limit_index] = ComputeBudgetInstruction::set_compute_unit_limit(compute_unit_limit);
+        let v0_msg = v0::Message::try_compile(&engine.pubkey, &ixs, &alts, recent_blockhash)?;
+        Ok(VersionedTransaction::try_new(VersionedMessage::V0(v0_msg), &[&engine.keypair])?)
     };
-    info!("STEP 7: Compiled v0 message successfully");
-
-    let versioned_msg = VersionedMessage::V0(v0_msg);
+    let route = route_key(&legs, true);
 
-    let tx = match VersionedTransaction::try_new(versioned_msg, &[&*PRIVATE_KEY]) {
+    let tx = match right_sized_transaction(engine, &route, build).await {
         Ok(t) => t,
         Err(e) => {
-            error!(error = %e, "STEP 8 FAILED: VersionedTransaction::try_new (signing) failed");
-            return;
+            error!(error = %e, "STEP 7 FAILED: compiling or signing the transaction failed");
+            engine.ledger.record_build(&pending.opportunity_id, &pending.path, BuildOutcome::Failed, Some(&e.to_string()));
+            engine.metrics.record_submission(&pending.path, "build_failed");
+            return None;
         }
     };
-    info!("STEP 8: Signed transaction successfully");
+    info!("STEP 8: Compiled and signed transaction successfully");
+
+    // ── Pre-submit simulation gate ──────────────────────────────────────
+
+    let gate = run_simulation_gate(
+        engine,
+        &tx,
+        &flash_ctx.reserve_info.token_mint,
+        min_profit_raw,
+        "flash_loan",
+    )
+    .await;
+    if !gate.passed() {
+        info!("STEP 9 SKIPPED: Flash loan trade rejected by simulation gate");
+        engine.ledger.record_build(&pending.opportunity_id, &pending.path, BuildOutcome::Rejected, None);
+        engine.metrics.record_submission(&pending.path, "simulation_rejected");
+        return None;
+    }
+    let tx = right_size_simulated(engine, &route, tx, gate.units_consumed(), build);
+    engine.ledger.record_build(&pending.opportunity_id, &pending.path, BuildOutcome::Built, None);
 
     // ── Submit via RPC ──────────────────────────────────────────────────
 
@@ -308,12 +376,27 @@ pub async fn submit_flash_loan_trade(
         "STEP 9: Submitting flash-loan-wrapped trade via RPC"
     );
 
-    match SUBMIT_CLIENT.send_transaction(&tx).await {
-        Ok(sig) => {
-            info!(signature = %sig, "STEP 9 SUCCESS: Flash loan trade submitted");
+    match submit_transaction(engine, &tx, tip.as_ref()).await {
+        Ok(broadcast) => {
+            let sig = broadcast.signature;
+            info!(
+                signature = %sig,
+                first_endpoint = %broadcast.first_endpoint,
+                sent_to = broadcast.sent_to,
+                "STEP 9 SUCCESS: Flash loan trade submitted"
+            );
+            let pending = pending.sent(sig, &lifetime).accepted_by(&broadcast.first_endpoint);
+            lifetime.sent();
+            engine.ledger.record_submission(&pending, None);
+            engine.metrics.record_submission(&pending.path, "sent");
+            spawn_landing_tracker(engine, pending);
+            Some(sig)
         }
         Err(e) => {
             error!(error = %e, "STEP 9 FAILED: Flash loan trade submission failed");
+            engine.ledger.record_submission(&pending, Some(&e.to_string()));
+            engine.metrics.record_submission(&pending.path, "send_failed");
+            None
         }
     }
 }
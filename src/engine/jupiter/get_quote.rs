@@ -1,14 +1,28 @@
-use jupiter_swap_api_client::quote::{QuoteRequest, QuoteResponse};
+use jupiter_swap_api_client::quote::QuoteRequest;
 use solana_sdk::pubkey::Pubkey;
 
-use crate::JUPITER_CLIENT;
+use crate::{Engine, QuoteMode, QuoteProvider, RoundTripQuote};
 
-/// Get quote for big trade mode (with only_direct_routes and exclude_dexes)
+/// Get quote for big trade mode from the instrument's quote provider.
 pub async fn get_quote_big_trade(
+    engine: &Engine,
     input_amount: u64,
     mother_token: &str,
     arb_token: &str,
-) -> Result<(u64, u64, QuoteResponse, QuoteResponse), anyhow::Error> {
+) -> Result<RoundTripQuote, anyhow::Error> {
+    engine
+        .quote_provider(mother_token)
+        .round_trip_quote(input_amount, mother_token, arb_token, QuoteMode::BigTrade)
+        .await
+}
+
+/// Round trip for big trade mode (with only_direct_routes and exclude_dexes)
+pub async fn round_trip_big_trade<P: QuoteProvider + ?Sized>(
+    provider: &P,
+    input_amount: u64,
+    mother_token: &str,
+    arb_token: &str,
+) -> Result<RoundTripQuote, anyhow::Error> {
     let quote_request_1 = QuoteRequest {
         amount: input_amount,
         input_mint: Pubkey::from_str_const(mother_token),
@@ -19,7 +33,7 @@ pub async fn get_quote_big_trade(
         ..QuoteRequest::default()
     };
 
-    let quote_response_1 = JUPITER_CLIENT.quote(&quote_request_1).await?;
+    let quote_response_1 = provider.quote(&quote_request_1).await?;
 
     let dexes_used_in_req_1 = quote_response_1
         .route_plan
@@ -39,7 +53,7 @@ pub async fn get_quote_big_trade(
         ..QuoteRequest::default()
     };
 
-    let quote_response_2 = JUPITER_CLIENT.quote(&quote_request_2).await?;
+    let quote_response_2 = provider.quote(&quote_request_2).await?;
 
     Ok((
         input_amount,
@@ -49,17 +63,31 @@ pub async fn get_quote_big_trade(
     ))
 }
 
-/// Get quote for polling mode
+/// Get quote for polling mode from the instrument's quote provider.
+pub async fn get_quote_polling(
+    engine: &Engine,
+    input_amount: u64,
+    mother_token: &str,
+    arb_token: &str,
+) -> Result<RoundTripQuote, anyhow::Error> {
+    engine
+        .quote_provider(mother_token)
+        .round_trip_quote(input_amount, mother_token, arb_token, QuoteMode::Polling)
+        .await
+}
+
+/// Round trip for polling mode
 /// Based on Jupiter API documentation: https://dev.jup.ag/api-reference/swap/quote
 /// - No only_direct_routes: Allows multi-hop routes for better opportunities (default: false)
 /// - No exclude_dexes: Allows all DEXes to be considered
 /// - Uses default restrict_intermediate_tokens (true): Reduces exposure to high slippage routes
 /// - slippage_bps: 0 for precise arbitrage calculations
-pub async fn get_quote_polling(
+pub async fn round_trip_polling<P: QuoteProvider + ?Sized>(
+    provider: &P,
     input_amount: u64,
     mother_token: &str,
     arb_token: &str,
-) -> Result<(u64, u64, QuoteResponse, QuoteResponse), anyhow::Error> {
+) -> Result<RoundTripQuote, anyhow::Error> {
     // Request 1: mother_token -> arb_token
     // No only_direct_routes: allows multi-hop routes (default: false)
     // No restrict_intermediate_tokens override: uses default (true) for stability
@@ -72,7 +100,7 @@ pub async fn get_quote_polling(
         ..QuoteRequest::default()
     };
 
-    let quote_response_1 = JUPITER_CLIENT.quote(&quote_request_1).await?;
+    let quote_response_1 = provider.quote(&quote_request_1).await?;
 
     // Request 2: arb_token -> mother_token
     // No exclude_dexes: allows all DEXes (no restrictions)
@@ -87,7 +115,7 @@ pub async fn get_quote_polling(
         ..QuoteRequest::default()
     };
 
-    let quote_response_2 = JUPITER_CLIENT.quote(&quote_request_2).await?;
+    let quote_response_2 = provider.quote(&quote_request_2).await?;
 
     Ok((
         input_amount,
@@ -99,9 +127,10 @@ pub async fn get_quote_polling(
 
 // Keep the old function for backward compatibility, but it uses big_trade mode
 pub async fn get_quote(
+    engine: &Engine,
     input_amount: u64,
     mother_token: &str,
     arb_token: &str,
-) -> Result<(u64, u64, QuoteResponse, QuoteResponse), anyhow::Error> {
-    get_quote_big_trade(input_amount, mother_token, arb_token).await
+) -> Result<RoundTripQuote, anyhow::Error> {
+    get_quote_big_trade(engine, input_amount, mother_token, arb_token).await
 }
@@ -0,0 +1,234 @@
+//! Durable SQLite ledger of everything the bot did with an opportunity.
+//!
+//! Each opportunity gets a stable ID derived from its quote legs ([`opportunity_id`]),
+//! and every later stage — the legs' quotes, build attempts, submissions and landing
+//! confirmations — is written against that ID. The `landings.jsonl` file of the
+//! landing tracker stays the quick append-only view; this database is the one to
+//! query (see [`report`]) for e.g. net profit per target token over the last week.
+//!
+//! Writes never fail or block the trading path: they are queued to one writer thread,
+//! errors are logged and the trade proceeds. Reads wait for the queued writes first.
+
+use std::path::Path;
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use jupiter_swap_api_client::quote::QuoteResponse;
+use rusqlite::Connection;
+use solana_sdk::hash::hash;
+use tracing::warn;
+
+use crate::app::config::LedgerConfig;
+
+pub mod record;
+pub mod report;
+
+pub use record::*;
+pub use report::*;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS opportunities (
+    id            TEXT PRIMARY KEY,
+    discovered_at TEXT NOT NULL,
+    source        TEXT NOT NULL,
+    instrument    TEXT NOT NULL,
+    target        TEXT NOT NULL,
+    route         TEXT NOT NULL,
+    in_amount     INTEGER NOT NULL,
+    out_amount    INTEGER NOT NULL,
+    gross_profit  INTEGER NOT NULL,
+    net_profit    INTEGER NOT NULL,
+    decimals      INTEGER NOT NULL,
+    trigger_tx    TEXT
+);
+CREATE INDEX IF NOT EXISTS opportunities_discovered_at ON opportunities (discovered_at);
+
+CREATE TABLE IF NOT EXISTS quotes (
+    opportunity_id TEXT NOT NULL REFERENCES opportunities (id),
+    leg            INTEGER NOT NULL,
+    input_mint     TEXT NOT NULL,
+    output_mint    TEXT NOT NULL,
+    in_amount      INTEGER NOT NULL,
+    out_amount     INTEGER NOT NULL,
+    context_slot   INTEGER NOT NULL,
+    dexes          TEXT NOT NULL,
+    PRIMARY KEY (opportunity_id, leg)
+);
+
+CREATE TABLE IF NOT EXISTS build_attempts (
+    id             INTEGER PRIMARY KEY AUTOINCREMENT,
+    opportunity_id TEXT NOT NULL,
+    attempted_at   TEXT NOT NULL,
+    path           TEXT NOT NULL,
+    outcome        TEXT NOT NULL,
+    error          TEXT
+);
+CREATE INDEX IF NOT EXISTS build_attempts_opportunity ON build_attempts (opportunity_id);
+
+CREATE TABLE IF NOT EXISTS submissions (
+    id              INTEGER PRIMARY KEY AUTOINCREMENT,
+    opportunity_id  TEXT NOT NULL,
+    submitted_at    TEXT NOT NULL,
+    path            TEXT NOT NULL,
+    signature       TEXT UNIQUE,
+    blockhash       TEXT NOT NULL,
+    expected_profit INTEGER NOT NULL,
+    error           TEXT
+);
+CREATE INDEX IF NOT EXISTS submissions_opportunity ON submissions (opportunity_id);
+
+CREATE TABLE IF NOT EXISTS confirmations (
+    signature       TEXT PRIMARY KEY,
+    opportunity_id  TEXT NOT NULL,
+    recorded_at     TEXT NOT NULL,
+    status          TEXT NOT NULL,
+    slot            INTEGER,
+    realized_profit INTEGER,
+    fee_lamports    INTEGER,
+    error           TEXT
+);
+";
+
+/// A queued write, run on the writer thread.
+type WriteFn = Box<dyn FnOnce(&mut Connection) -> rusqlite::Result<()> + Send>;
+
+enum Job {
+    Write(&'static str, WriteFn),
+    /// Answered once every write queued before it has run.
+    Flush(Sender<()>),
+}
+
+/// Handle to the SQLite ledger; a no-op unless `[ledger] enabled = true`.
+pub struct Ledger {
+    conn: Option<Arc<Mutex<Connection>>>,
+    jobs: Option<Sender<Job>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl Ledger {
+    /// Open the ledger configured by `[ledger]`, or a disabled one.
+    pub fn new(config: &LedgerConfig) -> Result<Self, anyhow::Error> {
+        if !config.enabled {
+            return Ok(Self::disabled());
+        }
+        Self::open(&config.path)
+    }
+
+    /// A ledger that records nothing.
+    pub fn disabled() -> Self {
+        Self {
+            conn: None,
+            jobs: None,
+            writer: None,
+        }
+    }
+
+    /// Open (creating if needed) the database at `path`, apply the schema and start the
+    /// writer thread.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let path = path.as_ref();
+        let conn = Connection::open(path)
+            .map_err(|e| anyhow::anyhow!("Failed to open ledger {}: {}", path.display(), e))?;
+        // The bot writes while the `ledger` report reads
+        conn.busy_timeout(Duration::from_secs(5))?;
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+        conn.execute_batch(SCHEMA)?;
+
+        let conn = Arc::new(Mutex::new(conn));
+        let (jobs, queue) = mpsc::channel();
+        let writer = std::thread::Builder::new()
+            .name("ledger-writer".to_string())
+            .spawn({
+                let conn = conn.clone();
+                move || {
+                    for job in queue {
+                        match job {
+                            Job::Write(table, f) => {
+                                if let Err(e) = f(&mut conn.lock().unwrap()) {
+                                    warn!(error = %e, %table, "Ledger write failed");
+                                }
+                            }
+                            Job::Flush(done) => {
+                                let _ = done.send(());
+                            }
+                        }
+                    }
+                }
+            })?;
+        Ok(Self {
+            conn: Some(conn),
+            jobs: Some(jobs),
+            writer: Some(writer),
+        })
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.conn.is_some()
+    }
+
+    /// Queue a write for the writer thread; failures are logged there.
+    fn write(&self, table: &'static str, f: impl FnOnce(&mut Connection) -> rusqlite::Result<()> + Send + 'static) {
+        let Some(jobs) = &self.jobs else {
+            return;
+        };
+        if jobs.send(Job::Write(table, Box::new(f))).is_err() {
+            warn!(%table, "Ledger writer stopped; write dropped");
+        }
+    }
+
+    /// Wait until every write queued so far has run.
+    pub fn flush(&self) {
+        let Some(jobs) = &self.jobs else {
+            return;
+        };
+        let (done, wait) = mpsc::channel();
+        if jobs.send(Job::Flush(done)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+
+    /// Run a read query after the queued writes; errors if the ledger is disabled.
+    fn read<T>(&self, f: impl FnOnce(&Connection) -> rusqlite::Result<T>) -> Result<T, anyhow::Error> {
+        let conn = self
+            .conn
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Ledger is disabled; set [ledger] enabled = true"))?;
+        self.flush();
+        let conn = conn.lock().unwrap();
+        Ok(f(&conn)?)
+    }
+}
+
+impl Drop for Ledger {
+    /// Let the writer thread finish the queued writes.
+    fn drop(&mut self) {
+        self.jobs.take();
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+/// Stable ID of the opportunity quoted by `legs`: the same legs always map to the same
+/// ID, so discovery, submission and landing can each derive it independently.
+pub fn opportunity_id(legs: &[QuoteResponse]) -> String {
+    let mut bytes = Vec::with_capacity(legs.len() * 88);
+    for leg in legs {
+        bytes.extend_from_slice(leg.input_mint.as_ref());
+        bytes.extend_from_slice(leg.output_mint.as_ref());
+        bytes.extend_from_slice(&leg.in_amount.to_le_bytes());
+        bytes.extend_from_slice(&leg.out_amount.to_le_bytes());
+        bytes.extend_from_slice(&leg.context_slot.to_le_bytes());
+    }
+    let mut id = hash(&bytes).to_string();
+    id.truncate(16);
+    id
+}
+
+/// Ledger timestamp: RFC 3339 UTC with milliseconds, so text order is time order.
+pub fn ledger_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
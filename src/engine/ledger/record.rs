@@ -0,0 +1,170 @@
+//! Ledger writes, one per pipeline stage.
+
+use chrono::Utc;
+use rusqlite::params;
+
+use crate::{route_label, CycleQuote, LandingRecord, PendingTrade};
+
+use super::{ledger_timestamp, opportunity_id, Ledger};
+
+/// Result of turning an opportunity into a signed transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildOutcome {
+    /// Signed and ready to send.
+    Built,
+    /// Swap instructions, message compilation or signing failed.
+    Failed,
+    /// Built, but rejected by the pre-submit simulation gate.
+    Rejected,
+}
+
+impl BuildOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuildOutcome::Built => "built",
+            BuildOutcome::Failed => "failed",
+            BuildOutcome::Rejected => "rejected",
+        }
+    }
+}
+
+impl Ledger {
+    /// Record a discovered opportunity and its leg quotes; returns its ID.
+    ///
+    /// `source` is the discovery mode ("polling", "big_trade"), `route` the opportunity's
+    /// [`CycleQuote::label`], `net_profit` the raw profit after transaction costs and
+    /// `trigger` the transaction that revealed it.
+    /// Seeing the same legs again is a no-op.
+    pub fn record_opportunity(
+        &self,
+        source: &str,
+        opportunity: &CycleQuote,
+        route: &str,
+        net_profit: i64,
+        decimals: u8,
+        trigger: Option<&str>,
+    ) -> String {
+        let id = opportunity_id(&opportunity.legs);
+        let Some(first) = opportunity.legs.first() else {
+            return id;
+        };
+        let target = opportunity.legs[1..]
+            .iter()
+            .map(|leg| leg.input_mint.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let quotes: Vec<(String, String, i64, i64, i64, String)> = opportunity
+            .legs
+            .iter()
+            .map(|quote| {
+                (
+                    quote.input_mint.to_string(),
+                    quote.output_mint.to_string(),
+                    quote.in_amount as i64,
+                    quote.out_amount as i64,
+                    quote.context_slot as i64,
+                    route_label(&[quote]),
+                )
+            })
+            .collect();
+        let row = (
+            id.clone(),
+            ledger_timestamp(Utc::now()),
+            source.to_string(),
+            first.input_mint.to_string(),
+            target,
+            route.to_string(),
+            opportunity.in_amount as i64,
+            opportunity.out_amount as i64,
+            opportunity.profit(),
+            net_profit,
+            decimals,
+            trigger.map(str::to_string),
+        );
+
+        self.write("opportunities", move |conn| {
+            let tx = conn.transaction()?;
+            let inserted = tx.execute(
+                "INSERT OR IGNORE INTO opportunities (id, discovered_at, source, instrument, target, route, in_amount, out_amount, gross_profit, net_profit, decimals, trigger_tx)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![row.0, row.1, row.2, row.3, row.4, row.5, row.6, row.7, row.8, row.9, row.10, row.11],
+            )?;
+            if inserted > 0 {
+                for (leg, (input_mint, output_mint, in_amount, out_amount, context_slot, dexes)) in
+                    quotes.iter().enumerate()
+                {
+                    tx.execute(
+                        "INSERT INTO quotes (opportunity_id, leg, input_mint, output_mint, in_amount, out_amount, context_slot, dexes)
+                         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![row.0, leg as i64, input_mint, output_mint, in_amount, out_amount, context_slot, dexes],
+                    )?;
+                }
+            }
+            tx.commit()
+        });
+        id
+    }
+
+    /// Record one attempt to build `opportunity_id` into a transaction on `path`.
+    pub fn record_build(&self, opportunity_id: &str, path: &str, outcome: BuildOutcome, error: Option<&str>) {
+        let row = (
+            opportunity_id.to_string(),
+            ledger_timestamp(Utc::now()),
+            path.to_string(),
+            outcome.as_str(),
+            error.map(str::to_string),
+        );
+        self.write("build_attempts", move |conn| {
+            conn.execute(
+                "INSERT INTO build_attempts (opportunity_id, attempted_at, path, outcome, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![row.0, row.1, row.2, row.3, row.4],
+            )?;
+            Ok(())
+        });
+    }
+
+    /// Record a `sendTransaction` of `trade`; `error` is set (and the signature left
+    /// empty) when the send itself failed.
+    pub fn record_submission(&self, trade: &PendingTrade, error: Option<&str>) {
+        let row = (
+            trade.opportunity_id.clone(),
+            ledger_timestamp(trade.submitted_at),
+            trade.path.clone(),
+            error.is_none().then(|| trade.signature.to_string()),
+            trade.blockhash.to_string(),
+            trade.expected_profit,
+            error.map(str::to_string),
+        );
+        self.write("submissions", move |conn| {
+            conn.execute(
+                "INSERT OR IGNORE INTO submissions (opportunity_id, submitted_at, path, signature, blockhash, expected_profit, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![row.0, row.1, row.2, row.3, row.4, row.5, row.6],
+            )?;
+            Ok(())
+        });
+    }
+
+    /// Record the settled landing outcome of a submitted transaction.
+    pub fn record_confirmation(&self, record: &LandingRecord) {
+        let row = (
+            record.signature.clone(),
+            record.opportunity_id.clone(),
+            ledger_timestamp(Utc::now()),
+            record.status.as_str(),
+            record.slot.map(|s| s as i64),
+            record.realized_profit,
+            record.fee_lamports.map(|f| f as i64),
+            record.error.clone(),
+        );
+        self.write("confirmations", move |conn| {
+            conn.execute(
+                "INSERT OR REPLACE INTO confirmations (signature, opportunity_id, recorded_at, status, slot, realized_profit, fee_lamports, error)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![row.0, row.1, row.2, row.3, row.4, row.5, row.6, row.7],
+            )?;
+            Ok(())
+        });
+    }
+}
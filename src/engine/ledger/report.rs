@@ -0,0 +1,153 @@
+//! Ledger queries behind the `ledger` command.
+
+use chrono::{DateTime, Utc};
+use rusqlite::params;
+use serde::Serialize;
+
+use super::{ledger_timestamp, Ledger};
+
+/// Outcomes of all opportunities of one instrument through one set of target tokens.
+#[derive(Debug, Clone)]
+pub struct TargetProfit {
+    pub instrument: String,
+    /// Intermediate token mints, comma separated (one mint for two-leg round trips).
+    pub target: String,
+    /// Token symbols along the cycle, e.g. "WSOL -> USDC -> WSOL".
+    pub route: String,
+    pub decimals: u8,
+    pub opportunities: u64,
+    pub submissions: u64,
+    /// Submissions confirmed or finalized without error.
+    pub landed: u64,
+    /// Quoted profit of the submitted trades, in raw base-token units.
+    pub expected_profit: i64,
+    /// Measured balance change of the landed trades, in raw base-token units.
+    pub realized_profit: i64,
+    pub fee_lamports: u64,
+}
+
+impl TargetProfit {
+    /// Realized profit in base-token units.
+    pub fn realized_profit_ui(&self) -> f64 {
+        self.realized_profit as f64 / 10_f64.powi(self.decimals as i32)
+    }
+}
+
+/// One row of the `opportunities` table.
+#[derive(Debug, Clone, Serialize)]
+pub struct OpportunityRow {
+    pub id: String,
+    pub discovered_at: String,
+    pub source: String,
+    pub instrument: String,
+    pub route: String,
+    pub in_amount: u64,
+    pub out_amount: u64,
+    pub net_profit: i64,
+    pub decimals: u8,
+    pub trigger_tx: Option<String>,
+}
+
+/// A submission joined with its confirmation, if the landing tracker recorded one.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmissionRow {
+    pub opportunity_id: String,
+    pub submitted_at: String,
+    pub path: String,
+    pub signature: Option<String>,
+    pub expected_profit: i64,
+    /// Landing status ("finalized", "expired", ...), or None while untracked.
+    pub status: Option<String>,
+    pub realized_profit: Option<i64>,
+    pub error: Option<String>,
+}
+
+impl Ledger {
+    /// Net results per (instrument, target) of opportunities discovered since `since`,
+    /// best realized profit first.
+    pub fn profit_by_target(&self, since: DateTime<Utc>) -> Result<Vec<TargetProfit>, anyhow::Error> {
+        self.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT o.instrument, o.target, MIN(o.route), MAX(o.decimals),
+                        COUNT(DISTINCT o.id),
+                        COUNT(s.signature),
+                        COUNT(CASE WHEN c.status IN ('confirmed', 'finalized') AND c.error IS NULL THEN 1 END),
+                        COALESCE(SUM(s.expected_profit), 0),
+                        COALESCE(SUM(c.realized_profit), 0),
+                        COALESCE(SUM(c.fee_lamports), 0)
+                 FROM opportunities o
+                 LEFT JOIN submissions s ON s.opportunity_id = o.id AND s.signature IS NOT NULL
+                 LEFT JOIN confirmations c ON c.signature = s.signature
+                 WHERE o.discovered_at >= ?1
+                 GROUP BY o.instrument, o.target
+                 ORDER BY 9 DESC, 8 DESC",
+            )?;
+            let rows = stmt.query_map(params![ledger_timestamp(since)], |row| {
+                Ok(TargetProfit {
+                    instrument: row.get(0)?,
+                    target: row.get(1)?,
+                    route: row.get(2)?,
+                    decimals: row.get(3)?,
+                    opportunities: row.get::<_, i64>(4)? as u64,
+                    submissions: row.get::<_, i64>(5)? as u64,
+                    landed: row.get::<_, i64>(6)? as u64,
+                    expected_profit: row.get(7)?,
+                    realized_profit: row.get(8)?,
+                    fee_lamports: row.get::<_, i64>(9)? as u64,
+                })
+            })?;
+            rows.collect()
+        })
+    }
+
+    /// The `limit` most recently discovered opportunities, newest first.
+    pub fn recent_opportunities(&self, limit: usize) -> Result<Vec<OpportunityRow>, anyhow::Error> {
+        self.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT id, discovered_at, source, instrument, route, in_amount, out_amount, net_profit, decimals, trigger_tx
+                 FROM opportunities ORDER BY discovered_at DESC LIMIT ?1",
+            )?;
+            let rows = stmt.query_map(params![limit as i64], |row| {
+                Ok(OpportunityRow {
+                    id: row.get(0)?,
+                    discovered_at: row.get(1)?,
+                    source: row.get(2)?,
+                    instrument: row.get(3)?,
+                    route: row.get(4)?,
+                    in_amount: row.get::<_, i64>(5)? as u64,
+                    out_amount: row.get::<_, i64>(6)? as u64,
+                    net_profit: row.get(7)?,
+                    decimals: row.get(8)?,
+                    trigger_tx: row.get(9)?,
+                })
+            })?;
+            rows.collect()
+        })
+    }
+
+    /// The `limit` most recent submissions (including failed sends), newest first.
+    pub fn recent_submissions(&self, limit: usize) -> Result<Vec<SubmissionRow>, anyhow::Error> {
+        self.read(|conn| {
+            let mut stmt = conn.prepare(
+                "SELECT s.opportunity_id, s.submitted_at, s.path, s.signature, s.expected_profit,
+                        c.status, c.realized_profit, COALESCE(s.error, c.error)
+                 FROM submissions s
+                 LEFT JOIN confirmations c ON c.signature = s.signature
+                 ORDER BY s.submitted_at DESC, s.id DESC LIMIT ?1",
+            )?;
+            let rows = stmt.query_map(params![limit as i64], |row| {
+                Ok(SubmissionRow {
+                    opportunity_id: row.get(0)?,
+                    submitted_at: row.get(1)?,
+                    path: row.get(2)?,
+                    signature: row.get(3)?,
+                    expected_profit: row.get(4)?,
+                    status: row.get(5)?,
+                    realized_profit: row.get(6)?,
+                    error: row.get(7)?,
+                })
+            })?;
+            rows.collect()
+        })
+    }
+}
@@ -0,0 +1,214 @@
+//! Prometheus metrics of the arbitrage pipeline.
+//!
+//! Every [`Engine`](crate::Engine) owns a [`Metrics`] registry that the pipeline updates
+//! as it goes; with `[metrics] enabled = true` it is served as text on `/metrics`
+//! (see [`server`]). Amount-valued metrics use base-token units.
+
+use prometheus::{
+    Encoder, Gauge, GaugeVec, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, Opts,
+    Registry, TextEncoder,
+};
+
+use crate::sol_price_age;
+
+pub mod server;
+
+pub use server::*;
+
+/// Latency buckets (seconds) for Jupiter calls and quote sweeps.
+const LATENCY_BUCKETS: &[f64] = &[0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// All metrics exported by the engine.
+pub struct Metrics {
+    registry: Registry,
+    /// Round-trip quotes by target token, and cycle quotes as target "cycle", by outcome
+    /// ("ok" / "failed").
+    pub quotes: IntCounterVec,
+    /// Wall time of one `simulate_amount_in` sweep, by mode ("polling" / "big_trade").
+    pub simulate_latency: HistogramVec,
+    /// Profitable candidates found, by source ("polling" / "big_trade").
+    pub opportunities: IntCounterVec,
+    /// Submission attempts by path ("polling", "big_trade", "flash_loan") and outcome
+    /// ("sent", "send_failed", "build_failed", "simulation_rejected").
+    pub submissions: IntCounterVec,
+    /// Settled landings by path and final status.
+    pub landings: IntCounterVec,
+    /// Time until a submit endpoint accepted a transaction, by endpoint name.
+    pub submit_latency: HistogramVec,
+    /// Transactions a submit endpoint rejected or failed to take, by endpoint name.
+    pub submit_errors: IntCounterVec,
+    /// Transactions a submit endpoint accepted before every other one, by endpoint name.
+    pub submit_first_accepted: IntCounterVec,
+    /// Landed transactions credited to the endpoint that accepted them first.
+    pub submit_first_landed: IntCounterVec,
+    /// Jito bundles by status ("sent", "rejected", then "landed" / "failed" / "timeout").
+    pub bundles: IntCounterVec,
+    /// Tips paid in sent Jito bundles, in lamports.
+    pub bundle_tips: IntCounter,
+    /// Yellowstone reconnects by stream ("big_trades" / "pool_watch").
+    pub yellowstone_reconnects: IntCounterVec,
+    pub nonce_fetch_errors: IntCounter,
+    pub blockhash_fetch_errors: IntCounter,
+    /// Seconds since the SOL price was last fetched; -1 while on the config fallback.
+    pub sol_price_age: Gauge,
+    /// Realized profit of landed trades by instrument mint, in token units.
+    pub realized_pnl: GaugeVec,
+    /// Network fees of landed trades, in lamports.
+    pub fees_paid: IntCounter,
+    /// Jupiter API latency by call ("quote_round_trip" / "swap_instructions").
+    pub jupiter_latency: HistogramVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let quotes = IntCounterVec::new(
+            Opts::new("arb_quotes_total", "Round-trip quotes by target token (\"cycle\" for cycles) and outcome"),
+            &["target", "outcome"],
+        )
+        .unwrap();
+        let simulate_latency = HistogramVec::new(
+            HistogramOpts::new("arb_simulate_amount_in_seconds", "simulate_amount_in sweep latency")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["mode"],
+        )
+        .unwrap();
+        let opportunities = IntCounterVec::new(
+            Opts::new("arb_opportunities_total", "Profitable candidates found"),
+            &["source"],
+        )
+        .unwrap();
+        let submissions = IntCounterVec::new(
+            Opts::new("arb_submissions_total", "Submission attempts by path and outcome"),
+            &["path", "outcome"],
+        )
+        .unwrap();
+        let landings = IntCounterVec::new(
+            Opts::new("arb_landings_total", "Settled landings by path and status"),
+            &["path", "status"],
+        )
+        .unwrap();
+        let submit_latency = HistogramVec::new(
+            HistogramOpts::new("arb_submit_endpoint_latency_seconds", "Time until a submit endpoint accepted a transaction")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["endpoint"],
+        )
+        .unwrap();
+        let submit_errors = IntCounterVec::new(
+            Opts::new("arb_submit_endpoint_errors_total", "Transactions a submit endpoint failed to accept"),
+            &["endpoint"],
+        )
+        .unwrap();
+        let submit_first_accepted = IntCounterVec::new(
+            Opts::new("arb_submit_endpoint_first_accepted_total", "Transactions a submit endpoint accepted first"),
+            &["endpoint"],
+        )
+        .unwrap();
+        let submit_first_landed = IntCounterVec::new(
+            Opts::new("arb_submit_endpoint_first_landed_total", "Landed transactions a submit endpoint accepted first"),
+            &["endpoint"],
+        )
+        .unwrap();
+        let bundles = IntCounterVec::new(
+            Opts::new("arb_jito_bundles_total", "Jito bundles by status"),
+            &["status"],
+        )
+        .unwrap();
+        let bundle_tips = IntCounter::new("arb_jito_bundle_tips_lamports_total", "Tips paid in sent Jito bundles").unwrap();
+        let yellowstone_reconnects = IntCounterVec::new(
+            Opts::new("arb_yellowstone_reconnects_total", "Yellowstone gRPC reconnects"),
+            &["stream"],
+        )
+        .unwrap();
+        let nonce_fetch_errors =
+            IntCounter::new("arb_nonce_fetch_errors_total", "Failed durable nonce reads").unwrap();
+        let blockhash_fetch_errors =
+            IntCounter::new("arb_blockhash_fetch_errors_total", "Failed recent blockhash fetches").unwrap();
+        let sol_price_age = Gauge::new(
+            "arb_sol_price_age_seconds",
+            "Seconds since the SOL price was fetched (-1 = config fallback)",
+        )
+        .unwrap();
+        let realized_pnl = GaugeVec::new(
+            Opts::new("arb_realized_pnl", "Realized profit of landed trades in token units"),
+            &["instrument"],
+        )
+        .unwrap();
+        let fees_paid =
+            IntCounter::new("arb_fees_paid_lamports_total", "Network fees of landed trades").unwrap();
+        let jupiter_latency = HistogramVec::new(
+            HistogramOpts::new("arb_jupiter_latency_seconds", "Jupiter API latency")
+                .buckets(LATENCY_BUCKETS.to_vec()),
+            &["call"],
+        )
+        .unwrap();
+
+        let registry = Registry::new();
+        registry.register(Box::new(quotes.clone())).unwrap();
+        registry.register(Box::new(simulate_latency.clone())).unwrap();
+        registry.register(Box::new(opportunities.clone())).unwrap();
+        registry.register(Box::new(submissions.clone())).unwrap();
+        registry.register(Box::new(landings.clone())).unwrap();
+        registry.register(Box::new(submit_latency.clone())).unwrap();
+        registry.register(Box::new(submit_errors.clone())).unwrap();
+        registry.register(Box::new(submit_first_accepted.clone())).unwrap();
+        registry.register(Box::new(submit_first_landed.clone())).unwrap();
+        registry.register(Box::new(bundles.clone())).unwrap();
+        registry.register(Box::new(bundle_tips.clone())).unwrap();
+        registry.register(Box::new(yellowstone_reconnects.clone())).unwrap();
+        registry.register(Box::new(nonce_fetch_errors.clone())).unwrap();
+        registry.register(Box::new(blockhash_fetch_errors.clone())).unwrap();
+        registry.register(Box::new(sol_price_age.clone())).unwrap();
+        registry.register(Box::new(realized_pnl.clone())).unwrap();
+        registry.register(Box::new(fees_paid.clone())).unwrap();
+        registry.register(Box::new(jupiter_latency.clone())).unwrap();
+
+        Self {
+            registry,
+            quotes,
+            simulate_latency,
+            opportunities,
+            submissions,
+            landings,
+            submit_latency,
+            submit_errors,
+            submit_first_accepted,
+            submit_first_landed,
+            bundles,
+            bundle_tips,
+            yellowstone_reconnects,
+            nonce_fetch_errors,
+            blockhash_fetch_errors,
+            sol_price_age,
+            realized_pnl,
+            fees_paid,
+            jupiter_latency,
+        }
+    }
+
+    /// Count one quote for `target` (a mint, or intermediate mints joined with ",").
+    pub fn record_quote(&self, target: &str, ok: bool) {
+        let outcome = if ok { "ok" } else { "failed" };
+        self.quotes.with_label_values(&[target, outcome]).inc();
+    }
+
+    pub fn record_submission(&self, path: &str, outcome: &str) {
+        self.submissions.with_label_values(&[path, outcome]).inc();
+    }
+
+    /// Prometheus text exposition of all metrics.
+    pub fn render(&self) -> String {
+        self.sol_price_age
+            .set(sol_price_age().map(|age| age.as_secs_f64()).unwrap_or(-1.0));
+        let mut buffer = Vec::new();
+        if let Err(e) = TextEncoder::new().encode(&self.registry.gather(), &mut buffer) {
+            tracing::warn!(error = %e, "Failed to encode metrics");
+        }
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
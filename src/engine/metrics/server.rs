@@ -0,0 +1,38 @@
+//! HTTP `/metrics` endpoint.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use tokio::net::TcpListener;
+use tracing::info;
+
+use crate::Engine;
+
+/// Router serving the engine's metrics on `GET /metrics`.
+pub fn metrics_router(engine: Arc<Engine>) -> Router {
+    Router::new()
+        .route("/metrics", get(render_metrics))
+        .with_state(engine)
+}
+
+async fn render_metrics(State(engine): State<Arc<Engine>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        engine.metrics.render(),
+    )
+}
+
+/// Serve `/metrics` on `[metrics] listen` until the process exits.
+pub async fn serve_metrics(engine: Arc<Engine>) -> Result<(), anyhow::Error> {
+    let listen = engine.config().metrics.listen.clone();
+    let listener = TcpListener::bind(&listen)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to bind metrics endpoint {}: {}", listen, e))?;
+    info!(%listen, "Serving Prometheus metrics on /metrics");
+    axum::serve(listener, metrics_router(engine)).await?;
+    Ok(())
+}
@@ -1,9 +1,23 @@
+pub mod admin;
+pub mod context;
 pub mod discovery;
 pub mod execute;
 pub mod jupiter;
+pub mod ledger;
+pub mod metrics;
+pub mod pools;
+pub mod quote;
 pub mod runtime;
+pub mod tokens;
 
+pub use admin::*;
+pub use context::*;
 pub use discovery::*;
 pub use execute::*;
 pub use jupiter::*;
+pub use ledger::*;
+pub use metrics::*;
+pub use pools::*;
+pub use quote::*;
 pub use runtime::*;
+pub use tokens::*;
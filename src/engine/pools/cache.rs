@@ -0,0 +1,198 @@
+//! In-memory pool state, updated account by account.
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::{AccountMap, PoolState};
+use crate::app::config::PoolsConfig;
+
+#[derive(Default)]
+struct CacheState {
+    pools: HashMap<Pubkey, PoolState>,
+    /// Vault / config account data read by the decoded pools.
+    accounts: AccountMap,
+    /// Dependencies of all decoded pools.
+    dependencies: HashSet<Pubkey>,
+    /// Slot of the last applied update per account.
+    slots: HashMap<Pubkey, u64>,
+}
+
+/// Decoded pools of `[pools] addresses` and the accounts they price from.
+pub struct PoolCache {
+    config: PoolsConfig,
+    addresses: Vec<Pubkey>,
+    state: RwLock<CacheState>,
+}
+
+impl PoolCache {
+    /// Empty cache for the configured pool addresses (none if `[pools]` is disabled).
+    pub fn new(config: &PoolsConfig) -> Result<Self, anyhow::Error> {
+        let addresses = if config.enabled {
+            config
+                .addresses
+                .iter()
+                .map(|a| {
+                    Pubkey::from_str(a).map_err(|e| {
+                        anyhow::anyhow!("Invalid pools.addresses entry {:?}: {}", a, e)
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            config: config.clone(),
+            addresses,
+            state: RwLock::new(CacheState::default()),
+        })
+    }
+
+    /// True if local pool math is enabled and at least one pool is configured.
+    pub fn enabled(&self) -> bool {
+        !self.addresses.is_empty()
+    }
+
+    /// Configured pool state accounts.
+    pub fn pool_addresses(&self) -> &[Pubkey] {
+        &self.addresses
+    }
+
+    /// Vault and config accounts of the pools decoded so far.
+    pub fn dependency_addresses(&self) -> Vec<Pubkey> {
+        self.state
+            .read()
+            .unwrap()
+            .dependencies
+            .iter()
+            .copied()
+            .collect()
+    }
+
+    /// Every account whose updates change local quotes: pools and their dependencies.
+    pub fn watched_accounts(&self) -> Vec<Pubkey> {
+        let mut accounts = self.addresses.clone();
+        accounts.extend(self.dependency_addresses());
+        accounts
+    }
+
+    pub fn is_watched(&self, address: &Pubkey) -> bool {
+        self.addresses.contains(address)
+            || self.state.read().unwrap().dependencies.contains(address)
+    }
+
+    /// Number of decoded pools.
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().pools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn pool(&self, address: &Pubkey) -> Option<PoolState> {
+        self.state.read().unwrap().pools.get(address).cloned()
+    }
+
+    /// Mint pairs of the pools whose quotes depend on `address` (the pool account itself,
+    /// or one of its vault / config accounts).
+    pub fn mints_affected_by(&self, address: &Pubkey) -> Vec<(Pubkey, Pubkey)> {
+        let state = self.state.read().unwrap();
+        state
+            .pools
+            .iter()
+            .filter(|(pool_address, pool)| {
+                *pool_address == address || pool.dependencies().contains(address)
+            })
+            .map(|(_, pool)| pool.mints())
+            .collect()
+    }
+
+    /// Apply new data for `address` observed at `slot`.
+    ///
+    /// Pool accounts are re-decoded; dependency accounts are stored as is; other accounts
+    /// and updates older than the last applied one are ignored. Returns true if a watched
+    /// account changed.
+    pub fn update_account(
+        &self,
+        address: &Pubkey,
+        owner: &Pubkey,
+        data: &[u8],
+        slot: u64,
+    ) -> Result<bool, anyhow::Error> {
+        let is_pool = self.addresses.contains(address);
+        let mut state = self.state.write().unwrap();
+        if !is_pool && !state.dependencies.contains(address) {
+            return Ok(false);
+        }
+        if state.slots.get(address).is_some_and(|&last| slot < last) {
+            return Ok(false);
+        }
+        state.slots.insert(*address, slot);
+
+        if is_pool {
+            let pool = PoolState::decode(owner, data, &self.config)
+                .map_err(|e| anyhow::anyhow!("Failed to decode pool {}: {}", address, e))?;
+            if state.pools.get(address) == Some(&pool) {
+                return Ok(false);
+            }
+            state.pools.insert(*address, pool);
+            let dependencies = state
+                .pools
+                .values()
+                .flat_map(PoolState::dependencies)
+                .collect();
+            state.dependencies = dependencies;
+            Ok(true)
+        } else {
+            if state.accounts.get(address).map(Vec::as_slice) == Some(data) {
+                return Ok(false);
+            }
+            state.accounts.insert(*address, data.to_vec());
+            Ok(true)
+        }
+    }
+
+    /// Best local output over all pools swapping `input` for `output`, with the pool used.
+    pub fn quote(&self, input: &Pubkey, output: &Pubkey, amount_in: u64) -> Option<(Pubkey, u64)> {
+        let state = self.state.read().unwrap();
+        state
+            .pools
+            .iter()
+            .filter(|(_, pool)| pool.trades(input, output))
+            .filter_map(|(address, pool)| {
+                pool.quote(input, amount_in, &state.accounts)
+                    .map(|out| (*address, out))
+            })
+            .max_by_key(|&(_, out)| out)
+    }
+
+    /// Local output of swapping `amount_in` along `hops` (first to last mint), taking the
+    /// best pool on every leg. None if some leg has no priced pool.
+    pub fn quote_path(&self, hops: &[Pubkey], amount_in: u64) -> Option<u64> {
+        hops.windows(2).try_fold(amount_in, |amount, pair| {
+            self.quote(&pair[0], &pair[1], amount).map(|(_, out)| out)
+        })
+    }
+
+    /// Whether a cycle along `hops` is worth quoting on Jupiter at any of `amounts`.
+    ///
+    /// Cycles with a leg not covered by local pools always pass. Covered cycles pass if
+    /// their best local round trip loses less than `prefilter_margin_bps`.
+    pub fn passes_prefilter(&self, hops: &[Pubkey], amounts: &[u64]) -> bool {
+        let mut covered = false;
+        for &amount in amounts.iter().filter(|&&a| a > 0) {
+            let Some(out) = self.quote_path(hops, amount) else {
+                continue;
+            };
+            covered = true;
+            let return_bps = (out as f64 / amount as f64 - 1.0) * 10_000.0;
+            if return_bps > -self.config.prefilter_margin_bps {
+                return true;
+            }
+        }
+        !covered
+    }
+}
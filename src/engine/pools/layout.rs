@@ -0,0 +1,73 @@
+//! Little-endian field readers and shared swap math.
+//!
+//! Decoders check the account length once, so the readers index without bounds checks.
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Offset of `amount` in an SPL Token / Token-2022 token account.
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+pub(crate) fn read_u8(data: &[u8], offset: usize) -> u8 {
+    data[offset]
+}
+
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(data[offset..offset + 2].try_into().unwrap())
+}
+
+pub(crate) fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+pub(crate) fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+pub(crate) fn read_u128(data: &[u8], offset: usize) -> u128 {
+    u128::from_le_bytes(data[offset..offset + 16].try_into().unwrap())
+}
+
+pub(crate) fn read_pubkey(data: &[u8], offset: usize) -> Pubkey {
+    Pubkey::new_from_array(data[offset..offset + 32].try_into().unwrap())
+}
+
+/// Fail with a readable error if `data` is shorter than `len`.
+pub(crate) fn ensure_len(venue: &str, data: &[u8], len: usize) -> Result<(), anyhow::Error> {
+    if data.len() < len {
+        return Err(anyhow::anyhow!(
+            "{} pool account is {} bytes, expected at least {}",
+            venue,
+            data.len(),
+            len
+        ));
+    }
+    Ok(())
+}
+
+/// `amount` of an SPL Token or Token-2022 token account.
+pub fn token_account_amount(data: &[u8]) -> Option<u64> {
+    (data.len() >= TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)
+        .then(|| read_u64(data, TOKEN_ACCOUNT_AMOUNT_OFFSET))
+}
+
+/// Constant-product (x * y = k) output for `amount_in`, with a
+/// `fee_numerator / fee_denominator` fee taken from the input.
+pub fn constant_product_out(
+    reserve_in: u64,
+    reserve_out: u64,
+    amount_in: u64,
+    fee_numerator: u64,
+    fee_denominator: u64,
+) -> Option<u64> {
+    if reserve_in == 0
+        || reserve_out == 0
+        || fee_denominator == 0
+        || fee_numerator > fee_denominator
+    {
+        return None;
+    }
+    let amount_in =
+        amount_in as u128 * (fee_denominator - fee_numerator) as u128 / fee_denominator as u128;
+    let out = reserve_out as u128 * amount_in / (reserve_in as u128 + amount_in);
+    Some(out as u64)
+}
@@ -0,0 +1,184 @@
+//! Local AMM pool math for quote-free opportunity detection.
+//!
+//! Pool state accounts of the main venues in [`PROGRAM_MAP`](crate::PROGRAM_MAP) are
+//! decoded into [`PoolState`] and kept in the engine's [`PoolCache`], together with the
+//! vault and config accounts each pool prices from. Swap outputs are then computed
+//! locally, so the polling loop only asks Jupiter to confirm cycles that already look
+//! profitable here. Accounts are loaded over RPC and kept fresh by the Yellowstone
+//! account subscription and/or a periodic RPC refresh.
+//!
+//! The math is a prefilter, not a quote: concentrated-liquidity venues are priced at
+//! the current tick / active bin only.
+
+use std::collections::HashMap;
+
+use solana_sdk::pubkey::Pubkey;
+
+use crate::app::config::PoolsConfig;
+use crate::PROGRAM_MAP;
+
+pub mod cache;
+pub mod layout;
+pub mod meteora_dlmm;
+pub mod pump_swap;
+pub mod raydium_amm;
+pub mod raydium_cp;
+pub mod refresh;
+pub mod whirlpool;
+
+pub use cache::*;
+pub use layout::{constant_product_out, token_account_amount};
+pub use meteora_dlmm::*;
+pub use pump_swap::*;
+pub use raydium_amm::*;
+pub use raydium_cp::*;
+pub use refresh::*;
+pub use whirlpool::*;
+
+/// Raw account data by address (token vaults, config accounts).
+pub type AccountMap = HashMap<Pubkey, Vec<u8>>;
+
+pub const RAYDIUM_AMM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8");
+pub const RAYDIUM_CP_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+pub const WHIRLPOOL_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc");
+pub const METEORA_DLMM_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo");
+pub const PUMP_SWAP_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("pAMMBay6oceH9fJKBRHGP5D4bD4sWpmSwMn52FMfXEA");
+
+/// Venues with a local decoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Venue {
+    RaydiumAmm,
+    RaydiumCp,
+    Whirlpool,
+    MeteoraDlmm,
+    PumpSwap,
+}
+
+impl Venue {
+    pub const ALL: [Venue; 5] = [
+        Venue::RaydiumAmm,
+        Venue::RaydiumCp,
+        Venue::Whirlpool,
+        Venue::MeteoraDlmm,
+        Venue::PumpSwap,
+    ];
+
+    pub fn program_id(self) -> Pubkey {
+        match self {
+            Venue::RaydiumAmm => RAYDIUM_AMM_PROGRAM_ID,
+            Venue::RaydiumCp => RAYDIUM_CP_PROGRAM_ID,
+            Venue::Whirlpool => WHIRLPOOL_PROGRAM_ID,
+            Venue::MeteoraDlmm => METEORA_DLMM_PROGRAM_ID,
+            Venue::PumpSwap => PUMP_SWAP_PROGRAM_ID,
+        }
+    }
+
+    /// Venue owning accounts of `program_id`, if it has a decoder.
+    pub fn from_program_id(program_id: &Pubkey) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.program_id() == *program_id)
+    }
+
+    /// Display name from [`PROGRAM_MAP`].
+    pub fn name(self) -> &'static str {
+        PROGRAM_MAP
+            .get(self.program_id().to_string().as_str())
+            .copied()
+            .unwrap_or("Unknown")
+    }
+}
+
+/// Decoded state of one pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolState {
+    RaydiumAmm(RaydiumAmmPool),
+    RaydiumCp(RaydiumCpPool),
+    Whirlpool(WhirlpoolPool),
+    MeteoraDlmm(MeteoraDlmmPool),
+    PumpSwap(PumpSwapPool),
+}
+
+impl PoolState {
+    /// Decode a pool account owned by `owner`.
+    pub fn decode(
+        owner: &Pubkey,
+        data: &[u8],
+        config: &PoolsConfig,
+    ) -> Result<Self, anyhow::Error> {
+        let venue = Venue::from_program_id(owner)
+            .ok_or_else(|| anyhow::anyhow!("No local pool decoder for program {}", owner))?;
+        Ok(match venue {
+            Venue::RaydiumAmm => PoolState::RaydiumAmm(RaydiumAmmPool::decode(data)?),
+            Venue::RaydiumCp => PoolState::RaydiumCp(RaydiumCpPool::decode(data)?),
+            Venue::Whirlpool => PoolState::Whirlpool(WhirlpoolPool::decode(data)?),
+            Venue::MeteoraDlmm => PoolState::MeteoraDlmm(MeteoraDlmmPool::decode(data)?),
+            Venue::PumpSwap => {
+                PoolState::PumpSwap(PumpSwapPool::decode(data, config.pump_swap_fee_bps)?)
+            }
+        })
+    }
+
+    pub fn venue(&self) -> Venue {
+        match self {
+            PoolState::RaydiumAmm(_) => Venue::RaydiumAmm,
+            PoolState::RaydiumCp(_) => Venue::RaydiumCp,
+            PoolState::Whirlpool(_) => Venue::Whirlpool,
+            PoolState::MeteoraDlmm(_) => Venue::MeteoraDlmm,
+            PoolState::PumpSwap(_) => Venue::PumpSwap,
+        }
+    }
+
+    /// The pool's two mints, in the order `a_to_b` swaps them.
+    pub fn mints(&self) -> (Pubkey, Pubkey) {
+        match self {
+            PoolState::RaydiumAmm(p) => p.mints(),
+            PoolState::RaydiumCp(p) => p.mints(),
+            PoolState::Whirlpool(p) => p.mints(),
+            PoolState::MeteoraDlmm(p) => p.mints(),
+            PoolState::PumpSwap(p) => p.mints(),
+        }
+    }
+
+    /// Accounts besides the pool itself that [`PoolState::quote`] reads.
+    pub fn dependencies(&self) -> Vec<Pubkey> {
+        match self {
+            PoolState::RaydiumAmm(p) => p.dependencies(),
+            PoolState::RaydiumCp(p) => p.dependencies(),
+            PoolState::Whirlpool(p) => p.dependencies(),
+            PoolState::MeteoraDlmm(p) => p.dependencies(),
+            PoolState::PumpSwap(p) => p.dependencies(),
+        }
+    }
+
+    /// Local output for swapping `amount_in` of `input_mint`, or None if the pool does not
+    /// trade that mint, is disabled, or a dependency has not been loaded yet.
+    pub fn quote(&self, input_mint: &Pubkey, amount_in: u64, accounts: &AccountMap) -> Option<u64> {
+        let (a, b) = self.mints();
+        let a_to_b = if *input_mint == a {
+            true
+        } else if *input_mint == b {
+            false
+        } else {
+            return None;
+        };
+        match self {
+            PoolState::RaydiumAmm(p) => p.quote(a_to_b, amount_in, accounts),
+            PoolState::RaydiumCp(p) => p.quote(a_to_b, amount_in, accounts),
+            PoolState::Whirlpool(p) => p.quote(a_to_b, amount_in, accounts),
+            PoolState::MeteoraDlmm(p) => p.quote(a_to_b, amount_in, accounts),
+            PoolState::PumpSwap(p) => p.quote(a_to_b, amount_in, accounts),
+        }
+    }
+
+    /// True if the pool swaps between `x` and `y` (either direction).
+    pub fn trades(&self, x: &Pubkey, y: &Pubkey) -> bool {
+        let (a, b) = self.mints();
+        (a == *x && b == *y) || (a == *y && b == *x)
+    }
+}
@@ -0,0 +1,63 @@
+//! PumpSwap (`pAMMBay6...`): constant product over the two pool token accounts.
+//!
+//! Fees live in the program's global config rather than the pool, so the rate comes
+//! from `[pools] pump_swap_fee_bps` and is applied to the input in both directions.
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::layout::{constant_product_out, ensure_len, read_pubkey, token_account_amount};
+use super::AccountMap;
+
+const BASE_MINT: usize = 43;
+const QUOTE_MINT: usize = 75;
+const POOL_BASE_TOKEN_ACCOUNT: usize = 139;
+const POOL_QUOTE_TOKEN_ACCOUNT: usize = 171;
+
+const BPS_DENOMINATOR: u64 = 10_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PumpSwapPool {
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub pool_base_token_account: Pubkey,
+    pub pool_quote_token_account: Pubkey,
+    pub fee_bps: u64,
+}
+
+impl PumpSwapPool {
+    /// Size of `Pool` up to `lp_supply`; newer pools append a `coin_creator`.
+    pub const LEN: usize = 211;
+
+    pub fn decode(data: &[u8], fee_bps: u64) -> Result<Self, anyhow::Error> {
+        ensure_len("PumpSwap", data, Self::LEN)?;
+        Ok(Self {
+            base_mint: read_pubkey(data, BASE_MINT),
+            quote_mint: read_pubkey(data, QUOTE_MINT),
+            pool_base_token_account: read_pubkey(data, POOL_BASE_TOKEN_ACCOUNT),
+            pool_quote_token_account: read_pubkey(data, POOL_QUOTE_TOKEN_ACCOUNT),
+            fee_bps,
+        })
+    }
+
+    /// (base, quote) mints; `a_to_b` sells base for quote.
+    pub fn mints(&self) -> (Pubkey, Pubkey) {
+        (self.base_mint, self.quote_mint)
+    }
+
+    pub fn dependencies(&self) -> Vec<Pubkey> {
+        vec![self.pool_base_token_account, self.pool_quote_token_account]
+    }
+
+    pub fn quote(&self, a_to_b: bool, amount_in: u64, accounts: &AccountMap) -> Option<u64> {
+        let base = token_account_amount(accounts.get(&self.pool_base_token_account)?)?;
+        let quote = token_account_amount(accounts.get(&self.pool_quote_token_account)?)?;
+        let (reserve_in, reserve_out) = if a_to_b { (base, quote) } else { (quote, base) };
+        constant_product_out(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            self.fee_bps,
+            BPS_DENOMINATOR,
+        )
+    }
+}
@@ -0,0 +1,100 @@
+//! Raydium CP-Swap (`CPMMoo8L...`): constant product over the two vaults.
+//!
+//! Reserves are the vault balances minus accrued protocol and fund fees. The trade fee
+//! lives in the pool's `AmmConfig` account, which is tracked as a dependency.
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::layout::{
+    constant_product_out, ensure_len, read_pubkey, read_u64, read_u8, token_account_amount,
+};
+use super::AccountMap;
+
+const AMM_CONFIG: usize = 8;
+const TOKEN_0_VAULT: usize = 72;
+const TOKEN_1_VAULT: usize = 104;
+const TOKEN_0_MINT: usize = 168;
+const TOKEN_1_MINT: usize = 200;
+const STATUS: usize = 329;
+const PROTOCOL_FEES_TOKEN_0: usize = 341;
+const PROTOCOL_FEES_TOKEN_1: usize = 349;
+const FUND_FEES_TOKEN_0: usize = 357;
+const FUND_FEES_TOKEN_1: usize = 365;
+
+/// `AmmConfig.trade_fee_rate`, in millionths.
+const AMM_CONFIG_TRADE_FEE_RATE: usize = 12;
+const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+/// Status bit set when swaps are disabled.
+const STATUS_SWAP_DISABLED: u8 = 1 << 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RaydiumCpPool {
+    pub amm_config: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_vault: Pubkey,
+    pub token_1_vault: Pubkey,
+    pub status: u8,
+    /// Protocol + fund fees held in each vault but not part of the reserves.
+    pub fees_owed_0: u64,
+    pub fees_owed_1: u64,
+}
+
+impl RaydiumCpPool {
+    /// Size of `PoolState`, including the discriminator.
+    pub const LEN: usize = 637;
+
+    pub fn decode(data: &[u8]) -> Result<Self, anyhow::Error> {
+        ensure_len("Raydium CP", data, Self::LEN)?;
+        Ok(Self {
+            amm_config: read_pubkey(data, AMM_CONFIG),
+            token_0_mint: read_pubkey(data, TOKEN_0_MINT),
+            token_1_mint: read_pubkey(data, TOKEN_1_MINT),
+            token_0_vault: read_pubkey(data, TOKEN_0_VAULT),
+            token_1_vault: read_pubkey(data, TOKEN_1_VAULT),
+            status: read_u8(data, STATUS),
+            fees_owed_0: read_u64(data, PROTOCOL_FEES_TOKEN_0)
+                .saturating_add(read_u64(data, FUND_FEES_TOKEN_0)),
+            fees_owed_1: read_u64(data, PROTOCOL_FEES_TOKEN_1)
+                .saturating_add(read_u64(data, FUND_FEES_TOKEN_1)),
+        })
+    }
+
+    /// (token 0, token 1) mints; `a_to_b` swaps token 0 for token 1.
+    pub fn mints(&self) -> (Pubkey, Pubkey) {
+        (self.token_0_mint, self.token_1_mint)
+    }
+
+    pub fn dependencies(&self) -> Vec<Pubkey> {
+        vec![self.token_0_vault, self.token_1_vault, self.amm_config]
+    }
+
+    pub fn quote(&self, a_to_b: bool, amount_in: u64, accounts: &AccountMap) -> Option<u64> {
+        if self.status & STATUS_SWAP_DISABLED != 0 {
+            return None;
+        }
+        let config = accounts.get(&self.amm_config)?;
+        if config.len() < AMM_CONFIG_TRADE_FEE_RATE + 8 {
+            return None;
+        }
+        let trade_fee_rate = read_u64(config, AMM_CONFIG_TRADE_FEE_RATE);
+
+        let reserve_0 = token_account_amount(accounts.get(&self.token_0_vault)?)?
+            .saturating_sub(self.fees_owed_0);
+        let reserve_1 = token_account_amount(accounts.get(&self.token_1_vault)?)?
+            .saturating_sub(self.fees_owed_1);
+        let (reserve_in, reserve_out) = if a_to_b {
+            (reserve_0, reserve_1)
+        } else {
+            (reserve_1, reserve_0)
+        };
+        constant_product_out(
+            reserve_in,
+            reserve_out,
+            amount_in,
+            trade_fee_rate,
+            FEE_RATE_DENOMINATOR,
+        )
+    }
+}
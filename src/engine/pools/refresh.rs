@@ -0,0 +1,106 @@
+//! Keeping the [`PoolCache`] fresh: RPC snapshots and Yellowstone account updates.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use tokio::time::{interval, Duration};
+use tracing::{debug, warn};
+use yellowstone_grpc_proto::geyser::{SubscribeRequestFilterAccounts, SubscribeUpdateAccount};
+
+use super::PoolCache;
+use crate::Engine;
+
+/// `getMultipleAccounts` accepts at most 100 keys.
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Read `addresses` over RPC into the cache; returns how many accounts changed.
+async fn load_accounts(engine: &Engine, addresses: &[Pubkey]) -> Result<usize, anyhow::Error> {
+    let mut changed = 0;
+    for chunk in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let response = engine
+            .rpc_client
+            .get_multiple_accounts_with_commitment(chunk, CommitmentConfig::processed())
+            .await?;
+        let slot = response.context.slot;
+        for (address, account) in chunk.iter().zip(response.value) {
+            let Some(account) = account else {
+                warn!(%address, "Pool account not found");
+                continue;
+            };
+            match engine
+                .pools
+                .update_account(address, &account.owner, &account.data, slot)
+            {
+                Ok(true) => changed += 1,
+                Ok(false) => {}
+                Err(e) => warn!(error = %e, "Skipping pool"),
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Snapshot every configured pool, then the vault and config accounts it prices from.
+/// Returns how many accounts changed.
+pub async fn refresh_pools(engine: &Engine) -> Result<usize, anyhow::Error> {
+    if !engine.pools.enabled() {
+        return Ok(0);
+    }
+    let pools = load_accounts(engine, engine.pools.pool_addresses()).await?;
+    let dependencies = load_accounts(engine, &engine.pools.dependency_addresses()).await?;
+    Ok(pools + dependencies)
+}
+
+/// Re-snapshot all pools every `[pools] refresh_interval_ms`, forever (no-op if 0).
+pub async fn run_pool_refresher(engine: Arc<Engine>) {
+    let interval_ms = engine.config().pools.refresh_interval_ms;
+    if !engine.pools.enabled() || interval_ms == 0 {
+        return;
+    }
+    let mut ticker = interval(Duration::from_millis(interval_ms));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        ticker.tick().await;
+        match refresh_pools(&engine).await {
+            Ok(changed) => debug!(changed, "Pool accounts refreshed"),
+            Err(e) => warn!(error = ?e, "Pool refresh failed"),
+        }
+    }
+}
+
+/// Yellowstone `accounts` filters watching every pool, vault and config account under
+/// the key `"pools"`; empty if there is nothing to watch.
+pub fn pool_accounts_filters(pools: &PoolCache) -> HashMap<String, SubscribeRequestFilterAccounts> {
+    let accounts = pools.watched_accounts();
+    if accounts.is_empty() {
+        return HashMap::new();
+    }
+    let filter = SubscribeRequestFilterAccounts {
+        account: accounts.iter().map(|a| a.to_string()).collect(),
+        owner: vec![],
+        filters: vec![],
+        nonempty_txn_signature: None,
+    };
+    HashMap::from([("pools".to_string(), filter)])
+}
+
+/// Apply a Yellowstone account update to the cache; returns true if a watched account changed.
+pub fn apply_account_update(pools: &PoolCache, update: &SubscribeUpdateAccount) -> bool {
+    let Some(account) = &update.account else {
+        return false;
+    };
+    let (Ok(address), Ok(owner)) = (
+        Pubkey::try_from(account.pubkey.as_slice()),
+        Pubkey::try_from(account.owner.as_slice()),
+    ) else {
+        return false;
+    };
+    match pools.update_account(&address, &owner, &account.data, update.slot) {
+        Ok(changed) => changed,
+        Err(e) => {
+            warn!(error = %e, "Skipping pool update");
+            false
+        }
+    }
+}
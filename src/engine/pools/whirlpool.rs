@@ -0,0 +1,94 @@
+//! Orca Whirlpool (`whirLbMi...`): concentrated liquidity.
+//!
+//! Swaps are priced within the current tick range only: the active liquidity is assumed
+//! to extend past the next initialized tick, so large trades are estimated without the
+//! liquidity changes a real swap would cross. Output is capped by the vault balance.
+
+use solana_sdk::pubkey::Pubkey;
+
+use super::layout::{ensure_len, read_i32, read_pubkey, read_u128, read_u16, token_account_amount};
+use super::AccountMap;
+
+const FEE_RATE: usize = 45;
+const LIQUIDITY: usize = 49;
+const SQRT_PRICE: usize = 65;
+const TICK_CURRENT_INDEX: usize = 81;
+const TOKEN_MINT_A: usize = 101;
+const TOKEN_VAULT_A: usize = 133;
+const TOKEN_MINT_B: usize = 181;
+const TOKEN_VAULT_B: usize = 213;
+
+/// `fee_rate` is in hundredths of a basis point.
+const FEE_RATE_DENOMINATOR: f64 = 1_000_000.0;
+/// `sqrt_price` is a Q64.64 fixed-point number.
+const Q64: f64 = 18_446_744_073_709_551_616.0;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhirlpoolPool {
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_vault_a: Pubkey,
+    pub token_vault_b: Pubkey,
+    pub fee_rate: u16,
+    pub liquidity: u128,
+    pub sqrt_price: u128,
+    pub tick_current_index: i32,
+}
+
+impl WhirlpoolPool {
+    /// Size of the `Whirlpool` account, including the discriminator.
+    pub const LEN: usize = 653;
+
+    pub fn decode(data: &[u8]) -> Result<Self, anyhow::Error> {
+        ensure_len("Whirlpool", data, Self::LEN)?;
+        Ok(Self {
+            token_mint_a: read_pubkey(data, TOKEN_MINT_A),
+            token_mint_b: read_pubkey(data, TOKEN_MINT_B),
+            token_vault_a: read_pubkey(data, TOKEN_VAULT_A),
+            token_vault_b: read_pubkey(data, TOKEN_VAULT_B),
+            fee_rate: read_u16(data, FEE_RATE),
+            liquidity: read_u128(data, LIQUIDITY),
+            sqrt_price: read_u128(data, SQRT_PRICE),
+            tick_current_index: read_i32(data, TICK_CURRENT_INDEX),
+        })
+    }
+
+    /// (A, B) mints; `a_to_b` swaps A for B.
+    pub fn mints(&self) -> (Pubkey, Pubkey) {
+        (self.token_mint_a, self.token_mint_b)
+    }
+
+    pub fn dependencies(&self) -> Vec<Pubkey> {
+        vec![self.token_vault_a, self.token_vault_b]
+    }
+
+    pub fn quote(&self, a_to_b: bool, amount_in: u64, accounts: &AccountMap) -> Option<u64> {
+        if self.liquidity == 0 || self.sqrt_price == 0 {
+            return None;
+        }
+        let liquidity = self.liquidity as f64;
+        let sqrt_price = self.sqrt_price as f64 / Q64;
+        let amount_in = amount_in as f64 * (1.0 - self.fee_rate as f64 / FEE_RATE_DENOMINATOR);
+
+        let out = if a_to_b {
+            // Price (B per A) falls: sqrt_p' = L * sqrt_p / (L + dx * sqrt_p), dy = L * (sqrt_p - sqrt_p')
+            let next = liquidity * sqrt_price / (liquidity + amount_in * sqrt_price);
+            liquidity * (sqrt_price - next)
+        } else {
+            // Price rises: sqrt_p' = sqrt_p + dy / L, dx = L * (1 / sqrt_p - 1 / sqrt_p')
+            let next = sqrt_price + amount_in / liquidity;
+            liquidity * (1.0 / sqrt_price - 1.0 / next)
+        };
+
+        let vault_out = if a_to_b {
+            &self.token_vault_b
+        } else {
+            &self.token_vault_a
+        };
+        let available = accounts
+            .get(vault_out)
+            .and_then(|data| token_account_amount(data))
+            .unwrap_or(u64::MAX);
+        Some((out.max(0.0) as u64).min(available))
+    }
+}
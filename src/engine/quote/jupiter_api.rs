@@ -0,0 +1,75 @@
+//! [`QuoteProvider`] for the Jupiter swap API (hosted or self-hosted).
+
+use async_trait::async_trait;
+use jupiter_swap_api_client::{
+    quote::{QuoteRequest, QuoteResponse},
+    swap::{SwapInstructionsResponse, SwapInstructionsResponseInternal, SwapRequest},
+    JupiterSwapApiClient,
+};
+use tracing::info;
+
+use crate::QuoteProvider;
+
+#[async_trait]
+impl QuoteProvider for JupiterSwapApiClient {
+    async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, anyhow::Error> {
+        Ok(JupiterSwapApiClient::quote(self, request).await?)
+    }
+
+    async fn swap_instructions(
+        &self,
+        request: &SwapRequest,
+    ) -> Result<SwapInstructionsResponse, anyhow::Error> {
+        // Direct HTTP request instead of library call — gives us full error
+        // diagnostics (timeout vs. connect vs. HTTP status vs. deserialization).
+        let url = format!("{}/swap-instructions", self.base_path);
+
+        // Serialize body upfront so we can log its size and send with explicit
+        // Content-Length (matching curl's behavior exactly).
+        let body_json = serde_json::to_vec(request)?;
+        info!(
+            url = %url,
+            body_size = body_json.len(),
+            "swap-instructions: sending POST"
+        );
+
+        let response = reqwest::Client::builder()
+            .no_proxy()
+            .timeout(std::time::Duration::from_secs(30))
+            .build()?
+            .post(&url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json")
+            .body(body_json)
+            .send()
+            .await
+            .map_err(|e| {
+                anyhow::anyhow!(
+                    "swap-instructions POST to {} failed: {:?} (timeout={}, connect={}, request={})",
+                    url, e, e.is_timeout(), e.is_connect(), e.is_request()
+                )
+            })?;
+
+        let status = response.status();
+        info!(res = %status, "resp");
+
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            let preview = &body[..body.len().min(500)];
+            return Err(anyhow::anyhow!(
+                "swap-instructions returned HTTP {status}: {preview}"
+            ));
+        }
+
+        let body = response.bytes().await?;
+        let internal: SwapInstructionsResponseInternal = serde_json::from_slice(&body)
+            .map_err(|e| {
+                let preview = String::from_utf8_lossy(&body[..body.len().min(500)]);
+                anyhow::anyhow!(
+                    "swap-instructions deserialization failed: {e}, body_preview={preview}"
+                )
+            })?;
+
+        Ok(internal.into())
+    }
+}
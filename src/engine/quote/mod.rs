@@ -0,0 +1,3 @@
+mod jupiter_api;
+pub mod provider;
+pub use provider::*;
@@ -0,0 +1,67 @@
+//! Quote provider abstraction.
+//!
+//! Discovery and execution only need three things from a routing backend: a single
+//! quote, a round-trip (mother -> arb -> mother) quote and the swap instructions for a
+//! combined route. [`QuoteProvider`] captures that so a hosted Jupiter API, a
+//! self-hosted instance or a local/offline source can be chosen per instrument.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use jupiter_swap_api_client::{
+    quote::{QuoteRequest, QuoteResponse},
+    swap::{SwapInstructionsResponse, SwapRequest},
+    JupiterSwapApiClient,
+};
+
+use crate::app::config::QuoteProviderConfig;
+use crate::{round_trip_big_trade, round_trip_polling};
+
+/// Round-trip result: (input amount, final output amount, leg 1 quote, leg 2 quote).
+pub type RoundTripQuote = (u64, u64, QuoteResponse, QuoteResponse);
+
+/// Which request shape to use for a round-trip quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteMode {
+    /// Multi-hop routes, all DEXes (continuous polling).
+    Polling,
+    /// Direct routes only; leg 2 excludes the DEXes used by leg 1 (big-trades monitor).
+    BigTrade,
+}
+
+#[async_trait]
+pub trait QuoteProvider: Send + Sync {
+    /// Quote a single swap.
+    async fn quote(&self, request: &QuoteRequest) -> Result<QuoteResponse, anyhow::Error>;
+
+    /// Quote `mother_token -> arb_token -> mother_token` for `input_amount`.
+    ///
+    /// The default issues two [`QuoteProvider::quote`] calls shaped by `mode`.
+    async fn round_trip_quote(
+        &self,
+        input_amount: u64,
+        mother_token: &str,
+        arb_token: &str,
+        mode: QuoteMode,
+    ) -> Result<RoundTripQuote, anyhow::Error> {
+        match mode {
+            QuoteMode::Polling => round_trip_polling(self, input_amount, mother_token, arb_token).await,
+            QuoteMode::BigTrade => round_trip_big_trade(self, input_amount, mother_token, arb_token).await,
+        }
+    }
+
+    /// Build the setup/swap/cleanup instructions for a (combined) route.
+    async fn swap_instructions(
+        &self,
+        request: &SwapRequest,
+    ) -> Result<SwapInstructionsResponse, anyhow::Error>;
+}
+
+/// Build a provider from its `[quote_providers.<name>]` entry.
+pub fn build_quote_provider(config: &QuoteProviderConfig) -> Arc<dyn QuoteProvider> {
+    match config {
+        QuoteProviderConfig::Jupiter { endpoint } => {
+            Arc::new(JupiterSwapApiClient::new(endpoint.clone()))
+        }
+    }
+}
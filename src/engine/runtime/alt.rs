@@ -3,16 +3,17 @@ use solana_sdk::{
     message::AddressLookupTableAccount, pubkey::Pubkey,
 };
 
-use crate::{ALT_EXTERNAL, RPC_CLIENT};
+use crate::{Engine, ALT_EXTERNAL};
 
-pub async fn fetch_alt(lut_addrs: Vec<Pubkey>) -> Vec<AddressLookupTableAccount> {
+pub async fn fetch_alt(engine: &Engine, lut_addrs: Vec<Pubkey>) -> Vec<AddressLookupTableAccount> {
 
     let mut new_addr = Vec::new();
 
     new_addr.extend(ALT_EXTERNAL);
     new_addr.extend(lut_addrs);
 
-    let accounts = RPC_CLIENT
+    let accounts = engine
+        .rpc_client
         .get_multiple_accounts(&new_addr)
         .await
         .unwrap();
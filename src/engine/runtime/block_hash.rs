@@ -1,43 +1,72 @@
+//! Recent blockhash for `[strategy] transaction_lifetime = "blockhash"`.
+//!
+//! A background task refetches the latest confirmed blockhash every
+//! `blockhash_refresh_ms` together with its last valid block height; transactions are
+//! signed with the latest one and expire once the chain passes that height.
+
+use std::sync::{Arc, Mutex};
+
 use solana_sdk::{commitment_config::CommitmentConfig, hash::Hash};
-use tokio::time::{Duration, sleep};
+use tokio::time::{sleep, Duration, Instant};
+use tracing::warn;
+
+use crate::Engine;
+
+/// A blockhash and the last block height a transaction signed with it can land in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatestBlockhash {
+    pub blockhash: Hash,
+    pub last_valid_block_height: u64,
+}
+
+/// Latest blockhash fetched by [`run_blockhash_refresher`].
+#[derive(Default)]
+pub struct BlockhashCache {
+    latest: Mutex<Option<(LatestBlockhash, Instant)>>,
+}
 
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
+impl BlockhashCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-use crate::RPC_CLIENT;
+    pub fn set(&self, latest: LatestBlockhash) {
+        *self.latest.lock().unwrap() = Some((latest, Instant::now()));
+    }
 
-static GLOBAL_CURRENT_HASH: Lazy<Mutex<Hash>> = Lazy::new(|| Mutex::new(Hash::default()));
+    /// Latest blockhash; None until the first fetch.
+    pub fn get(&self) -> Option<LatestBlockhash> {
+        self.latest.lock().unwrap().map(|(latest, _)| latest)
+    }
 
-fn set_slot(value: Hash) {
-    let mut slot = GLOBAL_CURRENT_HASH.lock().unwrap();
-    *slot = value;
+    /// Time since the latest blockhash was fetched.
+    pub fn age(&self) -> Option<Duration> {
+        self.latest.lock().unwrap().map(|(_, fetched)| fetched.elapsed())
+    }
 }
 
-pub fn get_slot() -> Hash {
-    let slot = GLOBAL_CURRENT_HASH.lock().unwrap();
-    *slot
+/// Fetch the latest confirmed blockhash into `engine.blockhash`.
+pub async fn refresh_blockhash(engine: &Engine) -> Result<LatestBlockhash, anyhow::Error> {
+    let (blockhash, last_valid_block_height) = engine
+        .rpc_client
+        .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
+        .await?;
+    let latest = LatestBlockhash {
+        blockhash,
+        last_valid_block_height,
+    };
+    engine.blockhash.set(latest);
+    Ok(latest)
 }
 
-/// Continuously retries fetching the latest blockhash until successful.
-pub async fn recent_blockhash_handler() {
+/// Refetch the latest blockhash every `[strategy] blockhash_refresh_ms`; on errors the
+/// previous one stays in use until it expires.
+pub async fn run_blockhash_refresher(engine: Arc<Engine>) {
     loop {
-        match RPC_CLIENT
-            .get_latest_blockhash_with_commitment(CommitmentConfig::confirmed())
-            .await
-        {
-            Ok((latest_blockhash, _)) => {
-                set_slot(latest_blockhash);
-                break; // Exit the loop once we get a valid blockhash
-            }
-            Err(e) => {
-                println!(
-                    "[BLOCKHASH ERROR]\n\t* ERR MSG : {}\n\t* Retrying in 200ms...",
-                    e
-                );
-                sleep(Duration::from_millis(200)).await;
-            }
+        if let Err(e) = refresh_blockhash(&engine).await {
+            engine.metrics.blockhash_fetch_errors.inc();
+            warn!(error = %e, age = ?engine.blockhash.age(), "Failed to fetch the latest blockhash");
         }
+        sleep(Duration::from_millis(engine.config().strategy.blockhash_refresh_ms)).await;
     }
-
-    sleep(Duration::from_millis(500)).await;
 }
@@ -0,0 +1,165 @@
+//! Compute unit limits right-sized from simulation.
+//!
+//! With `[compute_units] enabled`, a transaction on a route without a learned limit is
+//! built with `[fees] compute_unit_limit`, simulated, and rebuilt requesting the
+//! simulated `unitsConsumed` plus `margin_bps`. The limit is kept per route (the pools of
+//! every leg, and whether the trade is flash-loan wrapped) for `cache_ttl_secs`, so later
+//! opportunities on the same route are built without another simulation. With the
+//! `[simulation]` gate enabled there is no separate probe: the transaction the gate
+//! simulates at the `[fees]` limit is rebuilt at the limit its report measured.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use jupiter_swap_api_client::quote::QuoteResponse;
+use solana_client::rpc_config::RpcSimulateTransactionConfig;
+use solana_sdk::{commitment_config::CommitmentConfig, transaction::VersionedTransaction};
+use tracing::{debug, warn};
+
+use crate::Engine;
+
+/// Learned compute unit limits by route key, see [`route_key`].
+#[derive(Default)]
+pub struct ComputeUnitCache {
+    limits: Mutex<HashMap<String, (u32, Instant)>>,
+}
+
+impl ComputeUnitCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limit learned for `route` no older than `max_age`.
+    pub fn get(&self, route: &str, max_age: Duration) -> Option<u32> {
+        let limits = self.limits.lock().unwrap();
+        let (limit, learned) = limits.get(route)?;
+        (learned.elapsed() <= max_age).then_some(*limit)
+    }
+
+    pub fn insert(&self, route: &str, limit: u32) {
+        self.limits.lock().unwrap().insert(route.to_string(), (limit, Instant::now()));
+    }
+
+    pub fn len(&self) -> usize {
+        self.limits.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Cache key of a trade: the pools of every leg in order, prefixed for flash-loan trades
+/// (which run the borrow and payback instructions as well).
+pub fn route_key(legs: &[QuoteResponse], flash_loan: bool) -> String {
+    let pools: Vec<String> = legs
+        .iter()
+        .flat_map(|leg| leg.route_plan.iter().map(|step| step.swap_info.amm_key.to_string()))
+        .collect();
+    let prefix = if flash_loan { "flash_loan:" } else { "" };
+    format!("{}{}", prefix, pools.join(">"))
+}
+
+/// `units_consumed` plus `margin_bps`, rounded up and capped at `max`.
+pub fn limit_with_margin(units_consumed: u64, margin_bps: u64, max: u32) -> u32 {
+    let limit = (units_consumed as u128 * (10_000 + margin_bps) as u128).div_ceil(10_000);
+    limit.clamp(1, max as u128) as u32
+}
+
+/// Compute units `tx` consumes in simulation; an error if it fails.
+pub async fn simulate_compute_units(engine: &Engine, tx: &VersionedTransaction) -> Result<u64, anyhow::Error> {
+    let config = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: false,
+        commitment: Some(CommitmentConfig::processed()),
+        ..Default::default()
+    };
+    let result = engine.rpc_client.simulate_transaction_with_config(tx, config).await?.value;
+    if let Some(err) = result.err {
+        return Err(anyhow::anyhow!("Simulation failed: {:?}", err));
+    }
+    result
+        .units_consumed
+        .filter(|units| *units > 0)
+        .ok_or_else(|| anyhow::anyhow!("Simulation reported no unitsConsumed"))
+}
+
+/// Build the transaction for `route` with `build(compute_unit_limit)`.
+///
+/// Disabled: the `[fees]` limit. Otherwise the limit learned for `route`, or one measured
+/// now by simulating the transaction at the `[fees]` limit; if that simulation fails the
+/// `[fees]` limit is kept and nothing is learned. With the `[simulation]` gate enabled a
+/// route without a learned limit is built at the `[fees]` limit unsimulated, for
+/// [`right_size_simulated`] to resize after the gate.
+pub async fn right_sized_transaction<F>(
+    engine: &Engine,
+    route: &str,
+    build: F,
+) -> Result<VersionedTransaction, anyhow::Error>
+where
+    F: Fn(u32) -> Result<VersionedTransaction, anyhow::Error>,
+{
+    let config = engine.config();
+    let max = config.tx_cost.compute_units as u32;
+    let settings = &config.compute_units;
+    if !settings.enabled {
+        return build(max);
+    }
+    if let Some(limit) = engine.compute_units.get(route, Duration::from_secs(settings.cache_ttl_secs)) {
+        return build(limit.min(max));
+    }
+    if config.simulation.enabled {
+        return build(max);
+    }
+
+    let probe = build(max)?;
+    match simulate_compute_units(engine, &probe).await {
+        Ok(units_consumed) => {
+            let limit = limit_with_margin(units_consumed, settings.margin_bps, max);
+            debug!(%route, units_consumed, limit, "Learned compute unit limit");
+            engine.compute_units.insert(route, limit);
+            build(limit)
+        }
+        Err(e) => {
+            warn!(error = %e, %route, "Compute unit simulation failed; using the [fees] limit");
+            Ok(probe)
+        }
+    }
+}
+
+/// Resize `tx` after the `[simulation]` gate passed it with `units_consumed`.
+///
+/// On a route without a learned limit (built at the `[fees]` limit by
+/// [`right_sized_transaction`]) the limit is learned from the gate's simulation and the
+/// transaction rebuilt at it. Otherwise, or if the rebuild fails, `tx` is kept.
+pub fn right_size_simulated<F>(
+    engine: &Engine,
+    route: &str,
+    tx: VersionedTransaction,
+    units_consumed: Option<u64>,
+    build: F,
+) -> VersionedTransaction
+where
+    F: Fn(u32) -> Result<VersionedTransaction, anyhow::Error>,
+{
+    let config = engine.config();
+    let settings = &config.compute_units;
+    let Some(units_consumed) = units_consumed.filter(|units| *units > 0) else {
+        return tx;
+    };
+    if !settings.enabled || engine.compute_units.get(route, Duration::from_secs(settings.cache_ttl_secs)).is_some() {
+        return tx;
+    }
+
+    let limit = limit_with_margin(units_consumed, settings.margin_bps, config.tx_cost.compute_units as u32);
+    debug!(%route, units_consumed, limit, "Learned compute unit limit from the simulation gate");
+    engine.compute_units.insert(route, limit);
+    match build(limit) {
+        Ok(resized) => resized,
+        Err(e) => {
+            warn!(error = %e, %route, "Rebuilding at the simulated compute unit limit failed; using the [fees] limit");
+            tx
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Hot reload of the config file: re-parse it when it changes and apply it through
+//! [`Engine::reload_config`].
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
+
+use crate::app::config::Config;
+use crate::{ConfigReload, Engine};
+
+/// Parse the config at `path` and apply it to `engine`.
+pub fn reload_config_file(engine: &Engine, path: &Path) -> Result<ConfigReload, anyhow::Error> {
+    let config = Config::load(path)?;
+    engine.reload_config(config)
+}
+
+/// Check `path` every `[hot_reload] check_interval_ms` and reload it when it changed, forever.
+///
+/// A file that fails to parse or is rejected leaves the current config in place; the
+/// next change is tried again.
+pub async fn watch_config_file(engine: Arc<Engine>, path: PathBuf) {
+    let interval_ms = engine.config().hot_reload.check_interval_ms.max(100);
+    info!(path = %path.display(), interval_ms, "Watching config file for changes");
+
+    let mut last_stamp = file_stamp(&path);
+    let mut ticker = interval(Duration::from_millis(interval_ms));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        ticker.tick().await;
+        let stamp = file_stamp(&path);
+        if stamp == last_stamp {
+            continue;
+        }
+        last_stamp = stamp;
+
+        match reload_config_file(&engine, &path) {
+            Ok(reload) if reload.applied.is_empty() && reload.needs_restart.is_empty() => {
+                debug!("Config file changed; no setting changed")
+            }
+            Ok(reload) => {
+                if !reload.applied.is_empty() {
+                    info!(sections = ?reload.applied, "Config reloaded");
+                }
+                if reload.applied.contains(&"strategy") {
+                    // New instruments need their decimals before the big-trade filter can use them
+                    if let Err(e) = engine.tokens.resolve_all(&instrument_mints(&engine)).await {
+                        warn!(error = %e, "Token metadata for reloaded instruments unavailable");
+                    }
+                }
+                if !reload.needs_restart.is_empty() {
+                    warn!(sections = ?reload.needs_restart, "Config sections changed; restart to apply them");
+                }
+            }
+            Err(e) => error!(error = %e, "Config reload rejected; keeping the current config"),
+        }
+    }
+}
+
+fn instrument_mints(engine: &Engine) -> Vec<String> {
+    engine.config().strategy.base_tokens.iter().map(|t| t.mint.clone()).collect()
+}
+
+/// Modification time and length of `path`; None while it is missing.
+fn file_stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
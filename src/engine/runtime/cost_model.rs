@@ -0,0 +1,278 @@
+//! Full cost of one arbitrage transaction, in lamports and in the base token.
+//!
+//! A transaction pays the base fee per signature, the priority fee (compute unit price
+//! × compute unit limit), the tip it carries (at least `[jito] min_tip_lamports` for
+//! bundles, `relay_tip_sol` for regular trades sent to the submit endpoints),
+//! rent for the token accounts it creates and, when flash-loan wrapped, the flash loan
+//! fee on the borrowed amount. Lamport costs are converted to the base token at the SOL
+//! price in that token: observed from quotes that already went through WSOL, else
+//! quoted for 1 SOL, and kept for `[fees] price_ttl_secs`. The compute unit price comes from
+//! [`crate::PriorityFeeEstimator::price`].
+
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use jupiter_swap_api_client::quote::{QuoteRequest, QuoteResponse};
+use solana_sdk::{commitment_config::CommitmentConfig, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey};
+use tracing::warn;
+
+use crate::app::config::TxCostConfig;
+use crate::chain::TRANSACTION_FEE;
+use crate::{relay_tip_lamports, Engine, WSOL};
+
+use super::sol_price::get_sol_price_usdc;
+
+/// Rent-exempt minimum of a 165-byte token account, paid when a transaction creates one.
+pub const TOKEN_ACCOUNT_RENT_LAMPORTS: u64 = 2_039_280;
+
+/// USD-pegged mints (USDC, USDT): without a quote, their SOL price is `sol_price_usd`.
+const USD_STABLECOINS: &[&str] = &[
+    "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+    "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB",
+];
+
+/// What a concrete transaction pays for besides the swap itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TxPlan {
+    pub signatures: u64,
+    pub compute_unit_limit: u64,
+    /// Compute unit price in micro-lamports.
+    pub compute_unit_price: u64,
+    pub tip_lamports: u64,
+    /// Token accounts the transaction creates and pays rent for.
+    pub created_token_accounts: u64,
+    /// Flash loan fee on the borrowed amount, in basis points; 0 without a flash loan.
+    pub flash_loan_fee_bps: u16,
+}
+
+impl TxPlan {
+    /// A single-signer transaction with the compute budget from `[fees]` and no tip.
+    pub fn from_fees(fees: &TxCostConfig) -> Self {
+        Self {
+            signatures: 1,
+            compute_unit_limit: fees.compute_units,
+            compute_unit_price: fees.priority_lamports,
+            ..Self::default()
+        }
+    }
+
+    pub fn lamports(&self) -> LamportCost {
+        let priority_fee = (self.compute_unit_limit as u128 * self.compute_unit_price as u128).div_ceil(1_000_000);
+        LamportCost {
+            base_fee: self.signatures * TRANSACTION_FEE,
+            priority_fee: priority_fee as u64,
+            tip: self.tip_lamports,
+            rent: self.created_token_accounts * TOKEN_ACCOUNT_RENT_LAMPORTS,
+        }
+    }
+}
+
+/// Lamports a transaction spends, by kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct LamportCost {
+    pub base_fee: u64,
+    pub priority_fee: u64,
+    pub tip: u64,
+    pub rent: u64,
+}
+
+impl LamportCost {
+    pub fn total(&self) -> u64 {
+        self.base_fee + self.priority_fee + self.tip + self.rent
+    }
+}
+
+/// Cost of a trade in the base token's raw units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeCost {
+    pub plan: TxPlan,
+    pub lamports: LamportCost,
+    /// `lamports` converted to the base token.
+    pub fixed: u64,
+    /// Raw base-token units per SOL used for the conversion.
+    pub sol_price: f64,
+    /// Largest share of a trade's remaining profit the priority fee may take, see
+    /// [`TradeCost::for_trade`]; None leaves the compute unit price as planned.
+    pub max_priority_share: Option<f64>,
+}
+
+impl TradeCost {
+    pub fn new(plan: TxPlan, sol_price: f64, max_priority_share: Option<f64>) -> Self {
+        let lamports = plan.lamports();
+        Self {
+            plan,
+            lamports,
+            fixed: (lamports.total() as f64 * sol_price / LAMPORTS_PER_SOL as f64).ceil() as u64,
+            sol_price,
+            max_priority_share,
+        }
+    }
+
+    /// Total cost of trading `in_amount`: the fixed cost plus the flash loan fee on it.
+    pub fn for_amount(&self, in_amount: u64) -> u64 {
+        let flash_loan_fee = (in_amount as u128 * self.plan.flash_loan_fee_bps as u128).div_ceil(10_000);
+        self.fixed + flash_loan_fee as u64
+    }
+
+    /// This cost for a trade of `in_amount` expected to return `gross_profit`: the compute
+    /// unit price is lowered where needed so the priority fee takes at most
+    /// `max_priority_share` of what is left after every other cost.
+    pub fn for_trade(&self, in_amount: u64, gross_profit: i64) -> TradeCost {
+        let Some(share) = self.max_priority_share else {
+            return *self;
+        };
+        let priority_fee = self.lamports.priority_fee as f64 * self.sol_price / LAMPORTS_PER_SOL as f64;
+        let other_costs = self.for_amount(in_amount) as f64 - priority_fee;
+        let left = (gross_profit as f64 - other_costs).max(0.0);
+        let max_priority_lamports = left * LAMPORTS_PER_SOL as f64 / self.sol_price * share;
+        let max_price = (max_priority_lamports * 1_000_000.0 / self.plan.compute_unit_limit.max(1) as f64) as u64;
+        if max_price >= self.plan.compute_unit_price {
+            return *self;
+        }
+        let plan = TxPlan {
+            compute_unit_price: max_price,
+            ..self.plan
+        };
+        Self::new(plan, self.sol_price, self.max_priority_share)
+    }
+}
+
+/// SOL prices and known token accounts behind [`trade_cost`].
+#[derive(Default)]
+pub struct CostModel {
+    /// Raw units of a mint per SOL, with when the price was seen.
+    sol_prices: Mutex<HashMap<String, (f64, Instant)>>,
+    /// Token accounts known to exist; creating them again costs no rent.
+    existing_accounts: Mutex<HashSet<Pubkey>>,
+}
+
+impl CostModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the SOL price implied by a quote that sells or buys WSOL.
+    pub fn observe(&self, quote: &QuoteResponse) {
+        if quote.in_amount == 0 || quote.out_amount == 0 {
+            return;
+        }
+        let sol = LAMPORTS_PER_SOL as f64;
+        let (mint, price) = if quote.input_mint == WSOL {
+            (quote.output_mint, quote.out_amount as f64 * sol / quote.in_amount as f64)
+        } else if quote.output_mint == WSOL {
+            (quote.input_mint, quote.in_amount as f64 * sol / quote.out_amount as f64)
+        } else {
+            return;
+        };
+        self.set_sol_price(&mint.to_string(), price);
+    }
+
+    pub fn set_sol_price(&self, mint: &str, price: f64) {
+        self.sol_prices.lock().unwrap().insert(mint.to_string(), (price, Instant::now()));
+    }
+
+    /// Last SOL price in `mint` no older than `max_age` (any age if None).
+    pub fn sol_price(&self, mint: &str, max_age: Option<Duration>) -> Option<f64> {
+        let prices = self.sol_prices.lock().unwrap();
+        let (price, seen) = prices.get(mint)?;
+        match max_age {
+            Some(max_age) if seen.elapsed() > max_age => None,
+            _ => Some(*price),
+        }
+    }
+}
+
+/// Raw units of `mint` per SOL: exact for WSOL, else a fresh observed price, else a quote
+/// for 1 SOL, else the last known price, else `sol_price_usd` for USD stablecoins.
+pub async fn sol_price_in(engine: &Engine, mint: &str, decimals: u8) -> Result<f64, anyhow::Error> {
+    if mint == WSOL.to_string() {
+        return Ok(LAMPORTS_PER_SOL as f64);
+    }
+    let fees = engine.fees();
+    if let Some(price) = engine.costs.sol_price(mint, Some(Duration::from_secs(fees.price_ttl_secs))) {
+        return Ok(price);
+    }
+
+    let request = QuoteRequest {
+        amount: LAMPORTS_PER_SOL,
+        input_mint: WSOL,
+        output_mint: Pubkey::from_str(mint)?,
+        slippage_bps: 0,
+        ..QuoteRequest::default()
+    };
+    let error = match engine.quote_provider(mint).quote(&request).await {
+        Ok(quote) if quote.out_amount > 0 => {
+            engine.costs.observe(&quote);
+            return Ok(quote.out_amount as f64);
+        }
+        Ok(_) => anyhow::anyhow!("empty quote"),
+        Err(e) => e,
+    };
+
+    if let Some(price) = engine.costs.sol_price(mint, None) {
+        warn!(%mint, error = %error, "SOL price quote failed; using the last known price");
+        return Ok(price);
+    }
+    if USD_STABLECOINS.contains(&mint) {
+        let sol_usd = get_sol_price_usdc(fees.sol_usd).await;
+        return Ok(sol_usd * 10_f64.powi(decimals as i32));
+    }
+    Err(anyhow::anyhow!("No SOL price in {}: {}", mint, error))
+}
+
+/// The transaction an opportunity in `base_mint` would submit: flash-loan wrapped when a
+/// reserve is configured, paying rent if the borrowed token's account does not exist yet.
+/// Only tips the transaction actually carries are counted: a bundle's (at least
+/// `[jito] min_tip_lamports`), else `relay_tip_sol` on a regular trade. Flash-loan trades
+/// sent to the submit endpoints pay no tip.
+pub async fn tx_plan(engine: &Engine, base_mint: &str) -> TxPlan {
+    let config = engine.config();
+    let mut plan = TxPlan::from_fees(&config.tx_cost);
+    plan.compute_unit_price = engine.priority_fees.price(&config);
+    let flash_loan = engine.flash_loan_context(base_mint).is_some();
+    if config.jito.enabled {
+        plan.tip_lamports = config.jito.min_tip_lamports;
+    } else if !flash_loan {
+        plan.tip_lamports = relay_tip_lamports(&config.tx_cost);
+    }
+    if !flash_loan {
+        return plan;
+    }
+    plan.flash_loan_fee_bps = config.flash_loan.fee_bps;
+    let exists = match engine.tokens.resolve(base_mint).await {
+        Ok(token) => token_account_exists(engine, &token.associated_token_address(&engine.pubkey)).await,
+        Err(_) => false,
+    };
+    plan.created_token_accounts = if exists { 0 } else { 1 };
+    plan
+}
+
+async fn token_account_exists(engine: &Engine, address: &Pubkey) -> bool {
+    if engine.costs.existing_accounts.lock().unwrap().contains(address) {
+        return true;
+    }
+    let exists = matches!(
+        engine
+            .rpc_client
+            .get_account_with_commitment(address, CommitmentConfig::processed())
+            .await,
+        Ok(response) if response.value.is_some()
+    );
+    if exists {
+        engine.costs.existing_accounts.lock().unwrap().insert(*address);
+    }
+    exists
+}
+
+/// Cost of an opportunity in `base_mint` (raw units, `decimals`), see the module docs.
+/// With `[priority_fee]` enabled, use [`TradeCost::for_trade`] to cap the priority fee
+/// per trade.
+pub async fn trade_cost(engine: &Engine, base_mint: &str, decimals: u8) -> Result<TradeCost, anyhow::Error> {
+    let plan = tx_plan(engine, base_mint).await;
+    let sol_price = sol_price_in(engine, base_mint, decimals).await?;
+    let config = engine.config();
+    let max_priority_share = config.priority_fee.enabled.then_some(config.priority_fee.max_profit_share);
+    Ok(TradeCost::new(plan, sol_price, max_priority_share))
+}
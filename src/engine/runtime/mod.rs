@@ -1,10 +1,20 @@
 mod alt;
 pub mod block_hash;
-pub mod fee_cost;
+pub mod compute_units;
+pub mod config_reload;
+pub mod cost_model;
 pub mod nonce;
+pub mod nonce_pool;
+pub mod priority_fee;
 pub mod sol_price;
+pub mod tx_lifetime;
 pub use alt::*;
 pub use block_hash::*;
-pub use fee_cost::*;
+pub use compute_units::*;
+pub use config_reload::*;
+pub use cost_model::*;
 pub use nonce::*;
-pub use sol_price::*;
\ No newline at end of file
+pub use nonce_pool::*;
+pub use priority_fee::*;
+pub use sol_price::*;
+pub use tx_lifetime::*;
\ No newline at end of file
@@ -1,49 +1,174 @@
+//! Durable nonce accounts: reading, lifecycle (create, fund, re-authorize, close), the
+//! startup authority check and the background fetcher behind [`crate::NoncePool`].
+
 use solana_rpc_client_nonce_utils::data_from_account;
-use solana_sdk::{commitment_config::CommitmentConfig, nonce::state::Data as NonceData};
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    nonce::state::{Data as NonceData, State as NonceState},
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{keypair::Keypair, Signer},
+    system_instruction::{
+        authorize_nonce_account as authorize_nonce_account_ix,
+        create_nonce_account as create_nonce_account_ixs, transfer, withdraw_nonce_account,
+    },
+    transaction::Transaction,
+};
 use tokio::time::{Duration, sleep};
-use once_cell::sync::Lazy;
-use std::sync::Mutex;
-
-use crate::{NONCE_ADDR, RPC_CLIENT};
 
-pub static GLOBAL_CURRENT_NONCE: Lazy<Mutex<NonceData>> =
-    Lazy::new(|| Mutex::new(NonceData::default()));
+use crate::{Engine, ENGINE};
 
+/// Store a nonce value in the global [`ENGINE`].
 pub fn set_nonce(new_nonce: NonceData) {
-    let mut nonce = GLOBAL_CURRENT_NONCE.lock().unwrap();
-    *nonce = new_nonce;
+    ENGINE.set_nonce(new_nonce);
 }
 
+/// Current nonce value of the global [`ENGINE`].
 pub fn get_nonce() -> NonceData {
-    let nonce = GLOBAL_CURRENT_NONCE.lock().unwrap();
-    nonce.clone()
+    ENGINE.get_nonce()
+}
+
+/// Fetch and decode the nonce account at `nonce_addr` (processed commitment).
+pub async fn read_nonce(engine: &Engine, nonce_addr: &Pubkey) -> Result<NonceData, anyhow::Error> {
+    Ok(inspect_nonce_account(engine, nonce_addr).await?.data)
+}
+
+/// A nonce account's state and balance.
+#[derive(Debug, Clone)]
+pub struct NonceAccountInfo {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub data: NonceData,
+}
+
+/// Fetch a nonce account with its balance (processed commitment).
+pub async fn inspect_nonce_account(engine: &Engine, address: &Pubkey) -> Result<NonceAccountInfo, anyhow::Error> {
+    let account = engine
+        .rpc_client
+        .get_account_with_commitment(address, CommitmentConfig::processed())
+        .await?
+        .value
+        .ok_or_else(|| anyhow::anyhow!("Nonce account {} not found", address))?;
+    let data = data_from_account(&account).map_err(|e| anyhow::anyhow!("Failed to decode nonce {}: {}", address, e))?;
+    Ok(NonceAccountInfo {
+        address: *address,
+        lamports: account.lamports,
+        data,
+    })
+}
+
+/// Sign `ixs` with the wallet as payer (plus `extra_signers`), send and wait for confirmation.
+async fn send_wallet_transaction(
+    engine: &Engine,
+    ixs: &[Instruction],
+    extra_signers: &[&Keypair],
+) -> Result<Signature, anyhow::Error> {
+    let mut signers: Vec<&Keypair> = vec![&engine.keypair];
+    signers.extend_from_slice(extra_signers);
+    let blockhash = engine.rpc_client.get_latest_blockhash().await?;
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&engine.pubkey), &signers, blockhash);
+    Ok(engine.rpc_client.send_and_confirm_transaction(&tx).await?)
+}
+
+/// Fetch `address` and fail unless the wallet is its nonce authority.
+async fn owned_nonce_account(engine: &Engine, address: &Pubkey) -> Result<NonceAccountInfo, anyhow::Error> {
+    let info = inspect_nonce_account(engine, address).await?;
+    if info.data.authority != engine.pubkey {
+        return Err(anyhow::anyhow!(
+            "Nonce account {} is controlled by {}, not the wallet {}",
+            address,
+            info.data.authority,
+            engine.pubkey
+        ));
+    }
+    Ok(info)
+}
+
+/// Create and fund a new durable nonce account with the engine's wallet as payer and
+/// nonce authority. Returns the new account's address and the creating transaction.
+pub async fn create_nonce_account(engine: &Engine) -> Result<(Pubkey, Signature), anyhow::Error> {
+    let nonce_keypair = Keypair::new();
+    let rent = engine
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(NonceState::size())
+        .await?;
+    let ixs = create_nonce_account_ixs(&engine.pubkey, &nonce_keypair.pubkey(), &engine.pubkey, rent);
+    let signature = send_wallet_transaction(engine, &ixs, &[&nonce_keypair]).await?;
+    Ok((nonce_keypair.pubkey(), signature))
+}
+
+/// Transfer `lamports` from the wallet to the nonce account at `address`.
+pub async fn fund_nonce_account(engine: &Engine, address: &Pubkey, lamports: u64) -> Result<Signature, anyhow::Error> {
+    // Only to an actual nonce account, not a mistyped address
+    inspect_nonce_account(engine, address).await?;
+    send_wallet_transaction(engine, &[transfer(&engine.pubkey, address, lamports)], &[]).await
+}
+
+/// Hand the nonce authority of `address` from the wallet to `new_authority`.
+pub async fn authorize_nonce_account(
+    engine: &Engine,
+    address: &Pubkey,
+    new_authority: &Pubkey,
+) -> Result<Signature, anyhow::Error> {
+    owned_nonce_account(engine, address).await?;
+    let ix = authorize_nonce_account_ix(address, &engine.pubkey, new_authority);
+    send_wallet_transaction(engine, &[ix], &[]).await
+}
+
+/// Withdraw the whole balance of `address` to `recipient` (default: the wallet), which
+/// closes the account. Returns the withdrawn lamports and the transaction.
+pub async fn close_nonce_account(
+    engine: &Engine,
+    address: &Pubkey,
+    recipient: Option<Pubkey>,
+) -> Result<(u64, Signature), anyhow::Error> {
+    let info = owned_nonce_account(engine, address).await?;
+    let recipient = recipient.unwrap_or(engine.pubkey);
+    let ix = withdraw_nonce_account(address, &engine.pubkey, &recipient, info.lamports);
+    let signature = send_wallet_transaction(engine, &[ix], &[]).await?;
+    Ok((info.lamports, signature))
 }
 
-pub async fn fetch_nonce() {
+/// Check every configured nonce account before the bot starts: it must exist and the
+/// signer must be its authority, or transactions advancing it can never land. Loads the
+/// current values into the engine's pool.
+pub async fn check_nonce_accounts(engine: &Engine) -> Result<(), anyhow::Error> {
+    let mut problems = Vec::new();
+    for address in engine.nonces.addresses() {
+        match read_nonce(engine, &address).await {
+            Ok(data) if data.authority != engine.pubkey => problems.push(format!(
+                "nonce account {} has authority {}, but the signer is {}; re-authorize it with \
+                 `nonce authorize` or configure the matching keypair",
+                address, data.authority, engine.pubkey
+            )),
+            Ok(data) => engine.nonces.set(&address, data),
+            Err(e) => problems.push(format!("{}; create one with `nonce create`", e)),
+        }
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(anyhow::anyhow!("Nonce accounts unusable:\n  {}", problems.join("\n  ")))
+}
+
+/// Poll every nonce account of the engine's pool every 200 ms and keep its current
+/// value up to date; a lease ends once its account's nonce advances.
+pub async fn fetch_nonce(engine: &Engine) {
+    let addresses = engine.nonces.addresses();
     loop {
-        match RPC_CLIENT
-            .get_account_with_commitment(&NONCE_ADDR, CommitmentConfig::processed())
-            .await
-        {
-            Ok(response) => {
-                if let Some(account) = response.value {
-                    match data_from_account(&account) {
-                        Ok(nonce_data) => {
-                            set_nonce(nonce_data);
-                        }
-                        Err(err) => {
-                            eprintln!("[NONCE ERROR] Failed to decode nonce: {}", err);
-                        }
-                    }
-                } else {
-                    eprintln!("[NONCE ERROR] Nonce account not found.");
+        for address in &addresses {
+            match read_nonce(engine, address).await {
+                Ok(nonce_data) => {
+                    engine.nonces.set(address, nonce_data);
+                }
+                Err(e) => {
+                    engine.metrics.nonce_fetch_errors.inc();
+                    eprintln!(
+                        "[NONCE ERROR]\n\t* ERR MSG : {}\n\t* Retrying in 200ms...",
+                        e
+                    );
                 }
-            }
-            Err(e) => {
-                eprintln!(
-                    "[NONCE ERROR]\n\t* ERR MSG : {}\n\t* Retrying in 200ms...",
-                    e
-                );
             }
         }
 
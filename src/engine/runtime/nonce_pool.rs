@@ -0,0 +1,155 @@
+//! Pool of durable nonce accounts leased to in-flight transactions.
+//!
+//! Each account in `[strategy] nonce_account_pubkey` + `nonce_account_pubkeys` holds one
+//! nonce value, and two transactions signed with the same value cannot both land. A
+//! submission therefore leases an account whose value no other transaction uses and
+//! keeps the lease while its transaction is in flight. The lease ends when
+//! [`fetch_nonce`](crate::fetch_nonce) sees the nonce advance, when the transaction
+//! expires (`nonce_lease_timeout_secs`, or the landing tracker gives up on it), or at
+//! once when the transaction is never sent.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use solana_sdk::{hash::Hash, nonce::state::Data as NonceData, pubkey::Pubkey};
+
+struct Lease {
+    /// Nonce value the leasing transaction was signed with.
+    blockhash: Hash,
+    since: Instant,
+}
+
+#[derive(Default)]
+struct SlotState {
+    /// Last value read from chain; None until the first read.
+    nonce: Option<NonceData>,
+    lease: Option<Lease>,
+}
+
+struct NonceSlot {
+    address: Pubkey,
+    state: Mutex<SlotState>,
+}
+
+/// Nonce accounts, their current values and leases.
+pub struct NoncePool {
+    slots: Vec<NonceSlot>,
+}
+
+impl NoncePool {
+    /// A pool over `addresses`; the first is the primary account.
+    pub fn new(addresses: Vec<Pubkey>) -> Self {
+        Self {
+            slots: addresses
+                .into_iter()
+                .map(|address| NonceSlot {
+                    address,
+                    state: Mutex::new(SlotState::default()),
+                })
+                .collect(),
+        }
+    }
+
+    pub fn addresses(&self) -> Vec<Pubkey> {
+        self.slots.iter().map(|slot| slot.address).collect()
+    }
+
+    fn slot(&self, address: &Pubkey) -> Option<&NonceSlot> {
+        self.slots.iter().find(|slot| slot.address == *address)
+    }
+
+    /// Record the value read from `address`; a lease on an older value ends.
+    pub fn set(&self, address: &Pubkey, nonce: NonceData) {
+        let Some(slot) = self.slot(address) else {
+            return;
+        };
+        let mut state = slot.state.lock().unwrap();
+        if matches!(&state.lease, Some(lease) if lease.blockhash != nonce.blockhash()) {
+            state.lease = None;
+        }
+        state.nonce = Some(nonce);
+    }
+
+    /// Last value read from `address`.
+    pub fn get(&self, address: &Pubkey) -> Option<NonceData> {
+        self.slot(address)?.state.lock().unwrap().nonce.clone()
+    }
+
+    /// Lease an account with a known value that is not in use. Leases older than
+    /// `lease_timeout` count as expired. None when every account is leased or unread.
+    pub fn acquire(&self, lease_timeout: Duration) -> Option<NonceLease<'_>> {
+        for slot in &self.slots {
+            let mut state = slot.state.lock().unwrap();
+            let Some(blockhash) = state.nonce.as_ref().map(NonceData::blockhash) else {
+                continue;
+            };
+            if matches!(&state.lease, Some(lease) if lease.since.elapsed() < lease_timeout) {
+                continue;
+            }
+            state.lease = Some(Lease {
+                blockhash,
+                since: Instant::now(),
+            });
+            return Some(NonceLease {
+                pool: self,
+                address: slot.address,
+                blockhash,
+                sent: false,
+            });
+        }
+        None
+    }
+
+    /// End the lease on `address` if it is still held for `blockhash`.
+    pub fn release(&self, address: &Pubkey, blockhash: &Hash) {
+        let Some(slot) = self.slot(address) else {
+            return;
+        };
+        let mut state = slot.state.lock().unwrap();
+        if matches!(&state.lease, Some(lease) if lease.blockhash == *blockhash) {
+            state.lease = None;
+        }
+    }
+
+    /// Accounts currently leased (expired leases included until reacquired).
+    pub fn leased(&self) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.state.lock().unwrap().lease.is_some())
+            .count()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+}
+
+/// One nonce account reserved for one transaction. Dropping the lease before
+/// [`NonceLease::sent`] returns the account to the pool.
+pub struct NonceLease<'a> {
+    pool: &'a NoncePool,
+    pub address: Pubkey,
+    /// Nonce value to sign with.
+    pub blockhash: Hash,
+    sent: bool,
+}
+
+impl NonceLease<'_> {
+    /// The transaction was sent: keep the account leased until its nonce advances or
+    /// the lease expires.
+    pub fn sent(mut self) {
+        self.sent = true;
+    }
+}
+
+impl Drop for NonceLease<'_> {
+    fn drop(&mut self) {
+        if !self.sent {
+            self.pool.release(&self.address, &self.blockhash);
+        }
+    }
+}
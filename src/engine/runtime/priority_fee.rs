@@ -0,0 +1,153 @@
+//! Compute unit price estimated from recent prioritization fees.
+//!
+//! With `[priority_fee] enabled`, [`run_priority_fee_estimator`] samples
+//! `getRecentPrioritizationFees` for the writable accounts our routes lock — the pools in
+//! recent quotes, the writable accounts of built swap instructions, the Jupiter program
+//! and `[priority_fee] accounts` — and keeps the configured percentile of the per-slot
+//! fees, bounded by `max_micro_lamports`. [`PriorityFeeEstimator::price`] feeds both the
+//! cost model and the compute budget instructions; the cost model lowers it per trade
+//! so the priority fee never takes more than `max_profit_share` of the expected profit.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use jupiter_swap_api_client::quote::QuoteResponse;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use tokio::time::{interval, Duration};
+use tracing::{debug, warn};
+
+use crate::app::config::Config;
+use crate::{Engine, JUPITER_PROGRAM_ADDR};
+
+/// `getRecentPrioritizationFees` accepts at most 128 accounts.
+const MAX_ACCOUNTS: usize = 128;
+
+/// Accounts to sample and the current estimate.
+#[derive(Default)]
+pub struct PriorityFeeEstimator {
+    /// Writable accounts seen in our routes, with when they were last seen.
+    accounts: Mutex<HashMap<Pubkey, Instant>>,
+    /// Micro-lamports per compute unit; None until the first sample.
+    estimate: Mutex<Option<u64>>,
+}
+
+impl PriorityFeeEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the pools a quote routes through.
+    pub fn observe_quote(&self, quote: &QuoteResponse) {
+        self.observe(quote.route_plan.iter().map(|step| step.swap_info.amm_key));
+    }
+
+    /// Record the writable, non-signer accounts of a built instruction.
+    pub fn observe_instruction(&self, ix: &Instruction) {
+        self.observe(
+            ix.accounts
+                .iter()
+                .filter(|meta| meta.is_writable && !meta.is_signer)
+                .map(|meta| meta.pubkey),
+        );
+    }
+
+    fn observe(&self, accounts: impl IntoIterator<Item = Pubkey>) {
+        let now = Instant::now();
+        let mut seen = self.accounts.lock().unwrap();
+        for account in accounts {
+            seen.insert(account, now);
+        }
+        // Keep the most recently seen
+        while seen.len() > MAX_ACCOUNTS {
+            let oldest = seen.iter().min_by_key(|(_, at)| **at).map(|(k, _)| *k);
+            if let Some(oldest) = oldest {
+                seen.remove(&oldest);
+            }
+        }
+    }
+
+    /// Accounts to sample: the Jupiter program, `configured`, then the most recently seen.
+    pub fn accounts(&self, configured: &[String]) -> Vec<Pubkey> {
+        let mut accounts = vec![JUPITER_PROGRAM_ADDR];
+        accounts.extend(configured.iter().filter_map(|a| Pubkey::from_str(a).ok()));
+
+        let mut seen: Vec<(Pubkey, Instant)> =
+            self.accounts.lock().unwrap().iter().map(|(k, at)| (*k, *at)).collect();
+        seen.sort_by(|a, b| b.1.cmp(&a.1));
+        for (account, _) in seen {
+            if accounts.len() >= MAX_ACCOUNTS {
+                break;
+            }
+            if !accounts.contains(&account) {
+                accounts.push(account);
+            }
+        }
+        accounts.truncate(MAX_ACCOUNTS);
+        accounts
+    }
+
+    pub fn estimate(&self) -> Option<u64> {
+        *self.estimate.lock().unwrap()
+    }
+
+    pub fn set_estimate(&self, micro_lamports: u64) {
+        *self.estimate.lock().unwrap() = Some(micro_lamports);
+    }
+
+    /// Compute unit price to pay before any per-trade cap: the estimate when
+    /// `[priority_fee]` is enabled and sampled, else `[fees] priority_fee_lamports`.
+    pub fn price(&self, config: &Config) -> u64 {
+        match self.estimate() {
+            Some(estimate) if config.priority_fee.enabled => estimate,
+            _ => config.tx_cost.priority_lamports,
+        }
+    }
+}
+
+/// Nearest-rank percentile `p` (0-100) of `samples`; None if empty.
+pub fn fee_percentile(samples: &mut [u64], p: u8) -> Option<u64> {
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_unstable();
+    let rank = (p.min(100) as usize * samples.len()).div_ceil(100).max(1);
+    Some(samples[rank - 1])
+}
+
+/// Sample recent prioritization fees once and update the estimate.
+pub async fn refresh_priority_fee(engine: &Engine) -> Result<u64, anyhow::Error> {
+    let config = engine.config();
+    let settings = &config.priority_fee;
+    let accounts = engine.priority_fees.accounts(&settings.accounts);
+    let mut samples: Vec<u64> = engine
+        .rpc_client
+        .get_recent_prioritization_fees(&accounts)
+        .await?
+        .iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    let price = fee_percentile(&mut samples, settings.percentile)
+        .unwrap_or(0)
+        .min(settings.max_micro_lamports);
+    engine.priority_fees.set_estimate(price);
+    Ok(price)
+}
+
+/// Keep the estimate fresh every `[priority_fee] refresh_interval_ms`; returns at once if disabled.
+pub async fn run_priority_fee_estimator(engine: Arc<Engine>) {
+    let settings = engine.config().priority_fee.clone();
+    if !settings.enabled {
+        return;
+    }
+    let mut ticker = interval(Duration::from_millis(settings.refresh_interval_ms.max(1)));
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        ticker.tick().await;
+        match refresh_priority_fee(&engine).await {
+            Ok(price) => debug!(micro_lamports = price, "Priority fee estimate updated"),
+            Err(e) => warn!(error = ?e, "Priority fee sampling failed"),
+        }
+    }
+}
@@ -3,10 +3,14 @@ use tokio::sync::Mutex;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
 use chrono::Utc;
+use std::time::{Duration, Instant};
 
 /// Shared state for SOL price (fetched from CoinGecko)
 pub static SOL_PRICE: Lazy<Arc<Mutex<Option<f64>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
 
+/// When [`SOL_PRICE`] was last updated
+static SOL_PRICE_UPDATED_AT: std::sync::Mutex<Option<Instant>> = std::sync::Mutex::new(None);
+
 /// CoinGecko API response structure
 #[derive(Debug, Deserialize)]
 struct CoinGeckoResponse {
@@ -89,6 +93,12 @@ pub async fn fetch_sol_price_from_coingecko() -> Result<f64, anyhow::Error> {
 pub async fn update_sol_price(price: f64) {
     let mut price_guard = SOL_PRICE.lock().await;
     *price_guard = Some(price);
+    *SOL_PRICE_UPDATED_AT.lock().unwrap() = Some(Instant::now());
+}
+
+/// Time since the SOL price was last fetched; None while the config fallback is in use.
+pub fn sol_price_age() -> Option<Duration> {
+    SOL_PRICE_UPDATED_AT.lock().unwrap().map(|at| at.elapsed())
 }
 
 /// Get the current SOL price (with fallback to config value)
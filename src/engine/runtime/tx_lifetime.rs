@@ -0,0 +1,50 @@
+//! What a transaction is signed against, per `[strategy] transaction_lifetime`.
+
+use solana_sdk::{hash::Hash, instruction::Instruction, pubkey::Pubkey, system_instruction::advance_nonce_account};
+
+use crate::{LatestBlockhash, NonceLease};
+
+/// A leased durable nonce, or a recent blockhash. See [`crate::Engine::acquire_lifetime`].
+pub enum TxLifetime<'a> {
+    Nonce(NonceLease<'a>),
+    Blockhash(LatestBlockhash),
+}
+
+impl TxLifetime<'_> {
+    /// Blockhash to compile the message with (the nonce value for durable nonces).
+    pub fn blockhash(&self) -> Hash {
+        match self {
+            TxLifetime::Nonce(lease) => lease.blockhash,
+            TxLifetime::Blockhash(latest) => latest.blockhash,
+        }
+    }
+
+    /// Nonce account the transaction advances.
+    pub fn nonce_account(&self) -> Option<Pubkey> {
+        match self {
+            TxLifetime::Nonce(lease) => Some(lease.address),
+            TxLifetime::Blockhash(_) => None,
+        }
+    }
+
+    /// Last block height the transaction can land in; None for durable nonces.
+    pub fn last_valid_block_height(&self) -> Option<u64> {
+        match self {
+            TxLifetime::Nonce(_) => None,
+            TxLifetime::Blockhash(latest) => Some(latest.last_valid_block_height),
+        }
+    }
+
+    /// `advance_nonce_account`, which must be the first instruction of a durable nonce
+    /// transaction; None for a recent blockhash.
+    pub fn advance_nonce_instruction(&self, authority: &Pubkey) -> Option<Instruction> {
+        self.nonce_account().map(|address| advance_nonce_account(&address, authority))
+    }
+
+    /// The transaction was sent: keep a nonce account leased, see [`NonceLease::sent`].
+    pub fn sent(self) {
+        if let TxLifetime::Nonce(lease) = self {
+            lease.sent();
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod registry;
+pub mod transfer_fee;
+
+pub use registry::*;
+pub use transfer_fee::*;
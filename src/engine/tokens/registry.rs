@@ -0,0 +1,241 @@
+//! Token metadata: decimals, owning token program and Token-2022 transfer fee from the
+//! on-chain mint account (SPL Token or Token-2022), display symbols from
+//! `[tokens] symbols`, then the built-in [`POPULAR_TOKEN_INFO`] list.
+//!
+//! Each mint is fetched once and kept in memory; with `[tokens] cache_path` set the
+//! results are also written to disk, so a restart needs no RPC round trip for them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Serialize};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use tracing::{debug, warn};
+
+use super::transfer_fee::{TransferFeeConfig, TRANSFER_FEE_CONFIG_EXTENSION};
+use crate::app::config::TokensConfig;
+use crate::{POPULAR_TOKEN_INFO, TOKEN_2022_PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+/// Length of the base mint layout, shared by SPL Token and Token-2022 mints.
+const MINT_LEN: usize = 82;
+/// Offsets of `decimals` and `is_initialized` in the base mint layout.
+const MINT_DECIMALS_OFFSET: usize = 44;
+const MINT_INITIALIZED_OFFSET: usize = 45;
+/// Offset of the account type byte in Token-2022 accounts with extensions (1 = mint).
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+const ACCOUNT_TYPE_MINT: u8 = 1;
+/// `getMultipleAccounts` limit.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Resolved metadata of one mint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMeta {
+    pub mint: String,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Owning token program: [`TOKEN_PROGRAM_ID`] or [`TOKEN_2022_PROGRAM_ID`].
+    pub program_id: Pubkey,
+    /// Token-2022 transfer fee, if the mint has one.
+    pub transfer_fee: Option<TransferFeeConfig>,
+}
+
+impl TokenMeta {
+    pub fn is_token_2022(&self) -> bool {
+        self.program_id == TOKEN_2022_PROGRAM_ID
+    }
+
+    /// Fee withheld from a transfer of `amount` (0 without a transfer fee).
+    pub fn transfer_fee_on(&self, amount: u64) -> u64 {
+        self.transfer_fee.map(|f| f.fee(amount)).unwrap_or(0)
+    }
+
+    /// The owner's associated token account for this mint, under the mint's token program.
+    pub fn associated_token_address(&self, owner: &Pubkey) -> Pubkey {
+        let mint = Pubkey::from_str(&self.mint).expect("resolved mints are valid pubkeys");
+        get_associated_token_address_with_program_id(owner, &mint, &self.program_id)
+    }
+}
+
+/// What is read from the mint account, as cached on disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct MintData {
+    decimals: u8,
+    program_id: String,
+    #[serde(default)]
+    transfer_fee: Option<TransferFeeConfig>,
+}
+
+/// Mint metadata resolved on chain and cached, see the module docs.
+pub struct TokenRegistry {
+    rpc_client: Arc<RpcClient>,
+    cache_path: Option<String>,
+    symbols: HashMap<String, String>,
+    mints: RwLock<HashMap<String, MintData>>,
+}
+
+impl TokenRegistry {
+    /// Registry reading mints through `rpc_client`, starting from the disk cache if one is
+    /// configured. An unreadable cache is ignored; it is rebuilt as mints are resolved.
+    pub fn new(config: &TokensConfig, rpc_client: Arc<RpcClient>) -> Self {
+        let cache_path = Some(config.cache_path.clone()).filter(|p| !p.is_empty());
+        let mints = cache_path.as_deref().map(load_cache).unwrap_or_default();
+        Self {
+            rpc_client,
+            cache_path,
+            symbols: config.symbols.clone(),
+            mints: RwLock::new(mints),
+        }
+    }
+
+    /// Display symbol of `mint`: the configured override, the built-in symbol, or the
+    /// abbreviated address. Never touches the network.
+    pub fn symbol(&self, mint: &str) -> String {
+        if let Some(symbol) = self.symbols.get(mint) {
+            return symbol.clone();
+        }
+        match POPULAR_TOKEN_INFO.iter().find(|t| t.mint == mint) {
+            Some(token) => token.symbol.to_string(),
+            None if mint.len() > 8 => format!("{}..{}", &mint[..4], &mint[mint.len() - 4..]),
+            None => mint.to_string(),
+        }
+    }
+
+    /// Metadata of `mint` if it was already resolved.
+    pub fn get(&self, mint: &str) -> Option<TokenMeta> {
+        let mints = self.mints.read().unwrap();
+        let data = mints.get(mint)?;
+        Some(TokenMeta {
+            mint: mint.to_string(),
+            symbol: self.symbol(mint),
+            decimals: data.decimals,
+            program_id: Pubkey::from_str(&data.program_id).ok()?,
+            transfer_fee: data.transfer_fee,
+        })
+    }
+
+    /// Metadata of `mint`, reading the mint account on first use.
+    pub async fn resolve(&self, mint: &str) -> Result<TokenMeta, anyhow::Error> {
+        let mut resolved = self.resolve_all(&[mint.to_string()]).await?;
+        Ok(resolved.remove(0))
+    }
+
+    /// Metadata of every mint in `mints`, in order, reading the accounts not cached yet
+    /// in batches. Fails if any of them is not an initialized token mint.
+    pub async fn resolve_all(&self, mints: &[String]) -> Result<Vec<TokenMeta>, anyhow::Error> {
+        let missing: Vec<&String> = {
+            let cached = self.mints.read().unwrap();
+            mints.iter().filter(|m| !cached.contains_key(m.as_str())).collect()
+        };
+        if !missing.is_empty() {
+            let mut fetched = Vec::with_capacity(missing.len());
+            for chunk in missing.chunks(MAX_ACCOUNTS_PER_REQUEST) {
+                let keys = chunk
+                    .iter()
+                    .map(|m| Pubkey::from_str(m).map_err(|e| anyhow::anyhow!("Invalid mint {:?}: {}", m, e)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let accounts = self.rpc_client.get_multiple_accounts(&keys).await?;
+                for (mint, account) in chunk.iter().zip(accounts) {
+                    let account = account.ok_or_else(|| anyhow::anyhow!("Mint account {} not found", mint))?;
+                    fetched.push((mint.to_string(), parse_mint(mint, &account)?));
+                }
+            }
+            debug!(count = fetched.len(), "Resolved token mints");
+            self.mints.write().unwrap().extend(fetched);
+            self.save_cache();
+        }
+
+        mints
+            .iter()
+            .map(|m| self.get(m).ok_or_else(|| anyhow::anyhow!("Mint {} was not resolved", m)))
+            .collect()
+    }
+
+    /// Mint address for `token`, given either a mint address or a symbol (configured
+    /// overrides first, then the built-in list; case-insensitive).
+    pub fn find_mint(&self, token: &str) -> Option<String> {
+        if Pubkey::from_str(token).is_ok() {
+            return Some(token.to_string());
+        }
+        self.symbols
+            .iter()
+            .find(|(_, symbol)| symbol.eq_ignore_ascii_case(token))
+            .map(|(mint, _)| mint.clone())
+            .or_else(|| {
+                POPULAR_TOKEN_INFO
+                    .iter()
+                    .find(|t| t.symbol.eq_ignore_ascii_case(token))
+                    .map(|t| t.mint.to_string())
+            })
+    }
+
+    fn save_cache(&self) {
+        let Some(path) = &self.cache_path else {
+            return;
+        };
+        // String keys and plain fields always serialize
+        let json = serde_json::to_string_pretty(&*self.mints.read().unwrap()).unwrap_or_default();
+        if let Err(e) = fs::write(path, json) {
+            warn!(path = %path, error = %e, "Failed to write token cache");
+        }
+    }
+}
+
+fn load_cache(path: &str) -> HashMap<String, MintData> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return HashMap::new();
+    };
+    serde_json::from_str(&content).unwrap_or_else(|e| {
+        warn!(path = %path, error = %e, "Ignoring unreadable token cache");
+        HashMap::new()
+    })
+}
+
+/// Decimals and token program of an SPL Token or Token-2022 mint account.
+fn parse_mint(mint: &str, account: &Account) -> Result<MintData, anyhow::Error> {
+    if account.owner != TOKEN_PROGRAM_ID && account.owner != TOKEN_2022_PROGRAM_ID {
+        return Err(anyhow::anyhow!(
+            "{} is not a token mint (owned by {}, not a token program)",
+            mint,
+            account.owner
+        ));
+    }
+    let data = &account.data;
+    // Token-2022 mints with extensions are padded past the token account size and tagged
+    let is_mint_layout = data.len() == MINT_LEN
+        || (account.owner == TOKEN_2022_PROGRAM_ID
+            && data.len() > ACCOUNT_TYPE_OFFSET
+            && data[ACCOUNT_TYPE_OFFSET] == ACCOUNT_TYPE_MINT);
+    if !is_mint_layout || data[MINT_INITIALIZED_OFFSET] == 0 {
+        return Err(anyhow::anyhow!("{} is not an initialized mint account", mint));
+    }
+    Ok(MintData {
+        decimals: data[MINT_DECIMALS_OFFSET],
+        program_id: account.owner.to_string(),
+        transfer_fee: extension(data, TRANSFER_FEE_CONFIG_EXTENSION).and_then(TransferFeeConfig::parse),
+    })
+}
+
+/// Value of the Token-2022 extension `extension_type` in mint account `data`, if present.
+/// Extensions are type (u16), length (u16), value entries after the account type byte.
+fn extension(data: &[u8], extension_type: u16) -> Option<&[u8]> {
+    let mut offset = ACCOUNT_TYPE_OFFSET + 1;
+    while let Some(header) = data.get(offset..offset + 4) {
+        let kind = u16::from_le_bytes([header[0], header[1]]);
+        let len = u16::from_le_bytes([header[2], header[3]]) as usize;
+        let value = data.get(offset + 4..offset + 4 + len)?;
+        if kind == extension_type {
+            return Some(value);
+        }
+        // Type 0 marks the start of unused space
+        if kind == 0 {
+            return None;
+        }
+        offset += 4 + len;
+    }
+    None
+}
@@ -0,0 +1,90 @@
+//! Token-2022 transfer fees: the `TransferFeeConfig` mint extension and its effect on
+//! quoted swap amounts.
+
+use jupiter_swap_api_client::quote::QuoteResponse;
+use serde::{Deserialize, Serialize};
+
+use super::TokenRegistry;
+
+/// `TransferFeeConfig` extension type in the Token-2022 TLV data.
+pub(crate) const TRANSFER_FEE_CONFIG_EXTENSION: u16 = 1;
+/// Offset of `older_transfer_fee` in the extension (after two authorities and the
+/// withheld amount); `newer_transfer_fee` follows it.
+const OLDER_FEE_OFFSET: usize = 72;
+const FEE_LEN: usize = 18;
+
+/// One transfer fee schedule: a share of the amount, capped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferFee {
+    pub basis_points: u16,
+    pub maximum_fee: u64,
+}
+
+impl TransferFee {
+    /// Fee withheld from a transfer of `amount` (rounded up, as the token program does).
+    pub fn fee(&self, amount: u64) -> u64 {
+        let fee = (amount as u128 * self.basis_points as u128).div_ceil(10_000);
+        (fee as u64).min(self.maximum_fee)
+    }
+}
+
+/// Both schedules of a mint's `TransferFeeConfig`. Which one is in force depends on the
+/// current epoch, so the larger fee of the two is assumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferFeeConfig {
+    pub older: TransferFee,
+    pub newer: TransferFee,
+}
+
+impl TransferFeeConfig {
+    /// Parse the extension value (108 bytes).
+    pub(crate) fn parse(data: &[u8]) -> Option<Self> {
+        let fee = |offset: usize| {
+            let bytes = data.get(offset..offset + FEE_LEN)?;
+            // epoch: u64, maximum_fee: u64, transfer_fee_basis_points: u16
+            Some(TransferFee {
+                maximum_fee: u64::from_le_bytes(bytes[8..16].try_into().ok()?),
+                basis_points: u16::from_le_bytes(bytes[16..18].try_into().ok()?),
+            })
+        };
+        Some(Self {
+            older: fee(OLDER_FEE_OFFSET)?,
+            newer: fee(OLDER_FEE_OFFSET + FEE_LEN)?,
+        })
+    }
+
+    /// Fee withheld from a transfer of `amount`.
+    pub fn fee(&self, amount: u64) -> u64 {
+        self.older.fee(amount).max(self.newer.fee(amount))
+    }
+}
+
+/// Amount of the last leg's output mint that reaches the wallet, after transfer fees.
+///
+/// Quotes price what the pools receive and send. Every leg moves its input from the
+/// wallet to a pool and its output back, and a fee-bearing mint withholds its fee from
+/// each recipient, so a leg's output is scaled down by the share of its input lost on
+/// the way in and then loses the output mint's fee on the way out. Mints that are not
+/// resolved yet are treated as fee-free.
+pub fn amount_after_transfer_fees(tokens: &TokenRegistry, legs: &[QuoteResponse]) -> u64 {
+    let fee = |mint: &str, amount: u64| {
+        tokens
+            .get(mint)
+            .and_then(|t| t.transfer_fee)
+            .map(|f| f.fee(amount))
+            .unwrap_or(0)
+    };
+    let Some(first) = legs.first() else {
+        return 0;
+    };
+    let mut sent = first.in_amount;
+    for leg in legs {
+        if leg.in_amount == 0 {
+            return 0;
+        }
+        let received_by_pool = sent.saturating_sub(fee(&leg.input_mint.to_string(), sent));
+        let pool_out = (leg.out_amount as u128 * received_by_pool as u128 / leg.in_amount as u128) as u64;
+        sent = pool_out.saturating_sub(fee(&leg.output_mint.to_string(), pool_out));
+    }
+    sent
+}
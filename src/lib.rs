@@ -1,7 +1,9 @@
 //! Jupiter arbitrage bot (offchain) library.
 //!
-//! Finds and executes profitable Jupiter swap opportunities. Submits transactions via RPC only.
-//! Supports continuous quote polling and optional big-trades monitoring via Yellowstone gRPC.
+//! Finds and executes profitable Jupiter swap opportunities. Submits transactions via RPC, or
+//! as Jito bundles with `[jito]` enabled.
+//! Supports continuous quote polling, optional big-trades monitoring and an optional pool
+//! watcher (both via Yellowstone gRPC).
 //!
 //! Configuration is loaded from `settings.toml` (or `Config.toml` as fallback) at runtime.
 //! See PROJECT.md in the repo for layout and config reference.
@@ -10,7 +12,13 @@
 //!
 //! - **app** — Configuration and runtime settings (node, swap API, strategy, tx cost).
 //! - **chain** — Chain data and constants (program maps, token info, transaction fee).
-//! - **engine** — Arbitrage engine (Jupiter integration, discovery, execution, runtime utilities).
+//! - **engine** — Arbitrage engine (Jupiter integration, discovery, execution, SQLite ledger,
+//!   metrics, admin API, token registry, runtime utilities).
+//!
+//! Pipeline functions take an [`Engine`] (config, signer, clients, nonce state), so the
+//! library can be driven from an in-memory [`Config`] and [`Keypair`](solana_sdk::signer::keypair::Keypair)
+//! via [`Engine::new`]. The `Lazy` globals in `app::settings` are kept as a convenience
+//! layer over the process-wide [`ENGINE`].
 
 pub mod app;
 pub mod chain;
@@ -0,0 +1,203 @@
+//! Admin API: bearer auth, pausing execution, per-instrument control and the one-off
+//! quote / simulation endpoints, against the mock Jupiter and RPC servers.
+
+mod support;
+
+use std::sync::Arc;
+
+use jupiter_arbitrage_bot_offchain::*;
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use support::{nonce_engine, poll, MockJupiter, MockRpc, NonceEngine, USDC, WSOL};
+use tokio::net::TcpListener;
+
+const TOKEN: &str = "test-admin-token";
+
+struct Harness {
+    jupiter: MockJupiter,
+    rpc: MockRpc,
+    engine: Arc<Engine>,
+    base_url: String,
+    client: reqwest::Client,
+}
+
+impl Harness {
+    async fn send(&self, method: reqwest::Method, path: &str, body: Option<Value>) -> (StatusCode, Value) {
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.base_url, path))
+            .bearer_auth(TOKEN);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        let response = request.send().await.unwrap();
+        let status = response.status();
+        (status, response.json().await.unwrap_or(Value::Null))
+    }
+
+    async fn get(&self, path: &str) -> (StatusCode, Value) {
+        self.send(reqwest::Method::GET, path, None).await
+    }
+
+    async fn post(&self, path: &str, body: Option<Value>) -> (StatusCode, Value) {
+        self.send(reqwest::Method::POST, path, body).await
+    }
+
+    async fn patch(&self, path: &str, body: Value) -> (StatusCode, Value) {
+        self.send(reqwest::Method::PATCH, path, Some(body)).await
+    }
+}
+
+/// One live WSOL instrument with a 1% edge through USDC, and the admin API on an
+/// ephemeral port.
+async fn harness() -> Harness {
+    let NonceEngine { jupiter, rpc, engine, .. } =
+        nonce_engine(&format!("[admin]\nenabled = true\nauth_token = \"{TOKEN}\"")).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = admin_router(engine.clone(), TOKEN.to_string());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    Harness {
+        jupiter,
+        rpc,
+        engine,
+        base_url: format!("http://{}", addr),
+        client: reqwest::Client::new(),
+    }
+}
+
+#[tokio::test]
+async fn requests_without_the_token_are_rejected() {
+    let h = harness().await;
+
+    let response = h.client.get(format!("{}/status", h.base_url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["error"].as_str().unwrap().contains("bearer token"));
+
+    let response = h
+        .client
+        .post(format!("{}/execution/pause", h.base_url))
+        .bearer_auth("wrong")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    assert!(h.engine.execution_enabled());
+
+    // A prefix or an extension of the token is not the token
+    let extended = format!("{}x", TOKEN);
+    for token in [&TOKEN[..TOKEN.len() - 1], extended.as_str()] {
+        let response = h.client.get(format!("{}/status", h.base_url)).bearer_auth(token).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+}
+
+#[tokio::test]
+async fn pause_stops_submission_until_resumed() {
+    let h = harness().await;
+
+    let (status, body) = h.post("/execution/pause", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["execution_enabled"], false);
+    assert!(!h.engine.execution_enabled());
+
+    assert!(poll(&h.engine).await.is_empty());
+    assert!(h.rpc.sent().is_empty());
+
+    let (status, _) = h.post("/execution/resume", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(h.rpc.sent().len(), 1);
+
+    let (_, body) = h.get("/status").await;
+    assert_eq!(body["execution_enabled"], true);
+}
+
+#[tokio::test]
+async fn disabled_instrument_is_not_quoted() {
+    let h = harness().await;
+
+    let (status, body) = h.post(&format!("/instruments/{}/disable", WSOL), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["enabled"], false);
+    assert!(h.engine.base_tokens().is_empty());
+
+    assert!(poll(&h.engine).await.is_empty());
+    assert_eq!(h.jupiter.quote_requests(), 0);
+
+    h.post(&format!("/instruments/{}/enable", WSOL), None).await;
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert!(h.jupiter.quote_requests() > 0);
+}
+
+#[tokio::test]
+async fn instrument_settings_are_validated_and_applied() {
+    let h = harness().await;
+    let path = format!("/instruments/{}", WSOL);
+
+    let (status, body) = h
+        .patch(&path, json!({ "min_profit_quote_units": 0.5, "notional_range": [0.5, 3.0] }))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["min_profit_quote_units"], 0.5);
+    assert_eq!(body["notional_range"], json!([0.5, 3.0]));
+    let instrument = &h.engine.base_tokens()[0];
+    assert_eq!(instrument.min_profit, 0.5);
+    assert_eq!(instrument.amount_range, [0.5, 3.0]);
+
+    // A 1% edge on at most 3 SOL is below the new 0.5 SOL minimum
+    assert!(poll(&h.engine).await.is_empty());
+
+    let (status, body) = h.patch(&path, json!({ "notional_range": [3.0, 1.0] })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("notional_range"));
+    assert_eq!(h.engine.base_tokens()[0].amount_range, [0.5, 3.0]);
+
+    let (status, _) = h.patch(&path, json!({ "min_profit_quote_units": -1.0 })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = h
+        .patch(&format!("/instruments/{}", USDC), json!({ "min_profit_quote_units": 1.0 }))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn one_off_quote_and_simulation() {
+    let h = harness().await;
+
+    let (status, body) = h
+        .post("/quote", Some(json!({ "input_mint": WSOL, "output_mint": USDC, "amount": 1_000_000_000u64 })))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["outAmount"], "150000000");
+
+    let (status, body) = h
+        .post("/quote", Some(json!({ "input_mint": "not-a-mint", "output_mint": USDC, "amount": 1 })))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("input_mint"));
+
+    let (status, body) = h
+        .post("/simulate", Some(json!({ "mint": WSOL, "target": USDC, "amount": 1_000_000_000u64 })))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["route"], "WSOL -> USDC -> WSOL");
+    assert!(body["quoted_profit"].as_i64().unwrap() > 0);
+    assert_eq!(body["simulation"]["succeeded"], true);
+    assert_eq!(body["simulation"]["units_consumed"], 120000);
+    // Simulated, never sent
+    assert!(h.rpc.sent().is_empty());
+}
+
+#[tokio::test]
+async fn ledger_lists_need_the_ledger() {
+    let h = harness().await;
+
+    let (status, body) = h.get("/opportunities?limit=5").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert!(body["error"].as_str().unwrap().contains("[ledger]"));
+}
@@ -0,0 +1,154 @@
+//! `transaction_lifetime = "blockhash"`: transactions are signed with a recent blockhash
+//! instead of a durable nonce and expire at its last valid block height.
+
+mod support;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::compute_budget;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::Keypair;
+use support::{poll, MockJupiter, MockRpc, USDC, WSOL};
+
+/// Last valid block height served by the mock `getLatestBlockhash`.
+const LAST_VALID_BLOCK_HEIGHT: u64 = 150;
+
+/// Blockhash served by the mock `getLatestBlockhash`.
+fn mock_blockhash() -> Hash {
+    Hash::new_from_array([7; 32])
+}
+
+struct Harness {
+    rpc: MockRpc,
+    engine: Arc<Engine>,
+    ledger_path: PathBuf,
+    _jupiter: MockJupiter,
+}
+
+async fn harness() -> Harness {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(WSOL, USDC, 0.15);
+    jupiter.set_rate(USDC, WSOL, 1.01 / 0.15);
+    let rpc = MockRpc::start().await;
+    let ledger_path = std::env::temp_dir().join(format!("landings-{}.jsonl", Pubkey::new_unique()));
+
+    let config = support::config(
+        &rpc.url,
+        &jupiter.url,
+        &format!(
+            r#"
+[strategy]
+execution_enabled = true
+transaction_lifetime = "blockhash"
+
+[landing]
+ledger_path = "{}"
+poll_interval_ms = 10
+timeout_secs = 1
+"#,
+            ledger_path.display()
+        ),
+    );
+    config.check().unwrap();
+    let engine = Arc::new(Engine::new(config, Keypair::new()).unwrap());
+    Harness {
+        rpc,
+        engine,
+        ledger_path,
+        _jupiter: jupiter,
+    }
+}
+
+/// Track the last sent transaction to the end and return its landing record.
+async fn track_last(h: &Harness) -> serde_json::Value {
+    let legs = vec![
+        serde_json::from_value(support::quote_json(WSOL, USDC, 1_000_000_000, 150_000_000)).unwrap(),
+        serde_json::from_value(support::quote_json(USDC, WSOL, 150_000_000, 1_010_000_000)).unwrap(),
+    ];
+    let signature = h.rpc.sent().last().unwrap().signatures[0];
+    let lifetime = h.engine.acquire_lifetime().unwrap();
+    let trade = PendingTrade::from_legs("polling", &legs).sent(signature, &lifetime);
+    assert_eq!(trade.last_valid_block_height, Some(LAST_VALID_BLOCK_HEIGHT));
+    assert_eq!(trade.nonce_account, None);
+
+    track_landing(h.engine.clone(), trade).await;
+    let ledger = std::fs::read_to_string(&h.ledger_path).unwrap();
+    serde_json::from_str(ledger.lines().last().unwrap()).unwrap()
+}
+
+#[tokio::test]
+async fn nothing_is_signed_before_the_first_blockhash() {
+    let h = harness().await;
+    assert!(h.engine.acquire_lifetime().is_none());
+    assert!(poll(&h.engine).await.is_empty());
+    assert!(h.rpc.sent().is_empty());
+}
+
+#[tokio::test]
+async fn a_stale_blockhash_is_not_signed_with() {
+    let h = harness().await;
+    h.engine
+        .update_config(|config| {
+            config.strategy.blockhash_refresh_ms = 10;
+            config.strategy.blockhash_max_age_ms = 50;
+            Ok(())
+        })
+        .unwrap();
+    refresh_blockhash(&h.engine).await.unwrap();
+    assert!(h.engine.acquire_lifetime().is_some());
+
+    // The refresher stopped: nothing is signed once the blockhash is older than the bound
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(h.engine.acquire_lifetime().is_none());
+    assert!(poll(&h.engine).await.is_empty());
+    assert!(h.rpc.sent().is_empty());
+    assert_eq!(h.engine.metrics.submissions.with_label_values(&["polling", "no_nonce"]).get(), 1);
+}
+
+#[tokio::test]
+async fn transactions_use_the_recent_blockhash_without_advancing_a_nonce() {
+    let h = harness().await;
+    let latest = refresh_blockhash(&h.engine).await.unwrap();
+    assert_eq!(latest.blockhash, mock_blockhash());
+    assert_eq!(latest.last_valid_block_height, LAST_VALID_BLOCK_HEIGHT);
+
+    // Nothing is leased: every poll can submit
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(poll(&h.engine).await.len(), 1);
+
+    for tx in h.rpc.sent() {
+        assert_eq!(*tx.message.recent_blockhash(), mock_blockhash());
+        let keys = tx.message.static_account_keys();
+        let first = &tx.message.instructions()[0];
+        assert_eq!(keys[first.program_id_index as usize], compute_budget::id());
+    }
+    assert_eq!(h.engine.nonces.len(), 0);
+}
+
+#[tokio::test]
+async fn trades_expire_once_past_the_last_valid_block_height() {
+    let h = harness().await;
+    refresh_blockhash(&h.engine).await.unwrap();
+    assert_eq!(poll(&h.engine).await.len(), 1);
+
+    h.rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT + 1);
+    let record = track_last(&h).await;
+    assert_eq!(record["status"], "expired");
+    assert_eq!(record["error"], "blockhash expired without this signature landing");
+    assert!(h.rpc.calls("getBlockHeight") > 0);
+}
+
+#[tokio::test]
+async fn trades_within_the_block_height_wait_for_the_timeout() {
+    let h = harness().await;
+    refresh_blockhash(&h.engine).await.unwrap();
+    assert_eq!(poll(&h.engine).await.len(), 1);
+
+    h.rpc.set_block_height(LAST_VALID_BLOCK_HEIGHT);
+    let record = track_last(&h).await;
+    assert_eq!(record["status"], "expired");
+    assert_eq!(record["error"], "not seen within 1s");
+}
@@ -0,0 +1,137 @@
+//! Compute unit right-sizing: the limit is the simulated `unitsConsumed` plus a margin,
+//! learned per route so later transactions on it skip the simulation.
+
+mod support;
+
+use std::time::Duration;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::compute_budget;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use support::{nonce_engine, poll, quote_json, NonceEngine, USDC, WSOL};
+
+/// `[fees] compute_unit_limit` in these tests.
+const MAX_UNITS: u32 = 400_000;
+/// The mock RPC simulates every transaction at 120_000 units; plus the 10% default margin.
+const RIGHT_SIZED: u32 = 132_000;
+
+async fn harness(compute_units: &str) -> NonceEngine {
+    nonce_engine(&format!("[fees]\ncompute_unit_limit = {MAX_UNITS}\n\n[compute_units]\n{compute_units}")).await
+}
+
+/// Compute unit limit set by the `SetComputeUnitLimit` instruction of `tx`.
+fn compute_unit_limit(tx: &VersionedTransaction) -> u32 {
+    let keys = tx.message.static_account_keys();
+    let ix = tx
+        .message
+        .instructions()
+        .iter()
+        .find(|ix| keys[ix.program_id_index as usize] == compute_budget::id() && ix.data[0] == 2)
+        .expect("compute unit limit instruction");
+    u32::from_le_bytes(ix.data[1..5].try_into().unwrap())
+}
+
+/// Build a transaction with one dummy instruction at `limit`.
+fn build(engine: &Engine, limit: u32) -> Result<VersionedTransaction, anyhow::Error> {
+    let ix = Instruction::new_with_bytes(
+        Pubkey::new_unique(),
+        &[1],
+        vec![AccountMeta::new(engine.pubkey, true)],
+    );
+    let lifetime = engine.acquire_lifetime().expect("nonce loaded");
+    build_transaction(engine, &lifetime, vec![ix], &[], limit, 1000)
+}
+
+#[test]
+fn limit_is_consumed_units_plus_margin_within_the_cap() {
+    assert_eq!(limit_with_margin(120_000, 1000, MAX_UNITS), RIGHT_SIZED);
+    // Rounded up, never zero, never above the [fees] limit
+    assert_eq!(limit_with_margin(1, 1, MAX_UNITS), 2);
+    assert_eq!(limit_with_margin(0, 1000, MAX_UNITS), 1);
+    assert_eq!(limit_with_margin(390_000, 1000, MAX_UNITS), MAX_UNITS);
+}
+
+#[test]
+fn flash_loan_trades_are_their_own_route() {
+    let quote = serde_json::from_value(quote_json(WSOL, USDC, 1_000_000_000, 150_000_000)).unwrap();
+    let back = serde_json::from_value(quote_json(USDC, WSOL, 150_000_000, 1_010_000_000)).unwrap();
+    let legs = vec![quote, back];
+    let regular = route_key(&legs, false);
+    assert_eq!(regular.split('>').count(), 2);
+    assert_ne!(route_key(&legs, true), regular);
+}
+
+#[tokio::test]
+async fn disabled_requests_the_fees_limit_without_simulating() {
+    let h = harness("enabled = false").await;
+    let tx = right_sized_transaction(&h.engine, "a>b", |limit| build(&h.engine, limit)).await.unwrap();
+    assert_eq!(compute_unit_limit(&tx), MAX_UNITS);
+    assert_eq!(h.rpc.calls("simulateTransaction"), 0);
+}
+
+#[tokio::test]
+async fn learned_limit_is_reused_per_route() {
+    let h = harness("enabled = true").await;
+
+    let tx = right_sized_transaction(&h.engine, "a>b", |limit| build(&h.engine, limit)).await.unwrap();
+    assert_eq!(compute_unit_limit(&tx), RIGHT_SIZED);
+    assert_eq!(h.rpc.calls("simulateTransaction"), 1);
+    assert_eq!(h.engine.compute_units.get("a>b", Duration::from_secs(60)), Some(RIGHT_SIZED));
+
+    // Same route: no simulation on the hot path
+    let tx = right_sized_transaction(&h.engine, "a>b", |limit| build(&h.engine, limit)).await.unwrap();
+    assert_eq!(compute_unit_limit(&tx), RIGHT_SIZED);
+    assert_eq!(h.rpc.calls("simulateTransaction"), 1);
+
+    // A new route is measured once
+    right_sized_transaction(&h.engine, "b>a", |limit| build(&h.engine, limit)).await.unwrap();
+    assert_eq!(h.rpc.calls("simulateTransaction"), 2);
+    assert_eq!(h.engine.compute_units.len(), 2);
+}
+
+#[tokio::test]
+async fn expired_limits_are_measured_again() {
+    let h = harness("enabled = true\nmargin_bps = 2500\ncache_ttl_secs = 0").await;
+    for _ in 0..2 {
+        let tx = right_sized_transaction(&h.engine, "a>b", |limit| build(&h.engine, limit)).await.unwrap();
+        assert_eq!(compute_unit_limit(&tx), 150_000);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    assert_eq!(h.rpc.calls("simulateTransaction"), 2);
+}
+
+#[tokio::test]
+async fn submitted_trades_request_the_simulated_units() {
+    let h = harness("enabled = true").await;
+
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    let sent = h.rpc.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(compute_unit_limit(&sent[0]), RIGHT_SIZED);
+    assert_eq!(h.rpc.calls("simulateTransaction"), 1);
+    assert_eq!(h.engine.compute_units.len(), 1);
+}
+
+#[tokio::test]
+async fn the_simulation_gate_measures_the_limit_without_a_probe() {
+    let h = harness("enabled = true\n\n[simulation]\nenabled = true").await;
+    let build_at = |limit| build(&h.engine, limit);
+
+    let tx = right_sized_transaction(&h.engine, "a>b", build_at).await.unwrap();
+    assert_eq!(compute_unit_limit(&tx), MAX_UNITS);
+    assert_eq!(h.rpc.calls("simulateTransaction"), 0);
+
+    // The gate simulated it at 120_000 units
+    let tx = right_size_simulated(&h.engine, "a>b", tx, Some(120_000), build_at);
+    assert_eq!(compute_unit_limit(&tx), RIGHT_SIZED);
+    assert_eq!(h.engine.compute_units.get("a>b", Duration::from_secs(60)), Some(RIGHT_SIZED));
+
+    // Learned: built at it from now on, and not resized by later simulations
+    let tx = right_sized_transaction(&h.engine, "a>b", build_at).await.unwrap();
+    assert_eq!(compute_unit_limit(&tx), RIGHT_SIZED);
+    let tx = right_size_simulated(&h.engine, "a>b", tx, Some(200_000), build_at);
+    assert_eq!(compute_unit_limit(&tx), RIGHT_SIZED);
+    assert_eq!(h.rpc.calls("simulateTransaction"), 0);
+}
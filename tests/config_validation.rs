@@ -0,0 +1,155 @@
+//! `Config::validate`: every bad value is reported with its TOML path, and a config
+//! that fails validation is never hot-reloaded.
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::signer::keypair::Keypair;
+
+const VALID: &str = r#"
+[connection]
+signer_keypair_path = "unused.json"
+rpc_endpoint = "http://127.0.0.1:1"
+submit_endpoint = "http://127.0.0.1:1"
+
+[dex_api]
+endpoint = "http://127.0.0.1:1"
+
+[strategy]
+nonce_account_pubkey = "Dxu8dEngsxTmttn66ngLaHJh4sSdw8oMWJzn4SmKoCLn"
+geyser_watch_enabled = false
+
+[[strategy.instruments]]
+token_mint = "So11111111111111111111111111111111111111112"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[fees]
+compute_unit_limit = 400000
+priority_fee_lamports = 1000
+relay_tip_sol = 0.0
+"#;
+
+/// Every problem in `config`, as "path: message" strings.
+fn problems(config: &str) -> Vec<String> {
+    Config::from_toml_str(config)
+        .unwrap()
+        .validate()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+fn paths(config: &str) -> Vec<String> {
+    Config::from_toml_str(config)
+        .unwrap()
+        .validate()
+        .into_iter()
+        .map(|p| p.path)
+        .collect()
+}
+
+#[test]
+fn valid_config_has_no_problems() {
+    assert!(problems(VALID).is_empty(), "{:?}", problems(VALID));
+    Config::from_toml_str(VALID).unwrap().check().unwrap();
+}
+
+#[test]
+fn shipped_settings_file_is_valid() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/settings.toml");
+    let config = Config::load(path).unwrap();
+    assert!(config.validate().is_empty(), "{:?}", config.validate());
+}
+
+#[test]
+fn every_problem_is_reported_with_its_path() {
+    let config = VALID
+        .replace("Dxu8dEngsxTmttn66ngLaHJh4sSdw8oMWJzn4SmKoCLn", "not-a-pubkey")
+        .replace("notional_range = [1.0, 2.0]", "notional_range = [2.0, 1.0]")
+        .replace("grid_steps = 2", "grid_steps = 0")
+        .replace("geyser_watch_enabled = false", "geyser_watch_enabled = true")
+        + r#"
+[[strategy.instruments]]
+token_mint = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[flash_loan]
+enabled = true
+
+[[flash_loan.reserves]]
+token_mint = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB"
+flashloan_token_reserves_liquidity = "4Y66HtUEqbbbpZdENGtFdVhUMS3tnagffn3M4do59Nfy"
+flashloan_borrow_position_on_liquidity = "BZZKgXxhxVkzx3NN8RfBPwU7ZmnQbDtp3ezcsXbiALL6"
+rate_model = "Acvyi9HBGmqh3Exe1N4PjBVyY8fokq2AdC6fSLqV6KSo"
+vault = "bad"
+liquidity = "7s1da8DduuBFqGra5bJBjpnvL5E9mGzCuMk1Qkh4or2Z"
+liquidity_program = "jupeiUmn818Jg1ekPURTpr4mFo29p46vygyykFJ3wZC"
+
+[tokens.symbols]
+"9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin" = "SRM"
+"not-a-mint" = "BAD"
+"#;
+
+    assert_eq!(
+        paths(&config),
+        vec![
+            "connection.geyser_endpoint",
+            "connection.geyser_auth_token",
+            "strategy.nonce_account_pubkey",
+            "strategy.instruments[0].notional_range",
+            "strategy.instruments[0].grid_steps",
+            "flash_loan.reserves[0].vault",
+            "flash_loan.reserves[0].token_mint",
+            "tokens.symbols.\"not-a-mint\"",
+        ]
+    );
+
+    let all = problems(&config);
+    assert!(all[0].contains("geyser_watch_enabled"));
+    assert!(all[2].contains("\"not-a-pubkey\" is not a valid public key"));
+    assert!(all[3].contains("0 < min <= max, got [2, 1]"));
+    assert!(all[6].contains("is not an instrument"));
+    assert!(all[7].contains("\"not-a-mint\" is not a valid public key"));
+
+    let err = Config::from_toml_str(&config).unwrap().check().unwrap_err().to_string();
+    assert!(err.starts_with("Invalid config (8 problems)"), "{}", err);
+    assert!(err.contains("flash_loan.reserves[0].vault"));
+}
+
+#[test]
+fn enabled_services_need_their_settings() {
+    let config = format!(
+        "{}\n[admin]\nenabled = true\nlisten = \"localhost\"\n\n[sizing]\ntolerance = 0.0\n",
+        VALID
+    );
+    assert_eq!(paths(&config), vec!["sizing.tolerance", "admin.listen", "admin.auth_token"]);
+}
+
+#[test]
+fn blockhash_lifetime_needs_no_nonce_account() {
+    let nonce_account = "nonce_account_pubkey = \"Dxu8dEngsxTmttn66ngLaHJh4sSdw8oMWJzn4SmKoCLn\"";
+    let without_nonce = VALID.replace(nonce_account, "");
+    assert_eq!(paths(&without_nonce), vec!["strategy.nonce_account_pubkey"]);
+
+    let blockhash = VALID.replace(nonce_account, "transaction_lifetime = \"blockhash\"");
+    assert!(problems(&blockhash).is_empty(), "{:?}", problems(&blockhash));
+    let engine = Engine::new(Config::from_toml_str(&blockhash).unwrap(), Keypair::new()).unwrap();
+    assert!(engine.nonces.is_empty());
+
+    let too_fast = blockhash.replace("geyser_watch_enabled = false", "geyser_watch_enabled = false\nblockhash_refresh_ms = 0");
+    assert_eq!(paths(&too_fast), vec!["strategy.blockhash_refresh_ms"]);
+}
+
+#[test]
+fn invalid_config_is_not_reloaded() {
+    let engine = Engine::new(Config::from_toml_str(VALID).unwrap(), Keypair::new()).unwrap();
+
+    let next = VALID.replace("grid_steps = 2", "grid_steps = 0");
+    let err = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap_err();
+    assert!(err.to_string().contains("strategy.instruments[0].grid_steps"), "{}", err);
+    assert_eq!(engine.base_tokens()[0].steps, 2);
+}
@@ -0,0 +1,174 @@
+//! Transaction cost model: lamport breakdown, conversion to the base token and the
+//! flash-loan extras.
+
+mod support;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use support::{flash_loan_section, quote_json, FakeAccount, MockJupiter, MockRpc, TOKEN_PROGRAM, USDC, USDT, WSOL};
+
+/// 5000 base fee + 400_000 CU × 1000 micro-lamports.
+const TX_LAMPORTS: u64 = 5_400;
+
+fn engine(jupiter: &MockJupiter, rpc: &MockRpc, keypair: Keypair, extra: &str) -> Engine {
+    let config = support::config(
+        &rpc.url,
+        &jupiter.url,
+        &format!(
+            r#"
+[strategy]
+nonce_account_pubkey = "{nonce}"
+
+[[strategy.instruments]]
+token_mint = "{WSOL}"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[[strategy.instruments]]
+token_mint = "{USDC}"
+min_delta_threshold = 100.0
+min_profit_quote_units = 0.01
+notional_range = [100.0, 200.0]
+grid_steps = 2
+
+{extra}
+"#,
+            nonce = Pubkey::new_unique(),
+        ),
+    );
+    Engine::new(config, keypair).unwrap()
+}
+
+#[test]
+fn lamport_cost_covers_fees_tip_and_rent() {
+    let plan = TxPlan {
+        signatures: 1,
+        compute_unit_limit: 200_000,
+        compute_unit_price: 12_345,
+        tip_lamports: 1_000_000,
+        created_token_accounts: 1,
+        flash_loan_fee_bps: 0,
+    };
+    let lamports = plan.lamports();
+    assert_eq!(lamports.base_fee, 5_000);
+    // 200_000 × 12_345 / 1e6 = 2469, no remainder
+    assert_eq!(lamports.priority_fee, 2_469);
+    assert_eq!(lamports.rent, TOKEN_ACCOUNT_RENT_LAMPORTS);
+    assert_eq!(lamports.total(), 5_000 + 2_469 + 1_000_000 + TOKEN_ACCOUNT_RENT_LAMPORTS);
+
+    // Priority fees round up to whole lamports
+    let plan = TxPlan { compute_unit_limit: 1, compute_unit_price: 1, ..plan };
+    assert_eq!(plan.lamports().priority_fee, 1);
+}
+
+#[tokio::test]
+async fn sol_base_pays_lamports_directly() {
+    let (jupiter, rpc) = (MockJupiter::start().await, MockRpc::start().await);
+    let engine = engine(&jupiter, &rpc, Keypair::new(), "");
+
+    let cost = trade_cost(&engine, WSOL, 9).await.unwrap();
+    assert_eq!(cost.lamports.total(), TX_LAMPORTS);
+    assert_eq!(cost.for_amount(1_000_000_000), TX_LAMPORTS);
+    assert_eq!(jupiter.quote_requests(), 0);
+}
+
+#[tokio::test]
+async fn other_bases_convert_at_the_quoted_sol_price() {
+    let (jupiter, rpc) = (MockJupiter::start().await, MockRpc::start().await);
+    jupiter.set_rate(WSOL, USDC, 0.2); // 200 USDC per SOL, not the configured 150
+    let engine = engine(&jupiter, &rpc, Keypair::new(), "");
+
+    let cost = trade_cost(&engine, USDC, 6).await.unwrap();
+    // 5400 lamports × 200e6 micro-USDC / 1e9 lamports
+    assert_eq!(cost.fixed, 1_080);
+    assert_eq!(jupiter.quote_requests(), 1);
+
+    // Cached for price_ttl_secs
+    trade_cost(&engine, USDC, 6).await.unwrap();
+    assert_eq!(jupiter.quote_requests(), 1);
+}
+
+#[tokio::test]
+async fn quotes_through_wsol_price_sol_without_another_request() {
+    let (jupiter, rpc) = (MockJupiter::start().await, MockRpc::start().await);
+    let engine = engine(&jupiter, &rpc, Keypair::new(), "");
+
+    // 300 USDC in for 2 SOL out: 150 USDC per SOL
+    let quote = serde_json::from_value(quote_json(USDC, WSOL, 300_000_000, 2_000_000_000)).unwrap();
+    engine.costs.observe(&quote);
+
+    let cost = trade_cost(&engine, USDC, 6).await.unwrap();
+    assert_eq!(cost.fixed, 810);
+    assert_eq!(jupiter.quote_requests(), 0);
+}
+
+#[tokio::test]
+async fn without_a_route_only_usd_stablecoins_fall_back_to_the_sol_usd_price() {
+    let (jupiter, rpc) = (MockJupiter::start().await, MockRpc::start().await);
+    let engine = engine(&jupiter, &rpc, Keypair::new(), "");
+
+    // sol_price_usd = 150 (no live price fetched in tests)
+    assert_eq!(sol_price_in(&engine, USDT, 6).await.unwrap(), 150_000_000.0);
+
+    let unknown = Pubkey::new_unique().to_string();
+    let err = trade_cost(&engine, &unknown, 6).await.unwrap_err();
+    assert!(err.to_string().contains("No SOL price"), "{}", err);
+}
+
+#[tokio::test]
+async fn flash_loan_trades_pay_the_fee_and_rent_until_the_account_exists() {
+    let (jupiter, rpc) = (MockJupiter::start().await, MockRpc::start().await);
+    let keypair = Keypair::new();
+    let owner = keypair.pubkey();
+    let engine = engine(&jupiter, &rpc, keypair, &flash_loan_section(WSOL, 9));
+
+    let cost = trade_cost(&engine, WSOL, 9).await.unwrap();
+    assert_eq!(cost.plan.created_token_accounts, 1);
+    assert_eq!(cost.fixed, TX_LAMPORTS + TOKEN_ACCOUNT_RENT_LAMPORTS);
+    // 9 bps of the borrowed 1 SOL
+    assert_eq!(cost.for_amount(1_000_000_000), cost.fixed + 900_000);
+
+    let ata = engine.tokens.resolve(WSOL).await.unwrap().associated_token_address(&owner);
+    rpc.set_account(
+        ata,
+        FakeAccount {
+            owner: TOKEN_PROGRAM.parse().unwrap(),
+            lamports: TOKEN_ACCOUNT_RENT_LAMPORTS,
+            data: vec![0; 165],
+        },
+    );
+    let cost = trade_cost(&engine, WSOL, 9).await.unwrap();
+    assert_eq!(cost.plan.created_token_accounts, 0);
+    assert_eq!(cost.fixed, TX_LAMPORTS);
+
+    // Instruments without a reserve pay neither rent nor the fee
+    jupiter.set_rate(WSOL, USDC, 0.15);
+    let usdc = trade_cost(&engine, USDC, 6).await.unwrap();
+    assert_eq!(usdc.plan, TxPlan { created_token_accounts: 0, flash_loan_fee_bps: 0, ..cost.plan });
+}
+
+#[tokio::test]
+async fn only_tips_the_transaction_carries_are_counted() {
+    let (jupiter, rpc) = (MockJupiter::start().await, MockRpc::start().await);
+    let relay_tip = "[fees]\nrelay_tip_sol = 0.001";
+
+    // Regular trades sent to the submit endpoints pay the relay tip
+    let regular = engine(&jupiter, &rpc, Keypair::new(), relay_tip);
+    let cost = trade_cost(&regular, WSOL, 9).await.unwrap();
+    assert_eq!(cost.lamports.tip, 1_000_000);
+
+    // Flash-loan trades carry no tip instruction
+    let flash_loan = engine(&jupiter, &rpc, Keypair::new(), &format!("{relay_tip}\n{}", flash_loan_section(WSOL, 9)));
+    let cost = trade_cost(&flash_loan, WSOL, 9).await.unwrap();
+    assert_eq!(cost.lamports.tip, 0);
+
+    // Bundles pay the Jito tip instead, at least min_tip_lamports
+    let flash_loan_toml = flash_loan_section(WSOL, 9);
+    let jito = format!("{relay_tip}\n{flash_loan_toml}\n[jito]\nenabled = true\nmin_tip_lamports = 20000");
+    let bundled = engine(&jupiter, &rpc, Keypair::new(), &jito);
+    let cost = trade_cost(&bundled, WSOL, 9).await.unwrap();
+    assert_eq!(cost.lamports.tip, 20_000);
+}
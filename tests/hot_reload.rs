@@ -0,0 +1,183 @@
+//! Config hot reload: `[strategy]`, `[fees]` and `[flash_loan]` are swapped in together,
+//! other sections wait for a restart and connection changes are rejected.
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::Keypair;
+
+use support::{flash_loan_section, USDC, WSOL};
+
+const NONCE: &str = "Dxu8dEngsxTmttn66ngLaHJh4sSdw8oMWJzn4SmKoCLn";
+
+/// Config text against `rpc` with the nonce account set; `extra` continues the
+/// `[strategy]` table and may open more sections.
+fn settings(rpc: &str, priority_fee: u64, extra: &str) -> String {
+    support::config_toml(
+        rpc,
+        "http://127.0.0.1:1",
+        &format!(
+            "[connection]\nsubmit_endpoint = \"http://127.0.0.1:1\"\n\n[fees]\npriority_fee_lamports = {priority_fee}\n\n[strategy]\nnonce_account_pubkey = \"{NONCE}\"\n{extra}"
+        ),
+    )
+}
+
+/// The WSOL instrument plus a USDC one; instrument arrays replace the base config's whole.
+const WSOL_AND_USDC: &str = r#"
+[[strategy.instruments]]
+token_mint = "So11111111111111111111111111111111111111112"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[[strategy.instruments]]
+token_mint = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.1
+notional_range = [100.0, 200.0]
+grid_steps = 2
+"#;
+
+fn engine(text: &str) -> Engine {
+    Engine::new(Config::from_toml_str(text).unwrap(), Keypair::new()).unwrap()
+}
+
+#[test]
+fn strategy_and_fees_swap_together_and_notify() {
+    let engine = engine(&settings("http://127.0.0.1:1", 1000, ""));
+    let mut updates = engine.subscribe_config();
+
+    let next = settings("http://127.0.0.1:1", 5000, WSOL_AND_USDC);
+    let reload = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+
+    assert_eq!(reload.applied, vec!["strategy", "fees"]);
+    assert!(reload.needs_restart.is_empty());
+    let mints: Vec<String> = engine.base_tokens().into_iter().map(|t| t.mint).collect();
+    assert_eq!(mints, vec![WSOL.to_string(), USDC.to_string()]);
+    assert_eq!(engine.fees().priority_lamports, 5000);
+
+    assert!(updates.has_changed().unwrap());
+    assert_eq!(updates.borrow_and_update().strategy.base_tokens.len(), 2);
+
+    // Same file again: nothing to do, nobody woken
+    let reload = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+    assert_eq!(reload, ConfigReload::default());
+    assert!(!updates.has_changed().unwrap());
+}
+
+#[test]
+fn connection_and_nonce_changes_are_rejected() {
+    let engine = engine(&settings("http://127.0.0.1:1", 1000, ""));
+
+    let next = settings("http://127.0.0.1:2", 5000, WSOL_AND_USDC);
+    let err = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap_err();
+    assert!(err.to_string().contains("connection.rpc_endpoint"), "{}", err);
+
+    let next = settings("http://127.0.0.1:1", 1000, "").replace(NONCE, &Pubkey::new_unique().to_string());
+    let err = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap_err();
+    assert!(err.to_string().contains("strategy.nonce_account_pubkey"), "{}", err);
+
+    // Nothing from the rejected files was applied
+    assert_eq!(engine.base_tokens().len(), 1);
+    assert_eq!(engine.fees().priority_lamports, 1000);
+}
+
+#[test]
+fn other_sections_need_a_restart() {
+    let engine = engine(&settings("http://127.0.0.1:1", 1000, ""));
+
+    let next = settings("http://127.0.0.1:1", 1000, "[sizing]\nmax_quotes = 7\n");
+    let reload = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+
+    assert!(reload.applied.is_empty());
+    assert_eq!(reload.needs_restart, vec!["sizing"]);
+    assert_eq!(engine.config().sizing, SizingConfig::default());
+}
+
+#[test]
+fn flash_loan_contexts_are_rebuilt() {
+    let engine = engine(&settings("http://127.0.0.1:1", 1000, ""));
+    assert!(engine.flash_loan_context(WSOL).is_none());
+
+    let next = settings("http://127.0.0.1:1", 1000, &flash_loan_section(WSOL, 0));
+    let reload = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+
+    assert_eq!(reload.applied, vec!["flash_loan"]);
+    assert!(engine.flash_loan_context(WSOL).is_some());
+    assert_eq!(engine.flash_loan_mints(), Some(vec![WSOL.to_string()]));
+}
+
+#[tokio::test]
+async fn watcher_applies_file_changes() {
+    let path = std::env::temp_dir().join(format!("settings-{}.toml", Pubkey::new_unique()));
+    let extra = "[hot_reload]\ncheck_interval_ms = 100\n";
+    std::fs::write(&path, settings("http://127.0.0.1:1", 1000, extra)).unwrap();
+    let engine = Arc::new(Engine::new(Config::load(&path).unwrap(), Keypair::new()).unwrap());
+    let watcher = tokio::spawn(watch_config_file(engine.clone(), path.clone()));
+    tokio::time::sleep(Duration::from_millis(150)).await;
+
+    // A broken file is skipped
+    std::fs::write(&path, "[strategy").unwrap();
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    assert_eq!(engine.base_tokens().len(), 1);
+
+    let next = settings("http://127.0.0.1:1", 1000, &format!("{}{}", WSOL_AND_USDC, extra));
+    std::fs::write(&path, next).unwrap();
+    for _ in 0..50 {
+        if engine.base_tokens().len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+
+    watcher.abort();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(engine.base_tokens().len(), 2);
+}
+
+#[tokio::test]
+async fn admin_overrides_survive_reloads() {
+    const TOKEN: &str = "test-admin-token";
+    let engine = Arc::new(engine(&settings("http://127.0.0.1:1", 1000, "execution_enabled = true\n")));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let router = admin_router(engine.clone(), TOKEN.to_string());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let client = reqwest::Client::new();
+    let pause = client.post(format!("{}/execution/pause", base_url)).bearer_auth(TOKEN).send().await.unwrap();
+    assert!(pause.status().is_success());
+    let patch = client
+        .patch(format!("{}/instruments/{}", base_url, WSOL))
+        .bearer_auth(TOKEN)
+        .json(&serde_json::json!({ "min_profit_quote_units": 0.5 }))
+        .send()
+        .await
+        .unwrap();
+    assert!(patch.status().is_success());
+    assert!(!engine.execution_enabled());
+
+    // An unrelated edit reloads [strategy] and [fees] but keeps the admin changes
+    let next = settings("http://127.0.0.1:1", 5000, "execution_enabled = true\n");
+    let reload = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+    assert_eq!(reload.applied, vec!["fees"]);
+    let instruments = format!("execution_enabled = true\n{}", WSOL_AND_USDC);
+    let next = settings("http://127.0.0.1:1", 5000, &instruments);
+    let reload = engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+    assert_eq!(reload.applied, vec!["strategy"]);
+    assert!(!engine.execution_enabled());
+    assert_eq!(engine.base_tokens()[0].min_profit, 0.5);
+    assert_eq!(engine.base_tokens().len(), 2);
+
+    // Editing the overridden setting in the file takes the file's value
+    let instruments = instruments.replace("min_profit_quote_units = 0.001", "min_profit_quote_units = 0.002");
+    let next = settings("http://127.0.0.1:1", 5000, &instruments);
+    engine.reload_config(Config::from_toml_str(&next).unwrap()).unwrap();
+    assert_eq!(engine.base_tokens()[0].min_profit, 0.002);
+    assert!(!engine.execution_enabled());
+}
@@ -0,0 +1,208 @@
+//! `[jito]`: trades are sent to the block engine as bundles, tipping a rotating tip
+//! account a share of the expected profit, and each bundle is polled until it settles.
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::Keypair;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use support::{poll, MockBlockEngine, MockJupiter, MockRpc, USDC, WSOL};
+
+const TIP_ACCOUNTS: [&str; 2] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+];
+
+struct Harness {
+    rpc: MockRpc,
+    block_engine: MockBlockEngine,
+    engine: Arc<Engine>,
+    _jupiter: MockJupiter,
+}
+
+fn config(rpc: &str, jupiter: &str, jito: &str) -> Config {
+    support::config(
+        rpc,
+        jupiter,
+        &format!(
+            "[dex_api]\njito_api_key = \"test-uuid\"\n\n[strategy]\ntransaction_lifetime = \"blockhash\"\nexecution_enabled = true\n\n[jito]\n{jito}"
+        ),
+    )
+}
+
+/// Engine with `[jito]` enabled against a mock block engine, plus `extra` settings.
+async fn harness(extra: &str) -> Harness {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(WSOL, USDC, 0.15);
+    jupiter.set_rate(USDC, WSOL, 1.01 / 0.15);
+    let rpc = MockRpc::start().await;
+    let block_engine = MockBlockEngine::start().await;
+
+    let jito = format!(
+        "enabled = true\nblock_engine_url = \"{}\"\ntip_accounts = [\"{}\", \"{}\"]\nstatus_poll_interval_ms = 10\n{}",
+        block_engine.url, TIP_ACCOUNTS[0], TIP_ACCOUNTS[1], extra
+    );
+    let config = config(&rpc.url, &jupiter.url, &jito);
+    config.check().unwrap();
+    let engine = Arc::new(Engine::new(config, Keypair::new()).unwrap());
+    refresh_blockhash(&engine).await.unwrap();
+    Harness {
+        rpc,
+        block_engine,
+        engine,
+        _jupiter: jupiter,
+    }
+}
+
+/// Tip account and lamports of the transfer ending `tx`.
+fn tip_of(tx: &VersionedTransaction) -> (Pubkey, u64) {
+    let keys = tx.message.static_account_keys();
+    let ix = tx.message.instructions().last().unwrap();
+    assert_eq!(keys[ix.program_id_index as usize], system_program::id());
+    let SystemInstruction::Transfer { lamports } = bincode::deserialize(&ix.data).unwrap() else {
+        panic!("the last instruction is not a transfer");
+    };
+    (keys[ix.accounts[1] as usize], lamports)
+}
+
+async fn wait_for_bundles(engine: &Engine, status: &str) -> u64 {
+    let counter = engine.metrics.bundles.with_label_values(&[status]);
+    for _ in 0..100 {
+        if counter.get() > 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    counter.get()
+}
+
+#[tokio::test]
+async fn trades_are_bundled_with_a_tip_to_rotating_tip_accounts() {
+    let h = harness("max_tip_lamports = 0").await;
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(poll(&h.engine).await.len(), 1);
+
+    let bundles = h.block_engine.bundles();
+    assert_eq!(bundles.len(), 2);
+    assert!(bundles.iter().all(|b| b.len() == 1));
+    assert!(h.rpc.sent().is_empty());
+
+    let tips: Vec<(Pubkey, u64)> = bundles.iter().map(|b| tip_of(&b[0])).collect();
+    let accounts: Vec<String> = tips.iter().map(|(account, _)| account.to_string()).collect();
+    assert_eq!(accounts, TIP_ACCOUNTS);
+    // Half of the ~0.02 SOL left after the base and priority fees
+    for (_, lamports) in &tips {
+        assert!((9_990_000..=10_000_000).contains(lamports), "tip {}", lamports);
+    }
+
+    let metrics = &h.engine.metrics;
+    assert_eq!(metrics.bundles.with_label_values(&["sent"]).get(), 2);
+    assert_eq!(metrics.bundle_tips.get(), tips[0].1 + tips[1].1);
+    assert_eq!(metrics.submit_first_accepted.with_label_values(&[JITO_ENDPOINT]).get(), 2);
+    assert_eq!(metrics.submissions.with_label_values(&["polling", "sent"]).get(), 2);
+    assert!(h.block_engine.auth_headers().iter().all(|h| h.as_deref() == Some("test-uuid")));
+}
+
+#[tokio::test]
+async fn the_tip_is_capped() {
+    let h = harness("max_tip_lamports = 50000").await;
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(tip_of(&h.block_engine.bundles()[0][0]).1, 50_000);
+}
+
+#[test]
+fn the_tip_is_a_share_of_the_profit_left_after_other_costs() {
+    let jito = JitoConfig {
+        tip_profit_share: 0.5,
+        min_tip_lamports: 1_000,
+        max_tip_lamports: 0,
+        ..JitoConfig::default()
+    };
+    // 5000 lamports base fee plus a 1000 lamport planned tip, priced in WSOL
+    let plan = TxPlan {
+        signatures: 1,
+        tip_lamports: 1_000,
+        ..TxPlan::default()
+    };
+    let cost = TradeCost::new(plan, 1e9, None);
+    assert_eq!(bundle_tip_lamports(&jito, &cost, 1_000_000_000, 1_005_000), 500_000);
+    // Unprofitable trades tip the minimum
+    assert_eq!(bundle_tip_lamports(&jito, &cost, 1_000_000_000, 4_000), 1_000);
+
+    let capped = JitoConfig {
+        max_tip_lamports: 100_000,
+        ..jito.clone()
+    };
+    assert_eq!(bundle_tip_lamports(&capped, &cost, 1_000_000_000, 1_005_000), 100_000);
+
+    // In USDC (6 decimals) at 150 USDC per SOL: 1.5 USDC left is 0.01 SOL
+    let cost = TradeCost::new(TxPlan::default(), 150_000_000.0, None);
+    assert_eq!(bundle_tip_lamports(&jito, &cost, 100_000_000, 1_500_000), 5_000_000);
+}
+
+#[tokio::test]
+async fn landed_bundles_are_counted() {
+    let h = harness("").await;
+    h.block_engine.set_status("Landed");
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(wait_for_bundles(&h.engine, "landed").await, 1);
+    assert!(h.block_engine.calls("getInflightBundleStatuses") >= 1);
+}
+
+#[tokio::test]
+async fn failed_bundles_are_counted() {
+    let h = harness("").await;
+    h.block_engine.set_status("Failed");
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(wait_for_bundles(&h.engine, "failed").await, 1);
+}
+
+#[tokio::test]
+async fn pending_bundles_time_out() {
+    let h = harness("status_timeout_secs = 0").await;
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(wait_for_bundles(&h.engine, "timeout").await, 1);
+    assert_eq!(h.engine.metrics.bundles.with_label_values(&["landed"]).get(), 0);
+}
+
+#[tokio::test]
+async fn a_rejected_bundle_is_a_failed_send() {
+    let h = harness("").await;
+    h.block_engine.reject_bundles();
+    assert!(poll(&h.engine).await.is_empty());
+
+    let metrics = &h.engine.metrics;
+    assert_eq!(metrics.bundles.with_label_values(&["rejected"]).get(), 1);
+    assert_eq!(metrics.submit_errors.with_label_values(&[JITO_ENDPOINT]).get(), 1);
+    assert_eq!(metrics.submissions.with_label_values(&["polling", "send_failed"]).get(), 1);
+    assert!(h.rpc.sent().is_empty());
+}
+
+#[test]
+fn jito_settings_are_validated() {
+    let paths = |jito: &str| -> Vec<String> {
+        let config = config("http://127.0.0.1:1", "http://127.0.0.1:1", jito);
+        config.validate().into_iter().map(|p| p.path).collect()
+    };
+    assert!(paths("enabled = true").is_empty());
+    assert_eq!(
+        paths(
+            "enabled = true\nblock_engine_url = \"ws://x\"\ntip_accounts = [\"nope\"]\ntip_profit_share = 1.5\nmin_tip_lamports = 10\nmax_tip_lamports = 5"
+        ),
+        [
+            "jito.block_engine_url",
+            "jito.tip_accounts[0]",
+            "jito.tip_profit_share",
+            "jito.max_tip_lamports"
+        ]
+    );
+    assert_eq!(paths("enabled = true\ntip_accounts = []"), ["jito.tip_accounts"]);
+    // Nothing is checked while disabled
+    assert!(paths("tip_accounts = []").is_empty());
+}
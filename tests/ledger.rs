@@ -0,0 +1,210 @@
+//! SQLite ledger: an opportunity polled against the mock servers is traced through its
+//! quotes, build attempt, submission and confirmation under one opportunity ID, and the
+//! per-target profit report adds it up.
+
+mod support;
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use jupiter_arbitrage_bot_offchain::*;
+use jupiter_swap_api_client::quote::QuoteResponse;
+use solana_sdk::pubkey::Pubkey;
+use support::{nonce_engine, poll, quote_json, MockJupiter, MockRpc, NonceEngine, USDC, WSOL};
+
+struct Harness {
+    _jupiter: MockJupiter,
+    _rpc: MockRpc,
+    engine: Arc<Engine>,
+    db_path: PathBuf,
+}
+
+impl Drop for Harness {
+    fn drop(&mut self) {
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", self.db_path.display(), suffix));
+        }
+    }
+}
+
+/// One WSOL instrument quoted through USDC with a 1% edge on the way back.
+async fn harness(live_trading: bool) -> Harness {
+    let db_path = std::env::temp_dir().join(format!("ledger-{}.sqlite", Pubkey::new_unique()));
+    let NonceEngine { jupiter, rpc, engine, .. } = nonce_engine(&format!(
+        "[strategy]\nexecution_enabled = {live_trading}\n\n[ledger]\nenabled = true\npath = \"{}\"",
+        db_path.display()
+    ))
+    .await;
+
+    Harness {
+        _jupiter: jupiter,
+        _rpc: rpc,
+        engine,
+        db_path,
+    }
+}
+
+fn count(db: &rusqlite::Connection, sql: &str, id: &str) -> i64 {
+    db.query_row(sql, [id], |row| row.get(0)).unwrap()
+}
+
+fn quote(input: &str, output: &str, in_amount: u64, out_amount: u64) -> QuoteResponse {
+    serde_json::from_value(quote_json(input, output, in_amount, out_amount)).unwrap()
+}
+
+#[test]
+fn opportunity_id_is_derived_from_the_legs() {
+    let legs = vec![
+        quote(WSOL, USDC, 1_000_000_000, 150_000_000),
+        quote(USDC, WSOL, 150_000_000, 1_010_000_000),
+    ];
+    let id = opportunity_id(&legs);
+
+    assert_eq!(id.len(), 16);
+    // Route step AMM keys differ between the two quote_json calls; only the legs count
+    let again = vec![
+        quote(WSOL, USDC, 1_000_000_000, 150_000_000),
+        quote(USDC, WSOL, 150_000_000, 1_010_000_000),
+    ];
+    assert_eq!(opportunity_id(&again), id);
+    assert_eq!(PendingTrade::from_legs("polling", &legs).opportunity_id, id);
+
+    let other = vec![
+        quote(WSOL, USDC, 2_000_000_000, 300_000_000),
+        quote(USDC, WSOL, 300_000_000, 2_020_000_000),
+    ];
+    assert_ne!(opportunity_id(&other), id);
+}
+
+#[tokio::test]
+async fn submitted_opportunity_is_traced_through_every_stage() {
+    let h = harness(true).await;
+
+    let signatures = poll(&h.engine).await;
+    assert_eq!(signatures.len(), 1);
+
+    // Both grid amounts are profitable; only the best is traded
+    let opportunities = h.engine.ledger.recent_opportunities(10).unwrap();
+    assert_eq!(opportunities.len(), 2);
+    for opportunity in &opportunities {
+        assert_eq!(opportunity.source, "polling");
+        assert_eq!(opportunity.instrument, WSOL);
+        assert_eq!(opportunity.route, "WSOL -> USDC -> WSOL");
+        assert!(opportunity.net_profit > 0);
+    }
+
+    let submissions = h.engine.ledger.recent_submissions(10).unwrap();
+    assert_eq!(submissions.len(), 1);
+    let opportunity = opportunities
+        .iter()
+        .find(|o| o.id == submissions[0].opportunity_id)
+        .expect("the traded opportunity is recorded");
+    assert!(opportunities.iter().all(|o| o.net_profit <= opportunity.net_profit));
+    assert_eq!(submissions[0].signature.as_deref(), Some(signatures[0].to_string().as_str()));
+    assert_eq!(submissions[0].status, None);
+
+    let db = rusqlite::Connection::open(&h.db_path).unwrap();
+    let quotes = count(&db, "SELECT COUNT(*) FROM quotes WHERE opportunity_id = ?1", &opportunity.id);
+    assert_eq!(quotes, 2);
+    let built = count(
+        &db,
+        "SELECT COUNT(*) FROM build_attempts WHERE opportunity_id = ?1 AND outcome = 'built'",
+        &opportunity.id,
+    );
+    assert_eq!(built, 1);
+
+    // What the landing tracker records once the trade settles
+    h.engine.ledger.record_confirmation(&LandingRecord {
+        recorded_at: Utc::now().to_rfc3339(),
+        submitted_at: submissions[0].submitted_at.clone(),
+        signature: signatures[0].to_string(),
+        opportunity_id: opportunity.id.clone(),
+        path: "polling".to_string(),
+        instrument: WSOL.to_string(),
+        route: "MockAmm -> MockAmm".to_string(),
+        status: LandingStatus::Finalized,
+        slot: Some(2),
+        expected_profit: submissions[0].expected_profit,
+        realized_profit: Some(9_000_000),
+        fee_lamports: Some(5_000),
+        error: None,
+    });
+
+    let submissions = h.engine.ledger.recent_submissions(10).unwrap();
+    assert_eq!(submissions[0].status.as_deref(), Some("finalized"));
+    assert_eq!(submissions[0].realized_profit, Some(9_000_000));
+
+    let report = h
+        .engine
+        .ledger
+        .profit_by_target(Utc::now() - chrono::Duration::days(7))
+        .unwrap();
+    assert_eq!(report.len(), 1);
+    assert_eq!(report[0].target, USDC);
+    assert_eq!((report[0].opportunities, report[0].submissions, report[0].landed), (2, 1, 1));
+    assert_eq!(report[0].realized_profit, 9_000_000);
+    assert_eq!(report[0].fee_lamports, 5_000);
+    assert!((report[0].realized_profit_ui() - 0.009).abs() < 1e-12);
+
+    // Nothing discovered after now
+    let later = h.engine.ledger.profit_by_target(Utc::now() + chrono::Duration::hours(1)).unwrap();
+    assert!(later.is_empty());
+}
+
+#[tokio::test]
+async fn dry_run_records_opportunities_only() {
+    let h = harness(false).await;
+
+    assert!(poll(&h.engine).await.is_empty());
+    // The same quotes again are the same opportunity
+    assert!(poll(&h.engine).await.is_empty());
+
+    // One per profitable grid amount
+    assert_eq!(h.engine.ledger.recent_opportunities(10).unwrap().len(), 2);
+    assert!(h.engine.ledger.recent_submissions(10).unwrap().is_empty());
+    let report = h.engine.ledger.profit_by_target(Utc::now() - chrono::Duration::days(1)).unwrap();
+    assert_eq!((report[0].opportunities, report[0].submissions), (2, 0));
+}
+
+#[test]
+fn disabled_ledger_records_nothing_and_refuses_queries() {
+    let ledger = Ledger::new(&LedgerConfig::default()).unwrap();
+    assert!(!ledger.enabled());
+
+    let legs = vec![
+        quote(WSOL, USDC, 1_000_000_000, 150_000_000),
+        quote(USDC, WSOL, 150_000_000, 1_010_000_000),
+    ];
+    let opportunity = CycleQuote {
+        in_amount: 1_000_000_000,
+        out_amount: 1_010_000_000,
+        legs: legs.clone(),
+        elapsed: 0,
+    };
+    assert_eq!(ledger.record_opportunity("polling", &opportunity, "WSOL -> USDC -> WSOL", 1, 9, None), opportunity_id(&legs));
+    assert!(ledger.recent_opportunities(10).is_err());
+}
+
+#[test]
+fn writes_do_not_wait_for_a_locked_database() {
+    let db_path = std::env::temp_dir().join(format!("ledger-{}.sqlite", Pubkey::new_unique()));
+    let ledger = Ledger::open(&db_path).unwrap();
+
+    // Another writer holds the database, e.g. a long `ledger` report transaction
+    let db = rusqlite::Connection::open(&db_path).unwrap();
+    db.execute_batch("BEGIN EXCLUSIVE").unwrap();
+    let started = std::time::Instant::now();
+    ledger.record_build("locked", "polling", BuildOutcome::Failed, Some("no free nonce account"));
+    assert!(started.elapsed() < Duration::from_millis(500));
+
+    db.execute_batch("COMMIT").unwrap();
+    ledger.flush();
+    assert_eq!(count(&db, "SELECT COUNT(*) FROM build_attempts WHERE opportunity_id = ?1", "locked"), 1);
+
+    drop((db, ledger));
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(format!("{}{}", db_path.display(), suffix));
+    }
+}
@@ -0,0 +1,444 @@
+//! Local pool math: per-venue decoding and swap output, the pool cache, the polling
+//! prefilter that keeps locally unprofitable cycles away from Jupiter, and the pool
+//! watcher's re-evaluation on account updates.
+
+mod support;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::Keypair;
+use support::{poll, FakeAccount, MockJupiter, MockRpc, USDC, WSOL};
+use yellowstone_grpc_proto::geyser::{SubscribeUpdateAccount, SubscribeUpdateAccountInfo};
+
+const SPL_TOKEN: Pubkey = Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+fn put(buf: &mut [u8], offset: usize, bytes: &[u8]) {
+    buf[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+/// An SPL token account holding `amount`.
+fn token_account(amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; 165];
+    put(&mut data, 64, &amount.to_le_bytes());
+    data
+}
+
+fn accounts(entries: &[(Pubkey, Vec<u8>)]) -> AccountMap {
+    entries.iter().cloned().collect::<HashMap<_, _>>()
+}
+
+fn pump_swap_pool(
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    base_vault: &Pubkey,
+    quote_vault: &Pubkey,
+) -> Vec<u8> {
+    let mut data = vec![0u8; 243];
+    put(&mut data, 43, base_mint.as_ref());
+    put(&mut data, 75, quote_mint.as_ref());
+    put(&mut data, 139, base_vault.as_ref());
+    put(&mut data, 171, quote_vault.as_ref());
+    data
+}
+
+#[test]
+fn raydium_amm_prices_vaults_net_of_pending_pnl() {
+    let (coin_mint, pc_mint, coin_vault, pc_vault) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut data = vec![0u8; RaydiumAmmPool::LEN];
+    put(&mut data, 0, &6u64.to_le_bytes()); // SwapOnly
+    put(&mut data, 176, &25u64.to_le_bytes());
+    put(&mut data, 184, &10_000u64.to_le_bytes());
+    put(&mut data, 192, &1_000u64.to_le_bytes());
+    put(&mut data, 200, &2_000u64.to_le_bytes());
+    put(&mut data, 336, coin_vault.as_ref());
+    put(&mut data, 368, pc_vault.as_ref());
+    put(&mut data, 400, coin_mint.as_ref());
+    put(&mut data, 432, pc_mint.as_ref());
+
+    let pool = PoolState::decode(&RAYDIUM_AMM_PROGRAM_ID, &data, &PoolsConfig::default()).unwrap();
+    assert_eq!(pool.venue(), Venue::RaydiumAmm);
+    assert_eq!(pool.mints(), (coin_mint, pc_mint));
+
+    let vaults = accounts(&[
+        (coin_vault, token_account(1_000_001_000)),
+        (pc_vault, token_account(150_000_002_000)),
+    ]);
+    let expected =
+        constant_product_out(1_000_000_000, 150_000_000_000, 1_000_000, 25, 10_000).unwrap();
+    assert_eq!(pool.quote(&coin_mint, 1_000_000, &vaults), Some(expected));
+    // 1e6 coin at 150 pc/coin, less 0.25% fee and ~0.1% price impact
+    assert!(
+        (149_400_000..149_625_000).contains(&expected),
+        "{}",
+        expected
+    );
+
+    assert_eq!(pool.quote(&Pubkey::new_unique(), 1_000_000, &vaults), None);
+    assert_eq!(pool.quote(&coin_mint, 1_000_000, &AccountMap::new()), None);
+}
+
+#[test]
+fn raydium_cp_reads_fee_from_amm_config_and_honours_swap_status() {
+    let (config, mint_0, mint_1, vault_0, vault_1) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut data = vec![0u8; RaydiumCpPool::LEN];
+    put(&mut data, 8, config.as_ref());
+    put(&mut data, 72, vault_0.as_ref());
+    put(&mut data, 104, vault_1.as_ref());
+    put(&mut data, 168, mint_0.as_ref());
+    put(&mut data, 200, mint_1.as_ref());
+    put(&mut data, 341, &500u64.to_le_bytes()); // protocol fees, token 0
+    put(&mut data, 365, &700u64.to_le_bytes()); // fund fees, token 1
+
+    let mut amm_config = vec![0u8; 236];
+    put(&mut amm_config, 12, &2_500u64.to_le_bytes());
+
+    let pool = PoolState::decode(&RAYDIUM_CP_PROGRAM_ID, &data, &PoolsConfig::default()).unwrap();
+    assert_eq!(pool.dependencies(), vec![vault_0, vault_1, config]);
+
+    let state = accounts(&[
+        (vault_0, token_account(5_000_500)),
+        (vault_1, token_account(10_000_700)),
+        (config, amm_config),
+    ]);
+    let expected = constant_product_out(10_000_000, 5_000_000, 10_000, 2_500, 1_000_000).unwrap();
+    assert_eq!(pool.quote(&mint_1, 10_000, &state), Some(expected));
+
+    put(&mut data, 329, &[1 << 2]);
+    let disabled =
+        PoolState::decode(&RAYDIUM_CP_PROGRAM_ID, &data, &PoolsConfig::default()).unwrap();
+    assert_eq!(disabled.quote(&mint_1, 10_000, &state), None);
+}
+
+#[test]
+fn whirlpool_prices_within_the_current_tick() {
+    let (mint_a, mint_b, vault_a, vault_b) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut data = vec![0u8; WhirlpoolPool::LEN];
+    put(&mut data, 45, &3_000u16.to_le_bytes()); // 0.3%
+    put(&mut data, 49, &1_000_000_000_000u128.to_le_bytes());
+    put(&mut data, 65, &(1u128 << 64).to_le_bytes()); // price 1.0
+    put(&mut data, 101, mint_a.as_ref());
+    put(&mut data, 133, vault_a.as_ref());
+    put(&mut data, 181, mint_b.as_ref());
+    put(&mut data, 213, vault_b.as_ref());
+
+    let pool = PoolState::decode(&WHIRLPOOL_PROGRAM_ID, &data, &PoolsConfig::default()).unwrap();
+    let vaults = accounts(&[
+        (vault_a, token_account(u64::MAX)),
+        (vault_b, token_account(u64::MAX)),
+    ]);
+    for input in [mint_a, mint_b] {
+        let out = pool.quote(&input, 1_000_000, &vaults).unwrap();
+        assert!((996_990..=997_000).contains(&out), "{}", out);
+    }
+
+    // Output never exceeds what the vault holds
+    let shallow = accounts(&[(vault_a, token_account(0)), (vault_b, token_account(500))]);
+    assert_eq!(pool.quote(&mint_a, 1_000_000, &shallow), Some(500));
+}
+
+#[test]
+fn meteora_dlmm_prices_at_the_active_bin() {
+    let (mint_x, mint_y, reserve_x, reserve_y) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let mut data = vec![0u8; MeteoraDlmmPool::LEN];
+    put(&mut data, 8, &10_000u16.to_le_bytes()); // base_factor
+    put(&mut data, 76, &100i32.to_le_bytes()); // active_id
+    put(&mut data, 80, &10u16.to_le_bytes()); // bin_step: 0.1%
+    put(&mut data, 88, mint_x.as_ref());
+    put(&mut data, 120, mint_y.as_ref());
+    put(&mut data, 152, reserve_x.as_ref());
+    put(&mut data, 184, reserve_y.as_ref());
+
+    let decoded =
+        PoolState::decode(&METEORA_DLMM_PROGRAM_ID, &data, &PoolsConfig::default()).unwrap();
+    let PoolState::MeteoraDlmm(dlmm) = decoded else {
+        panic!("expected a DLMM pool");
+    };
+    assert!((dlmm.base_fee() - 0.001).abs() < 1e-12);
+    assert!((dlmm.active_price() - 1.001_f64.powi(100)).abs() < 1e-12);
+
+    let pool = PoolState::MeteoraDlmm(dlmm);
+    let reserves = accounts(&[
+        (reserve_x, token_account(10_000_000)),
+        (reserve_y, token_account(10_000_000)),
+    ]);
+    let out = pool.quote(&mint_x, 1_000_000, &reserves).unwrap();
+    let expected = 1_000_000.0 * 0.999 * 1.001_f64.powi(100);
+    assert!(
+        (out as f64 - expected).abs() <= 1.0,
+        "{} vs {}",
+        out,
+        expected
+    );
+
+    let thin = accounts(&[
+        (reserve_x, token_account(10_000_000)),
+        (reserve_y, token_account(500_000)),
+    ]);
+    assert_eq!(pool.quote(&mint_x, 1_000_000, &thin), Some(500_000));
+}
+
+#[test]
+fn pump_swap_uses_the_configured_fee() {
+    let (base, quote, base_vault, quote_vault) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let data = pump_swap_pool(&base, &quote, &base_vault, &quote_vault);
+    let config = PoolsConfig {
+        pump_swap_fee_bps: 30,
+        ..PoolsConfig::default()
+    };
+    let pool = PoolState::decode(&PUMP_SWAP_PROGRAM_ID, &data, &config).unwrap();
+    let vaults = accounts(&[
+        (base_vault, token_account(1_000_000)),
+        (quote_vault, token_account(2_000_000)),
+    ]);
+    assert_eq!(
+        pool.quote(&quote, 10_000, &vaults),
+        constant_product_out(2_000_000, 1_000_000, 10_000, 30, 10_000)
+    );
+}
+
+#[test]
+fn decoding_rejects_unknown_programs_and_short_accounts() {
+    let config = PoolsConfig::default();
+    assert!(PoolState::decode(&Pubkey::new_unique(), &[0u8; 1024], &config).is_err());
+    assert!(PoolState::decode(&WHIRLPOOL_PROGRAM_ID, &[0u8; 100], &config).is_err());
+    assert_eq!(
+        Venue::from_program_id(&PUMP_SWAP_PROGRAM_ID),
+        Some(Venue::PumpSwap)
+    );
+    assert_eq!(Venue::Whirlpool.name(), "Whirlpool");
+}
+
+#[test]
+fn cache_tracks_dependencies_and_ignores_stale_updates() {
+    let (pool, base, quote, base_vault, quote_vault) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let cache = PoolCache::new(&PoolsConfig {
+        enabled: true,
+        addresses: vec![pool.to_string()],
+        ..PoolsConfig::default()
+    })
+    .unwrap();
+
+    // Vaults are unknown until the pool itself is decoded
+    assert!(!cache
+        .update_account(&base_vault, &SPL_TOKEN, &token_account(1), 1)
+        .unwrap());
+    assert!(cache
+        .update_account(
+            &pool,
+            &PUMP_SWAP_PROGRAM_ID,
+            &pump_swap_pool(&base, &quote, &base_vault, &quote_vault),
+            1
+        )
+        .unwrap());
+    assert_eq!(cache.watched_accounts().len(), 3);
+    assert!(cache.is_watched(&quote_vault));
+
+    assert!(cache
+        .update_account(&base_vault, &SPL_TOKEN, &token_account(1_000_000), 5)
+        .unwrap());
+    assert!(cache
+        .update_account(&quote_vault, &SPL_TOKEN, &token_account(1_000_000), 5)
+        .unwrap());
+    // Same data again, then an older slot: both no-ops
+    assert!(!cache
+        .update_account(&base_vault, &SPL_TOKEN, &token_account(1_000_000), 6)
+        .unwrap());
+    assert!(!cache
+        .update_account(&base_vault, &SPL_TOKEN, &token_account(1), 4)
+        .unwrap());
+
+    let (used, out) = cache.quote(&base, &quote, 1_000).unwrap();
+    assert_eq!(used, pool);
+    assert_eq!(
+        Some(out),
+        constant_product_out(1_000_000, 1_000_000, 1_000, 25, 10_000)
+    );
+
+    // A single pool round trip always loses its fees; unknown legs always pass
+    assert!(!cache.passes_prefilter(&[base, quote, base], &[1_000]));
+    assert!(cache.passes_prefilter(&[base, Pubkey::new_unique(), base], &[1_000]));
+}
+
+#[test]
+fn invalid_pool_address_is_rejected() {
+    let config = PoolsConfig {
+        enabled: true,
+        addresses: vec!["not-a-pubkey".to_string()],
+        ..PoolsConfig::default()
+    };
+    assert!(PoolCache::new(&config).is_err());
+}
+
+/// Two PumpSwap WSOL/USDC pools; `sell_price` is the USDC per SOL of the second one.
+async fn pool_harness(sell_price: u64) -> (MockJupiter, Arc<Engine>) {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(USDC, WSOL, 1.0 / 150.0 * 1000.0);
+    jupiter.set_rate(WSOL, USDC, 0.151);
+
+    let rpc = MockRpc::start().await;
+    let wsol = Pubkey::from_str_const(WSOL);
+    let usdc = Pubkey::from_str_const(USDC);
+    let mut addresses = Vec::new();
+    for price in [150, sell_price] {
+        let (pool, base_vault, quote_vault) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let account = |owner, data| FakeAccount {
+            owner,
+            lamports: 1_000_000,
+            data,
+        };
+        rpc.set_account(
+            pool,
+            account(
+                PUMP_SWAP_PROGRAM_ID,
+                pump_swap_pool(&wsol, &usdc, &base_vault, &quote_vault),
+            ),
+        );
+        rpc.set_account(
+            base_vault,
+            account(SPL_TOKEN, token_account(100_000_000_000_000)),
+        );
+        rpc.set_account(
+            quote_vault,
+            account(SPL_TOKEN, token_account(100_000 * price * 1_000_000)),
+        );
+        addresses.push(format!("\"{}\"", pool));
+    }
+
+    let config = support::config(
+        &rpc.url,
+        &jupiter.url,
+        &format!(
+            r#"
+[strategy]
+nonce_account_pubkey = "{nonce}"
+default_quote_mint = "{WSOL}"
+
+[[strategy.instruments]]
+token_mint = "{USDC}"
+min_delta_threshold = 100.0
+min_profit_quote_units = 0.01
+notional_range = [100.0, 200.0]
+grid_steps = 2
+
+[pools]
+enabled = true
+addresses = [{addresses}]
+refresh_interval_ms = 0
+"#,
+            nonce = Pubkey::new_unique(),
+            addresses = addresses.join(", "),
+        ),
+    );
+
+    let engine = Arc::new(Engine::new(config, Keypair::new()).unwrap());
+    // Two pools, then their four vaults
+    assert_eq!(refresh_pools(&engine).await.unwrap(), 6);
+    assert_eq!(engine.pools.len(), 2);
+    (jupiter, engine)
+}
+
+#[tokio::test]
+async fn locally_unprofitable_cycles_are_not_quoted() {
+    let (jupiter, engine) = pool_harness(150).await;
+
+    assert!(poll(&engine).await.is_empty());
+
+    assert_eq!(jupiter.quote_requests(), 0);
+}
+
+#[tokio::test]
+async fn locally_profitable_cycles_are_confirmed_on_jupiter() {
+    // Buy SOL at 150 on one pool, sell at 151 on the other: ~+16 bps after fees
+    let (jupiter, engine) = pool_harness(151).await;
+
+    assert!(poll(&engine).await.is_empty());
+
+    // Both legs, for both grid steps
+    assert_eq!(jupiter.quote_requests(), 4);
+}
+
+#[tokio::test]
+async fn pool_changes_only_re_evaluate_affected_instruments() {
+    let (jupiter, engine) = pool_harness(151).await;
+
+    evaluate_pool_changes(&engine, &[(Pubkey::new_unique(), Pubkey::new_unique())]).await;
+    assert_eq!(jupiter.quote_requests(), 0);
+
+    let pair = (Pubkey::from_str_const(WSOL), Pubkey::from_str_const(USDC));
+    evaluate_pool_changes(&engine, &[pair]).await;
+    assert_eq!(jupiter.quote_requests(), 4);
+}
+
+#[tokio::test]
+async fn yellowstone_vault_update_opens_an_opportunity() {
+    let (jupiter, engine) = pool_harness(150).await;
+    assert!(poll(&engine).await.is_empty());
+    assert_eq!(jupiter.quote_requests(), 0);
+
+    // Someone buys SOL on the second pool, pushing its price to 151 USDC
+    let pool = engine.pools.pool_addresses()[1];
+    let Some(PoolState::PumpSwap(state)) = engine.pools.pool(&pool) else {
+        panic!("expected a PumpSwap pool");
+    };
+    let vault = state.pool_quote_token_account;
+    let update = SubscribeUpdateAccount {
+        account: Some(SubscribeUpdateAccountInfo {
+            pubkey: vault.to_bytes().to_vec(),
+            owner: SPL_TOKEN.to_bytes().to_vec(),
+            data: token_account(100_000 * 151 * 1_000_000),
+            ..SubscribeUpdateAccountInfo::default()
+        }),
+        slot: 2,
+        ..SubscribeUpdateAccount::default()
+    };
+    assert!(apply_account_update(&engine.pools, &update));
+    // Replaying the same update is a no-op
+    assert!(!apply_account_update(&engine.pools, &update));
+
+    let changed = engine.pools.mints_affected_by(&vault);
+    assert_eq!(
+        changed,
+        vec![(Pubkey::from_str_const(WSOL), Pubkey::from_str_const(USDC))]
+    );
+    evaluate_pool_changes(&engine, &changed).await;
+    assert_eq!(jupiter.quote_requests(), 4);
+}
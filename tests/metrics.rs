@@ -0,0 +1,81 @@
+//! Prometheus metrics: the polling pipeline updates the engine's counters and
+//! histograms, and `/metrics` serves them in the text exposition format.
+
+mod support;
+
+use jupiter_arbitrage_bot_offchain::*;
+use support::{nonce_engine, poll, NonceEngine, USDC, USDT};
+use tokio::net::TcpListener;
+
+/// One WSOL instrument with a 1% edge through USDC; USDT has no route.
+async fn harness() -> NonceEngine {
+    nonce_engine("[metrics]\nenabled = true\nlisten = \"127.0.0.1:0\"").await
+}
+
+/// Value of the sample `name{...}` carrying all `labels` (e.g. `path="polling"`).
+fn sample(text: &str, name: &str, labels: &[&str]) -> Option<f64> {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter(|line| line.starts_with(&format!("{}{{", name)) || line.starts_with(&format!("{} ", name)))
+        .find(|line| labels.iter().all(|label| line.contains(label)))
+        .and_then(|line| line.rsplit(' ').next())
+        .and_then(|value| value.parse().ok())
+}
+
+#[tokio::test]
+async fn polling_pass_updates_pipeline_metrics() {
+    let h = harness().await;
+
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    let text = h.engine.metrics.render();
+
+    let usdc = format!("target=\"{}\"", USDC);
+    let usdt = format!("target=\"{}\"", USDT);
+    assert_eq!(sample(&text, "arb_quotes_total", &[&usdc, "outcome=\"ok\""]), Some(2.0));
+    assert_eq!(sample(&text, "arb_quotes_total", &[&usdt, "outcome=\"failed\""]), Some(2.0));
+    assert_eq!(
+        sample(&text, "arb_simulate_amount_in_seconds_count", &["mode=\"polling\""]),
+        Some(1.0)
+    );
+    assert_eq!(sample(&text, "arb_opportunities_total", &["source=\"polling\""]), Some(2.0));
+    assert_eq!(
+        sample(&text, "arb_submissions_total", &["path=\"polling\"", "outcome=\"sent\""]),
+        Some(1.0)
+    );
+    assert_eq!(sample(&text, "arb_nonce_fetch_errors_total", &[]), Some(0.0));
+    // Nothing fetched the SOL price in this process
+    assert_eq!(sample(&text, "arb_sol_price_age_seconds", &[]), Some(-1.0));
+}
+
+#[tokio::test]
+async fn latency_probe_feeds_the_jupiter_histogram() {
+    let h = harness().await;
+
+    estimate_jupiter_timing(&h.engine).await.unwrap();
+    estimate_jupiter_timing(&h.engine).await.unwrap();
+
+    let text = h.engine.metrics.render();
+    for call in ["quote_round_trip", "swap_instructions"] {
+        let label = format!("call=\"{}\"", call);
+        assert_eq!(sample(&text, "arb_jupiter_latency_seconds_count", &[&label]), Some(2.0));
+    }
+}
+
+#[tokio::test]
+async fn metrics_endpoint_serves_text_format() {
+    let h = harness().await;
+    h.engine.metrics.nonce_fetch_errors.inc();
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = metrics_router(h.engine.clone());
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+    let response = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap();
+    assert!(response.status().is_success());
+    let content_type = response.headers()["content-type"].to_str().unwrap().to_string();
+    assert!(content_type.starts_with("text/plain"));
+    let body = response.text().await.unwrap();
+    assert_eq!(sample(&body, "arb_nonce_fetch_errors_total", &[]), Some(1.0));
+    assert!(body.contains("# TYPE arb_jupiter_latency_seconds histogram"));
+}
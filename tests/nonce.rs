@@ -0,0 +1,321 @@
+//! Nonce account reads, lifecycle transactions and the nonce pool against the mock RPC.
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::hash::Hash;
+use solana_sdk::nonce::state::{Data as NonceData, DurableNonce};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use support::{MockJupiter, MockRpc, USDC, WSOL};
+
+fn engine(rpc: &MockRpc, nonce_account: &Pubkey) -> Engine {
+    let config = Config::from_toml_str(&format!(
+        r#"
+[connection]
+signer_keypair_path = "unused.json"
+rpc_endpoint = "{rpc}"
+submit_endpoint = "{rpc}"
+
+[dex_api]
+endpoint = "http://127.0.0.1:1"
+
+[strategy]
+nonce_account_pubkey = "{nonce_account}"
+geyser_watch_enabled = false
+
+[[strategy.instruments]]
+token_mint = "{WSOL}"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[fees]
+compute_unit_limit = 400000
+priority_fee_lamports = 1000
+relay_tip_sol = 0.0
+"#,
+        rpc = rpc.url,
+    ))
+    .unwrap();
+    Engine::new(config, Keypair::new()).unwrap()
+}
+
+#[tokio::test]
+async fn read_nonce_decodes_the_account() {
+    let rpc = MockRpc::start().await;
+    let nonce_account = Pubkey::new_unique();
+    let authority = Pubkey::new_unique();
+    let hash = rpc.set_nonce_account(nonce_account, authority, Hash::new_unique());
+    let engine = engine(&rpc, &nonce_account);
+
+    let data = read_nonce(&engine, &nonce_account).await.unwrap();
+    assert_eq!(data.authority, authority);
+    assert_eq!(data.blockhash(), hash);
+    assert_eq!(data.get_lamports_per_signature(), 5000);
+
+    let missing = Pubkey::new_unique();
+    let err = read_nonce(&engine, &missing).await.unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+}
+
+fn nonce_data(blockhash: Hash) -> NonceData {
+    NonceData::new(Pubkey::new_unique(), DurableNonce::from_blockhash(&blockhash), 5000)
+}
+
+#[test]
+fn leases_are_exclusive_until_the_nonce_advances() {
+    let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let pool = NoncePool::new(vec![a, b]);
+    let timeout = Duration::from_secs(60);
+    // Unread accounts are never leased
+    assert!(pool.acquire(timeout).is_none());
+
+    let (value_a, value_b) = (nonce_data(Hash::new_unique()), nonce_data(Hash::new_unique()));
+    pool.set(&a, value_a.clone());
+    pool.set(&b, value_b.clone());
+
+    let first = pool.acquire(timeout).unwrap();
+    assert_eq!((first.address, first.blockhash), (a, value_a.blockhash()));
+    first.sent();
+    let second = pool.acquire(timeout).unwrap();
+    assert_eq!(second.address, b);
+    second.sent();
+    assert!(pool.acquire(timeout).is_none());
+    assert_eq!(pool.leased(), 2);
+
+    // Re-reading the same value keeps the lease; a new value ends it
+    pool.set(&a, value_a);
+    assert!(pool.acquire(timeout).is_none());
+    let advanced = nonce_data(Hash::new_unique());
+    pool.set(&a, advanced.clone());
+    let lease = pool.acquire(timeout).unwrap();
+    assert_eq!((lease.address, lease.blockhash), (a, advanced.blockhash()));
+
+    // Dropped without being sent: back in the pool at once
+    drop(lease);
+    let lease = pool.acquire(timeout).unwrap();
+    assert_eq!(lease.address, a);
+    lease.sent();
+    assert!(pool.acquire(timeout).is_none());
+
+    // Expired leases are reused; releasing an old value is a no-op
+    assert_eq!(pool.acquire(Duration::ZERO).unwrap().address, a);
+    pool.release(&b, &Hash::new_unique());
+    assert_eq!(pool.leased(), 1);
+    pool.release(&b, &value_b.blockhash());
+    assert_eq!(pool.leased(), 0);
+}
+
+fn polling_engine(
+    jupiter: &MockJupiter,
+    rpc: &MockRpc,
+    keypair: Keypair,
+    nonce_accounts: &[Pubkey],
+) -> Arc<Engine> {
+    let pool: Vec<String> = nonce_accounts[1..].iter().map(|a| format!("\"{}\"", a)).collect();
+    let config = Config::from_toml_str(&format!(
+        r#"
+[connection]
+signer_keypair_path = "unused.json"
+rpc_endpoint = "{rpc}"
+submit_endpoint = "{rpc}"
+
+[dex_api]
+endpoint = "{jupiter}"
+
+[strategy]
+nonce_account_pubkey = "{primary}"
+nonce_account_pubkeys = [{pool}]
+execution_enabled = true
+polling_enabled = true
+geyser_watch_enabled = false
+
+[[strategy.instruments]]
+token_mint = "{WSOL}"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[fees]
+compute_unit_limit = 400000
+priority_fee_lamports = 1000
+relay_tip_sol = 0.0
+sol_price_usd = 150.0
+
+[flash_loan]
+enabled = false
+
+[landing]
+enabled = false
+"#,
+        rpc = rpc.url,
+        jupiter = jupiter.url,
+        primary = nonce_accounts[0],
+        pool = pool.join(", "),
+    ))
+    .unwrap();
+    config.check().unwrap();
+    let engine = Arc::new(Engine::new(config, keypair).unwrap());
+    let nonce_engine = engine.clone();
+    tokio::spawn(async move { fetch_nonce(&nonce_engine).await });
+    engine
+}
+
+/// Wait until `address` holds `blockhash` in the engine's pool.
+async fn wait_for(engine: &Engine, address: &Pubkey, blockhash: Hash) {
+    for _ in 0..50 {
+        if engine.nonces.get(address).map(|n| n.blockhash()) == Some(blockhash) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("nonce {} was never loaded", address);
+}
+
+/// Nonce account advanced by the first instruction of `tx`.
+fn advanced_nonce(tx: &VersionedTransaction) -> Pubkey {
+    let ix = &tx.message.instructions()[0];
+    tx.message.static_account_keys()[ix.accounts[0] as usize]
+}
+
+async fn poll(engine: &Arc<Engine>) -> usize {
+    let mut sent = 0;
+    for handle in poll_once(engine).await {
+        sent += handle.await.unwrap().is_some() as usize;
+    }
+    sent
+}
+
+#[tokio::test]
+async fn concurrent_submissions_use_distinct_nonce_accounts() {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(WSOL, USDC, 0.15);
+    jupiter.set_rate(USDC, WSOL, 1.01 / 0.15);
+    let rpc = MockRpc::start().await;
+    let keypair = Keypair::new();
+    let accounts = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let mut hashes = Vec::new();
+    for account in &accounts {
+        hashes.push(rpc.set_nonce_account(*account, keypair.pubkey(), Hash::new_unique()));
+    }
+    let engine = polling_engine(&jupiter, &rpc, keypair, &accounts);
+    for (account, hash) in accounts.iter().zip(&hashes) {
+        wait_for(&engine, account, *hash).await;
+    }
+
+    // Both accounts in flight, then nothing left to sign with
+    assert_eq!(poll(&engine).await, 1);
+    assert_eq!(poll(&engine).await, 1);
+    assert_eq!(poll(&engine).await, 0);
+    let sent = rpc.sent();
+    assert_eq!(advanced_nonce(&sent[0]), accounts[0]);
+    assert_eq!(*sent[0].message.recent_blockhash(), hashes[0]);
+    assert_eq!(advanced_nonce(&sent[1]), accounts[1]);
+    assert_eq!(*sent[1].message.recent_blockhash(), hashes[1]);
+
+    // The first transaction lands and advances its nonce: that account is free again
+    let advanced = rpc.set_nonce_account(accounts[0], engine.pubkey, Hash::new_unique());
+    wait_for(&engine, &accounts[0], advanced).await;
+    assert_eq!(poll(&engine).await, 1);
+    let sent = rpc.sent();
+    assert_eq!(advanced_nonce(&sent[2]), accounts[0]);
+    assert_eq!(*sent[2].message.recent_blockhash(), advanced);
+}
+
+#[tokio::test]
+async fn startup_check_requires_existing_accounts_controlled_by_the_signer() {
+    let rpc = MockRpc::start().await;
+    let nonce_account = Pubkey::new_unique();
+    let engine = engine(&rpc, &nonce_account);
+
+    let err = check_nonce_accounts(&engine).await.unwrap_err().to_string();
+    assert!(err.contains("not found") && err.contains("nonce create"), "{}", err);
+
+    let other = Pubkey::new_unique();
+    rpc.set_nonce_account(nonce_account, other, Hash::new_unique());
+    let err = check_nonce_accounts(&engine).await.unwrap_err().to_string();
+    assert!(err.contains(&other.to_string()) && err.contains(&engine.pubkey.to_string()), "{}", err);
+    assert!(err.contains("nonce authorize"), "{}", err);
+
+    let hash = rpc.set_nonce_account(nonce_account, engine.pubkey, Hash::new_unique());
+    check_nonce_accounts(&engine).await.unwrap();
+    // Loaded on the way
+    assert_eq!(engine.get_nonce().blockhash(), hash);
+}
+
+/// System instructions of the `n`th sent transaction.
+fn system_instructions(rpc: &MockRpc, n: usize) -> Vec<(SystemInstruction, Vec<Pubkey>)> {
+    let tx = &rpc.sent()[n];
+    let keys = tx.message.static_account_keys();
+    tx.message
+        .instructions()
+        .iter()
+        .map(|ix| {
+            assert_eq!(keys[ix.program_id_index as usize], system_program::id());
+            let accounts = ix.accounts.iter().map(|i| keys[*i as usize]).collect();
+            (bincode::deserialize(&ix.data).unwrap(), accounts)
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn lifecycle_transactions_are_signed_by_the_wallet() {
+    let rpc = MockRpc::start().await;
+    rpc.confirm_sent();
+    let nonce_account = Pubkey::new_unique();
+    let engine = engine(&rpc, &nonce_account);
+    rpc.set_nonce_account(nonce_account, engine.pubkey, Hash::new_unique());
+
+    let (created, _) = create_nonce_account(&engine).await.unwrap();
+    let ixs = system_instructions(&rpc, 0);
+    assert!(matches!(ixs[0].0, SystemInstruction::CreateAccount { lamports: 1_447_680, .. }));
+    assert_eq!(ixs[0].1[1], created);
+    assert_eq!(ixs[1].0, SystemInstruction::InitializeNonceAccount(engine.pubkey));
+    assert_eq!(rpc.sent()[0].signatures.len(), 2);
+
+    fund_nonce_account(&engine, &nonce_account, 5_000).await.unwrap();
+    let ixs = system_instructions(&rpc, 1);
+    assert_eq!(ixs[0].0, SystemInstruction::Transfer { lamports: 5_000 });
+    assert_eq!(ixs[0].1, vec![engine.pubkey, nonce_account]);
+
+    let new_authority = Pubkey::new_unique();
+    authorize_nonce_account(&engine, &nonce_account, &new_authority).await.unwrap();
+    let ixs = system_instructions(&rpc, 2);
+    assert_eq!(ixs[0].0, SystemInstruction::AuthorizeNonceAccount(new_authority));
+    assert_eq!(ixs[0].1, vec![nonce_account, engine.pubkey]);
+
+    let (lamports, _) = close_nonce_account(&engine, &nonce_account, None).await.unwrap();
+    assert_eq!(lamports, 1_447_680);
+    let ixs = system_instructions(&rpc, 3);
+    assert_eq!(ixs[0].0, SystemInstruction::WithdrawNonceAccount(1_447_680));
+    assert_eq!(&ixs[0].1[..2], &[nonce_account, engine.pubkey]);
+    assert!(rpc.sent().iter().all(|tx| tx.message.static_account_keys()[0] == engine.pubkey));
+}
+
+#[tokio::test]
+async fn accounts_of_another_authority_are_left_alone() {
+    let rpc = MockRpc::start().await;
+    rpc.confirm_sent();
+    let nonce_account = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    rpc.set_nonce_account(nonce_account, owner, Hash::new_unique());
+    let engine = engine(&rpc, &nonce_account);
+
+    let err = authorize_nonce_account(&engine, &nonce_account, &engine.pubkey).await.unwrap_err();
+    assert!(err.to_string().contains("controlled by"), "{}", err);
+    let err = close_nonce_account(&engine, &nonce_account, None).await.unwrap_err();
+    assert!(err.to_string().contains(&owner.to_string()), "{}", err);
+    // Not a nonce account at all
+    let err = fund_nonce_account(&engine, &Pubkey::new_unique(), 1).await.unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+    assert!(rpc.sent().is_empty());
+}
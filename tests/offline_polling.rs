@@ -0,0 +1,186 @@
+//! Drives the polling pipeline end to end against the in-process mock servers:
+//! `poll_once` -> `simulate_amount_in` -> `get_swap_ix` -> sign -> `sendTransaction`.
+
+mod support;
+
+use std::sync::Arc;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::hash::Hash;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use solana_sdk::system_program;
+use support::{poll, wait_for_nonce, MockJupiter, MockRpc, JITOSOL, USDC, WSOL};
+
+/// Raw-unit rate for 1 SOL (1e9 lamports) -> 150 USDC (150e6 micro-units).
+const SOL_TO_USDC: f64 = 0.15;
+
+struct Harness {
+    jupiter: MockJupiter,
+    rpc: MockRpc,
+    engine: Arc<Engine>,
+    nonce_hash: Hash,
+}
+
+async fn harness(usdc_to_sol: f64, live_trading: bool) -> Harness {
+    harness_with(usdc_to_sol, live_trading, "").await
+}
+
+async fn harness_with(usdc_to_sol: f64, live_trading: bool, extra_strategy: &str) -> Harness {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(WSOL, USDC, SOL_TO_USDC);
+    jupiter.set_rate(USDC, WSOL, usdc_to_sol);
+
+    let rpc = MockRpc::start().await;
+    let keypair = Keypair::new();
+    let nonce_account = Pubkey::new_unique();
+    let nonce_hash = rpc.set_nonce_account(nonce_account, keypair.pubkey(), Hash::new_unique());
+
+    let config = support::config(
+        &rpc.url,
+        &jupiter.url,
+        &format!(
+            "[strategy]\nnonce_account_pubkey = \"{nonce_account}\"\nexecution_enabled = {live_trading}\n{extra_strategy}"
+        ),
+    );
+    let engine = Arc::new(Engine::new(config, keypair).unwrap());
+
+    Harness {
+        jupiter,
+        rpc,
+        engine,
+        nonce_hash,
+    }
+}
+
+#[tokio::test]
+async fn profitable_round_trip_is_signed_with_nonce_and_sent() {
+    // 1% edge on the way back.
+    let h = harness(1.01 / SOL_TO_USDC, true).await;
+    wait_for_nonce(&h.engine, h.nonce_hash).await;
+
+    let signatures = poll(&h.engine).await;
+
+    assert_eq!(signatures.len(), 1, "best trade for the single instrument is submitted");
+    // Two notionals; USDT has no mock route so only the USDC legs are quoted successfully.
+    assert!(h.jupiter.quote_requests() >= 4);
+
+    let swap_requests = h.jupiter.swap_requests();
+    assert_eq!(swap_requests.len(), 1);
+    assert_eq!(swap_requests[0]["userPublicKey"], h.engine.pubkey.to_string());
+    assert_eq!(swap_requests[0]["quoteResponse"]["inputMint"], WSOL);
+    assert_eq!(swap_requests[0]["quoteResponse"]["outputMint"], WSOL);
+    assert_eq!(swap_requests[0]["quoteResponse"]["routePlan"].as_array().unwrap().len(), 2);
+
+    let sent = h.rpc.sent();
+    assert_eq!(sent.len(), 1);
+    let tx = &sent[0];
+    assert_eq!(tx.signatures[0], signatures[0]);
+    assert!(tx.verify_with_results().iter().all(|ok| *ok));
+    assert_eq!(*tx.message.recent_blockhash(), h.nonce_hash);
+
+    // advance_nonce, CU limit, CU price, setup, swap
+    let keys = tx.message.static_account_keys();
+    let programs: Vec<Pubkey> = tx
+        .message
+        .instructions()
+        .iter()
+        .map(|ix| keys[ix.program_id_index as usize])
+        .collect();
+    assert_eq!(programs.len(), 5);
+    assert_eq!(programs[0], system_program::id());
+    assert_eq!(programs[4].to_string(), support::JUPITER_PROGRAM);
+    assert!(h.rpc.calls("getMultipleAccounts") >= 1);
+}
+
+#[tokio::test]
+async fn unprofitable_round_trip_is_not_submitted() {
+    // 1% loss on the way back.
+    let h = harness(0.99 / SOL_TO_USDC, true).await;
+    wait_for_nonce(&h.engine, h.nonce_hash).await;
+
+    let signatures = poll(&h.engine).await;
+
+    assert!(signatures.is_empty());
+    assert!(h.jupiter.quote_requests() >= 4);
+    assert!(h.jupiter.swap_requests().is_empty());
+    assert!(h.rpc.sent().is_empty());
+}
+
+#[tokio::test]
+async fn execution_disabled_quotes_without_submitting() {
+    let h = harness(1.01 / SOL_TO_USDC, false).await;
+
+    let signatures = poll(&h.engine).await;
+
+    assert!(signatures.is_empty());
+    assert!(h.jupiter.quote_requests() >= 4);
+    assert!(h.jupiter.swap_requests().is_empty());
+    assert_eq!(h.rpc.calls("sendTransaction"), 0);
+}
+
+#[test]
+fn cycles_are_enumerated_shortest_first_without_repeats() {
+    let tokens: Vec<String> = [WSOL, USDC, JITOSOL].iter().map(|t| t.to_string()).collect();
+
+    let three = enumerate_cycles(WSOL, &tokens, 3, 100);
+    assert_eq!(
+        three,
+        vec![
+            vec![USDC.to_string(), JITOSOL.to_string()],
+            vec![JITOSOL.to_string(), USDC.to_string()],
+        ]
+    );
+
+    assert!(enumerate_cycles(WSOL, &tokens, 2, 100).is_empty());
+    assert_eq!(enumerate_cycles(WSOL, &tokens, 3, 1).len(), 1);
+}
+
+#[tokio::test]
+async fn triangular_cycle_is_found_and_submitted_as_one_route() {
+    // No edge on the two-leg round trip ...
+    let h = harness_with(
+        1.0 / SOL_TO_USDC,
+        true,
+        &format!("max_cycle_legs = 3\ncycle_tokens = [\"{USDC}\", \"{JITOSOL}\"]"),
+    )
+    .await;
+    // ... but WSOL -> USDC -> JitoSOL -> WSOL returns ~0.8% more.
+    h.jupiter.set_rate(USDC, JITOSOL, 0.9 / SOL_TO_USDC);
+    h.jupiter.set_rate(JITOSOL, WSOL, 1.12);
+    wait_for_nonce(&h.engine, h.nonce_hash).await;
+
+    let signatures = poll(&h.engine).await;
+    assert_eq!(signatures.len(), 1);
+
+    let swap_requests = h.jupiter.swap_requests();
+    assert_eq!(swap_requests.len(), 1);
+    let quote = &swap_requests[0]["quoteResponse"];
+    assert_eq!(quote["inputMint"], WSOL);
+    assert_eq!(quote["outputMint"], WSOL);
+    let route_plan = quote["routePlan"].as_array().unwrap();
+    let hops: Vec<(&str, &str)> = route_plan
+        .iter()
+        .map(|step| {
+            (
+                step["swapInfo"]["inputMint"].as_str().unwrap(),
+                step["swapInfo"]["outputMint"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(hops, vec![(WSOL, USDC), (USDC, JITOSOL), (JITOSOL, WSOL)]);
+    assert_eq!(h.rpc.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn a_cycle_through_an_invalid_mint_is_a_failed_quote() {
+    let h = harness(1.0 / SOL_TO_USDC, false).await;
+    let intermediates = vec![USDC.to_string(), "not-a-mint".to_string()];
+
+    let err = quote_cycle(&h.engine, 1_000_000_000, WSOL, &intermediates).await.unwrap_err();
+    assert!(err.to_string().contains("not-a-mint"), "{}", err);
+
+    assert_eq!(h.engine.metrics.quotes.with_label_values(&[CYCLE_QUOTE_TARGET, "failed"]).get(), 1);
+    // Nothing was quoted past the invalid mint
+    assert_eq!(h.jupiter.quote_requests(), 1);
+}
@@ -0,0 +1,105 @@
+//! Priority fee estimation: percentile of recent prioritization fees over the accounts
+//! our routes touch, fed into the cost model and the compute budget instructions.
+
+mod support;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::compute_budget;
+use solana_sdk::pubkey::Pubkey;
+use support::{nonce_engine, poll, quote_json, NonceEngine, USDC, WSOL};
+
+const COMPUTE_UNITS: u64 = 400_000;
+
+async fn harness(priority_fee: &str) -> NonceEngine {
+    nonce_engine(&format!("[fees]\ncompute_unit_limit = {COMPUTE_UNITS}\n\n[priority_fee]\n{priority_fee}")).await
+}
+
+/// Compute unit price set by the `SetComputeUnitPrice` instruction of `tx`.
+fn compute_unit_price(tx: &solana_sdk::transaction::VersionedTransaction) -> u64 {
+    let keys = tx.message.static_account_keys();
+    let ix = tx
+        .message
+        .instructions()
+        .iter()
+        .find(|ix| keys[ix.program_id_index as usize] == compute_budget::id() && ix.data[0] == 3)
+        .expect("compute unit price instruction");
+    u64::from_le_bytes(ix.data[1..9].try_into().unwrap())
+}
+
+#[test]
+fn percentile_is_nearest_rank() {
+    let mut samples = vec![40, 10, 30, 20];
+    assert_eq!(fee_percentile(&mut samples, 0), Some(10));
+    assert_eq!(fee_percentile(&mut samples, 50), Some(20));
+    assert_eq!(fee_percentile(&mut samples, 75), Some(30));
+    assert_eq!(fee_percentile(&mut samples, 100), Some(40));
+    assert_eq!(fee_percentile(&mut [], 50), None);
+}
+
+#[tokio::test]
+async fn estimate_samples_route_accounts_and_is_bounded() {
+    let extra = Pubkey::new_unique();
+    let h = harness(&format!(
+        "enabled = true\npercentile = 50\nmax_micro_lamports = 25000\naccounts = [\"{extra}\"]"
+    ))
+    .await;
+    // Disabled or not yet sampled: the static [fees] price
+    assert_eq!(h.engine.priority_fees.price(&h.engine.config()), 1000);
+
+    let quote = serde_json::from_value(quote_json(WSOL, USDC, 1_000_000_000, 150_000_000)).unwrap();
+    h.engine.priority_fees.observe_quote(&quote);
+    let pool = quote.route_plan[0].swap_info.amm_key;
+
+    h.rpc.set_prioritization_fees(&[0, 5_000, 10_000, 20_000, 90_000]);
+    assert_eq!(refresh_priority_fee(&h.engine).await.unwrap(), 10_000);
+    assert_eq!(h.engine.priority_fees.price(&h.engine.config()), 10_000);
+
+    let requested = h.rpc.prioritization_fee_requests();
+    let accounts = &requested[0];
+    assert_eq!(accounts[0], JUPITER_PROGRAM_ADDR.to_string());
+    assert!(accounts.contains(&extra.to_string()));
+    assert!(accounts.contains(&pool.to_string()));
+
+    // Spikes are cut at max_micro_lamports
+    h.rpc.set_prioritization_fees(&[100_000, 200_000]);
+    assert_eq!(refresh_priority_fee(&h.engine).await.unwrap(), 25_000);
+}
+
+#[tokio::test]
+async fn estimated_price_is_paid_when_the_profit_covers_it() {
+    let h = harness("enabled = true\npercentile = 100").await;
+    h.rpc.set_prioritization_fees(&[50_000]);
+    refresh_priority_fee(&h.engine).await.unwrap();
+
+    let cost = trade_cost(&h.engine, WSOL, 9).await.unwrap();
+    // 400_000 CU × 50_000 micro-lamports
+    assert_eq!(cost.lamports.priority_fee, 20_000);
+
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    let sent = h.rpc.sent();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(*sent[0].message.recent_blockhash(), h.nonce_hash);
+    assert_eq!(compute_unit_price(&sent[0]), 50_000);
+}
+
+#[tokio::test]
+async fn price_is_capped_to_a_share_of_the_expected_profit() {
+    let h = harness("enabled = true\npercentile = 100\nmax_profit_share = 0.01").await;
+    h.rpc.set_prioritization_fees(&[1_000_000]);
+    refresh_priority_fee(&h.engine).await.unwrap();
+
+    let cost = trade_cost(&h.engine, WSOL, 9).await.unwrap();
+    // 0.01 SOL profit on 1 SOL: at most 1% of what is left after the 5000 lamport base fee
+    let capped = cost.for_trade(1_000_000_000, 10_000_000);
+    assert_eq!(capped.lamports.priority_fee, (10_000_000 - 5_000) / 100);
+    // Nothing left to spend on an unprofitable trade
+    assert_eq!(cost.for_trade(1_000_000_000, -1).plan.compute_unit_price, 0);
+
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    let sent = h.rpc.sent();
+    assert_eq!(sent.len(), 1);
+    let price = compute_unit_price(&sent[0]);
+    assert!(price > 0 && price < 1_000_000, "price {}", price);
+    // The 2 SOL trade made ~0.02 SOL; its priority fee stays within 1% of that
+    assert!(price * COMPUTE_UNITS / 1_000_000 <= 200_000);
+}
@@ -0,0 +1,215 @@
+//! `[submit] endpoints`: each signed transaction is broadcast to several endpoints, with
+//! acceptance latency, errors and first acceptances tracked per endpoint.
+
+mod support;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use jupiter_arbitrage_bot_offchain::*;
+use solana_sdk::signer::keypair::Keypair;
+use support::{poll, MockJupiter, MockRpc, USDC, WSOL};
+
+struct Harness {
+    /// Read RPC (quotes, blockhash, landing status).
+    rpc: MockRpc,
+    a: MockRpc,
+    b: MockRpc,
+    engine: Arc<Engine>,
+    _jupiter: MockJupiter,
+}
+
+fn config(rpc: &str, jupiter: &str, submit: &str, landing: &str) -> Config {
+    let mut config = support::config(
+        rpc,
+        jupiter,
+        &format!(
+            "[strategy]\ntransaction_lifetime = \"blockhash\"\nexecution_enabled = true\n\n[landing]\n{landing}\n\n[submit]\n{submit}"
+        ),
+    );
+    // Only the [submit] endpoints send
+    config.node.submit_url.clear();
+    config
+}
+
+/// Engine whose `[submit]` section is built by `endpoints` from the read RPC and two more
+/// mock RPCs, "a" and "b".
+async fn harness_with(endpoints: impl FnOnce(&MockRpc, &MockRpc, &MockRpc) -> String, landing: &str) -> Harness {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(WSOL, USDC, 0.15);
+    jupiter.set_rate(USDC, WSOL, 1.01 / 0.15);
+    let (rpc, a, b) = (MockRpc::start().await, MockRpc::start().await, MockRpc::start().await);
+
+    let config = config(&rpc.url, &jupiter.url, &endpoints(&rpc, &a, &b), landing);
+    config.check().unwrap();
+    let engine = Arc::new(Engine::new(config, Keypair::new()).unwrap());
+    refresh_blockhash(&engine).await.unwrap();
+    Harness {
+        rpc,
+        a,
+        b,
+        engine,
+        _jupiter: jupiter,
+    }
+}
+
+async fn harness() -> Harness {
+    harness_with(
+        |_, a, b| {
+            format!(
+                "endpoints = [{{ name = \"a\", url = \"{}\" }}, {{ name = \"b\", url = \"{}\" }}]",
+                a.url, b.url
+            )
+        },
+        "enabled = false",
+    )
+    .await
+}
+
+fn count(metric: &prometheus::IntCounterVec, endpoint: &str) -> u64 {
+    metric.with_label_values(&[endpoint]).get()
+}
+
+fn latency_samples(engine: &Engine, endpoint: &str) -> u64 {
+    engine.metrics.submit_latency.with_label_values(&[endpoint]).get_sample_count()
+}
+
+/// Wait for `done`: endpoints answering after the first acceptance are recorded in the background.
+async fn eventually(done: impl Fn() -> bool) {
+    for _ in 0..100 {
+        if done() {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("condition never held");
+}
+
+#[tokio::test]
+async fn every_endpoint_gets_each_transaction() {
+    let h = harness().await;
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    eventually(|| latency_samples(&h.engine, "a") == 1 && latency_samples(&h.engine, "b") == 1).await;
+
+    let (a, b) = (h.a.sent(), h.b.sent());
+    assert_eq!((a.len(), b.len()), (1, 1));
+    assert_eq!(a[0].signatures, b[0].signatures);
+    assert!(h.rpc.sent().is_empty());
+
+    let metrics = &h.engine.metrics;
+    for endpoint in ["a", "b"] {
+        assert_eq!(count(&metrics.submit_errors, endpoint), 0);
+    }
+    assert_eq!(count(&metrics.submit_first_accepted, "a") + count(&metrics.submit_first_accepted, "b"), 1);
+}
+
+#[tokio::test]
+async fn a_failing_endpoint_does_not_stop_the_submission() {
+    let h = harness().await;
+    h.b.reject_sends();
+    assert_eq!(poll(&h.engine).await.len(), 1);
+
+    let metrics = &h.engine.metrics;
+    eventually(|| count(&metrics.submit_errors, "b") == 1).await;
+    assert_eq!(count(&metrics.submit_first_accepted, "a"), 1);
+    assert_eq!(latency_samples(&h.engine, "b"), 0);
+    assert_eq!(metrics.submissions.with_label_values(&["polling", "sent"]).get(), 1);
+}
+
+#[tokio::test]
+async fn the_first_acceptance_ends_the_wait() {
+    let h = harness().await;
+    h.b.set_send_delay(Duration::from_millis(1_000));
+    let started = std::time::Instant::now();
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert!(started.elapsed() < Duration::from_millis(900), "waited {:?}", started.elapsed());
+    assert_eq!(count(&h.engine.metrics.submit_first_accepted, "a"), 1);
+
+    // "b" is still recorded once it answers
+    eventually(|| latency_samples(&h.engine, "b") == 1).await;
+    assert_eq!(h.b.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn submission_fails_when_every_endpoint_fails() {
+    let h = harness().await;
+    h.a.reject_sends();
+    h.b.reject_sends();
+    assert!(poll(&h.engine).await.is_empty());
+
+    let metrics = &h.engine.metrics;
+    assert_eq!(count(&metrics.submit_errors, "a"), 1);
+    assert_eq!(count(&metrics.submit_errors, "b"), 1);
+    assert_eq!(metrics.submissions.with_label_values(&["polling", "send_failed"]).get(), 1);
+}
+
+#[tokio::test]
+async fn fanout_picks_endpoints_in_proportion_to_weight() {
+    let h = harness_with(
+        |_, a, b| {
+            format!(
+                "fanout = 1\nendpoints = [{{ name = \"a\", url = \"{}\", weight = 2 }}, {{ name = \"b\", url = \"{}\" }}, {{ name = \"off\", url = \"{}\", weight = 0 }}]",
+                a.url, b.url, a.url
+            )
+        },
+        "enabled = false",
+    )
+    .await;
+
+    let picks: Vec<String> = (0..6)
+        .map(|_| {
+            let picked = h.engine.submit_endpoints.pick();
+            assert_eq!(picked.len(), 1);
+            picked[0].name.clone()
+        })
+        .collect();
+    assert_eq!(picks, ["a", "b", "a", "a", "b", "a"]);
+    assert_eq!(h.engine.submit_endpoints.primary().name, "a");
+
+    assert_eq!(poll(&h.engine).await.len(), 1);
+    assert_eq!(h.a.sent().len() + h.b.sent().len(), 1);
+}
+
+#[tokio::test]
+async fn landings_are_credited_to_the_first_endpoint_to_accept() {
+    // "fast" is the read RPC, so the landing tracker sees its copy
+    let h = harness_with(
+        |rpc, _, b| {
+            format!(
+                "endpoints = [{{ name = \"slow\", url = \"{}\" }}, {{ name = \"fast\", url = \"{}\" }}]",
+                b.url, rpc.url
+            )
+        },
+        "enabled = true\nledger_path = \"/dev/null\"\npoll_interval_ms = 10\ntimeout_secs = 1",
+    )
+    .await;
+    h.b.set_send_delay(Duration::from_millis(200));
+    h.rpc.confirm_sent();
+    assert_eq!(poll(&h.engine).await.len(), 1);
+
+    let metrics = &h.engine.metrics;
+    assert_eq!(count(&metrics.submit_first_accepted, "fast"), 1);
+    for _ in 0..100 {
+        if count(&metrics.submit_first_landed, "fast") == 1 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    assert_eq!(count(&metrics.submit_first_landed, "fast"), 1);
+    assert_eq!(count(&metrics.submit_first_landed, "slow"), 0);
+}
+
+#[test]
+fn endpoints_are_validated() {
+    let paths = |submit: &str| -> Vec<String> {
+        let config = config("http://127.0.0.1:1", "http://127.0.0.1:1", submit, "");
+        config.validate().into_iter().map(|p| p.path).collect()
+    };
+    assert_eq!(
+        paths("endpoints = [{ name = \"a\", url = \"ws://x\" }, { name = \"a\", url = \"http://x\", weight = 0 }]"),
+        ["submit.endpoints[0].url", "submit.endpoints[1].name"]
+    );
+    assert_eq!(paths("endpoints = [{ name = \"a\", url = \"http://x\", weight = 0 }]"), ["submit.endpoints"]);
+    // Without [submit] endpoints, [connection] submit_endpoint is required
+    assert_eq!(paths(""), ["connection.submit_endpoint"]);
+}
@@ -0,0 +1,710 @@
+//! In-process stand-ins for the Jupiter swap API, a Solana RPC node and a Jito block
+//! engine.
+//!
+//! The servers bind to `127.0.0.1:0` and serve canned JSON, so integration tests can
+//! drive the full pipeline (quote -> swap-instructions -> sign -> send) with no network.
+//! [`config`], [`nonce_engine`], [`poll`] and [`wait_for_nonce`] cover the setup most
+//! tests share.
+
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use axum::extract::{Query, State};
+use axum::http::HeaderMap;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use jupiter_arbitrage_bot_offchain::{fetch_nonce, poll_once, Config, Engine};
+use serde_json::{json, Value};
+use solana_sdk::hash::Hash;
+use solana_sdk::nonce::state::{Data as NonceData, DurableNonce, State as NonceState, Versions};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use tokio::net::TcpListener;
+
+pub const WSOL: &str = "So11111111111111111111111111111111111111112";
+pub const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+pub const USDT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+pub const JITOSOL: &str = "J1toso1uCk3RLmjorhTtrVwY9HJ7X8V9yYac6Y7kGCPn";
+pub const JUPITER_PROGRAM: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNxVTaV4";
+pub const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+
+async fn serve(router: Router) -> SocketAddr {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+    addr
+}
+
+// -----------------------------------------------------------------------------
+// Config and pipeline
+// -----------------------------------------------------------------------------
+
+/// `settings.toml` text against the mock `rpc` and `jupiter` servers: one WSOL
+/// instrument (1-2 SOL, 2 grid steps), polling with execution off, 400k compute units at
+/// 1000 micro-lamports, no relay tip, flash loans and landing checks off. `extra_toml` is
+/// merged over it key by key; arrays such as `strategy.instruments` are replaced whole.
+pub fn config_toml(rpc: &str, jupiter: &str, extra_toml: &str) -> String {
+    let base = format!(
+        r#"
+[connection]
+signer_keypair_path = "unused.json"
+rpc_endpoint = "{rpc}"
+submit_endpoint = "{rpc}"
+
+[dex_api]
+endpoint = "{jupiter}"
+
+[strategy]
+execution_enabled = false
+polling_enabled = true
+geyser_watch_enabled = false
+
+[[strategy.instruments]]
+token_mint = "{WSOL}"
+min_delta_threshold = 1.0
+min_profit_quote_units = 0.001
+notional_range = [1.0, 2.0]
+grid_steps = 2
+
+[fees]
+compute_unit_limit = 400000
+priority_fee_lamports = 1000
+relay_tip_sol = 0.0
+sol_price_usd = 150.0
+
+[flash_loan]
+enabled = false
+
+[landing]
+enabled = false
+"#
+    );
+    let mut table: toml::Table = base.parse().unwrap();
+    merge(&mut table, extra_toml.parse().expect("extra_toml is not valid TOML"));
+    toml::to_string(&table).unwrap()
+}
+
+/// [`config_toml`], parsed.
+pub fn config(rpc: &str, jupiter: &str, extra_toml: &str) -> Config {
+    Config::from_toml_str(&config_toml(rpc, jupiter, extra_toml)).unwrap()
+}
+
+/// `[flash_loan]` enabled at `fee_bps` with one `mint` reserve of made-up accounts, as
+/// `extra_toml` for [`config`].
+pub fn flash_loan_section(mint: &str, fee_bps: u16) -> String {
+    let key = || Pubkey::new_unique().to_string();
+    format!(
+        r#"
+[flash_loan]
+enabled = true
+fee_bps = {fee_bps}
+
+[[flash_loan.reserves]]
+token_mint = "{mint}"
+flashloan_token_reserves_liquidity = "{}"
+flashloan_borrow_position_on_liquidity = "{}"
+rate_model = "{}"
+vault = "{}"
+liquidity = "{}"
+liquidity_program = "{}"
+"#,
+        key(),
+        key(),
+        key(),
+        key(),
+        key(),
+        key(),
+    )
+}
+
+fn merge(base: &mut toml::Table, extra: toml::Table) {
+    for (key, value) in extra {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(extra)) => merge(base, extra),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// One [`poll_once`] pass; the signatures of the trades it sent.
+pub async fn poll(engine: &Arc<Engine>) -> Vec<Signature> {
+    let mut sent = Vec::new();
+    for handle in poll_once(engine).await {
+        sent.extend(handle.await.unwrap());
+    }
+    sent
+}
+
+/// Start the nonce fetcher and wait until the primary nonce holds `nonce_hash`.
+pub async fn wait_for_nonce(engine: &Arc<Engine>, nonce_hash: Hash) {
+    let fetcher = engine.clone();
+    tokio::spawn(async move { fetch_nonce(&fetcher).await });
+    for _ in 0..50 {
+        if engine.get_nonce().blockhash() == nonce_hash {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("nonce was never loaded from getAccountInfo");
+}
+
+/// Raw-unit rate for 1 SOL (1e9 lamports) -> 150 USDC (150e6 micro-units).
+pub const SOL_TO_USDC: f64 = 0.15;
+
+/// Mock servers and engine of [`nonce_engine`].
+pub struct NonceEngine {
+    pub jupiter: MockJupiter,
+    pub rpc: MockRpc,
+    pub engine: Arc<Engine>,
+    /// Blockhash stored in the durable nonce account.
+    pub nonce_hash: Hash,
+}
+
+/// Engine over [`config`] with execution on, signing against a durable nonce account the
+/// mock RPC serves, and a 1% edge on WSOL -> USDC -> WSOL. `extra_toml` is merged over
+/// it; returns once the nonce is loaded.
+pub async fn nonce_engine(extra_toml: &str) -> NonceEngine {
+    let jupiter = MockJupiter::start().await;
+    jupiter.set_rate(WSOL, USDC, SOL_TO_USDC);
+    jupiter.set_rate(USDC, WSOL, 1.01 / SOL_TO_USDC);
+
+    let rpc = MockRpc::start().await;
+    let keypair = Keypair::new();
+    let nonce_account = Pubkey::new_unique();
+    let nonce_hash = rpc.set_nonce_account(nonce_account, keypair.pubkey(), Hash::new_unique());
+
+    let mut extra: toml::Table =
+        format!("[strategy]\nnonce_account_pubkey = \"{nonce_account}\"\nexecution_enabled = true").parse().unwrap();
+    merge(&mut extra, extra_toml.parse().expect("extra_toml is not valid TOML"));
+    let config = config(&rpc.url, &jupiter.url, &toml::to_string(&extra).unwrap());
+    config.check().unwrap();
+    let engine = Arc::new(Engine::new(config, keypair).unwrap());
+    wait_for_nonce(&engine, nonce_hash).await;
+
+    NonceEngine {
+        jupiter,
+        rpc,
+        engine,
+        nonce_hash,
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Jupiter
+// -----------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct JupiterState {
+    /// Raw-unit exchange rate per (input mint, output mint); pairs without a rate return HTTP 400.
+    pub rates: Mutex<HashMap<(String, String), f64>>,
+    pub quote_requests: AtomicUsize,
+    /// Bodies of all `/swap-instructions` requests, in arrival order.
+    pub swap_requests: Mutex<Vec<Value>>,
+}
+
+/// Mock Jupiter swap API serving `/quote` and `/swap-instructions`.
+pub struct MockJupiter {
+    pub url: String,
+    pub state: Arc<JupiterState>,
+}
+
+impl MockJupiter {
+    pub async fn start() -> Self {
+        let state = Arc::new(JupiterState::default());
+        let router = Router::new()
+            .route("/quote", get(quote))
+            .route("/swap-instructions", post(swap_instructions))
+            .with_state(state.clone());
+        let addr = serve(router).await;
+        Self {
+            url: format!("http://{}", addr),
+            state,
+        }
+    }
+
+    /// Quote `input -> output` at `rate` output raw units per input raw unit.
+    pub fn set_rate(&self, input: &str, output: &str, rate: f64) {
+        self.state
+            .rates
+            .lock()
+            .unwrap()
+            .insert((input.to_string(), output.to_string()), rate);
+    }
+
+    pub fn quote_requests(&self) -> usize {
+        self.state.quote_requests.load(Ordering::SeqCst)
+    }
+
+    pub fn swap_requests(&self) -> Vec<Value> {
+        self.state.swap_requests.lock().unwrap().clone()
+    }
+}
+
+/// A `QuoteResponse` JSON body with a single route step.
+pub fn quote_json(input_mint: &str, output_mint: &str, in_amount: u64, out_amount: u64) -> Value {
+    json!({
+        "inputMint": input_mint,
+        "inAmount": in_amount.to_string(),
+        "outputMint": output_mint,
+        "outAmount": out_amount.to_string(),
+        "otherAmountThreshold": out_amount.to_string(),
+        "swapMode": "ExactIn",
+        "slippageBps": 0,
+        "platformFee": null,
+        "priceImpactPct": "0",
+        "routePlan": [{
+            "swapInfo": {
+                "ammKey": Pubkey::new_unique().to_string(),
+                "label": "MockAmm",
+                "inputMint": input_mint,
+                "outputMint": output_mint,
+                "inAmount": in_amount.to_string(),
+                "outAmount": out_amount.to_string(),
+                "feeAmount": "0",
+                "feeMint": input_mint
+            },
+            "percent": 100
+        }],
+        "contextSlot": 1,
+        "timeTaken": 0.001
+    })
+}
+
+async fn quote(
+    State(state): State<Arc<JupiterState>>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Json<Value>, (axum::http::StatusCode, Json<Value>)> {
+    state.quote_requests.fetch_add(1, Ordering::SeqCst);
+    let input = params.get("inputMint").cloned().unwrap_or_default();
+    let output = params.get("outputMint").cloned().unwrap_or_default();
+    let amount: u64 = params
+        .get("amount")
+        .and_then(|a| a.parse().ok())
+        .unwrap_or_default();
+
+    let rate = state.rates.lock().unwrap().get(&(input.clone(), output.clone())).copied();
+    match rate {
+        Some(rate) => Ok(Json(quote_json(&input, &output, amount, (amount as f64 * rate) as u64))),
+        None => Err((
+            axum::http::StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "Could not find any route", "errorCode": "COULD_NOT_FIND_ANY_ROUTE" })),
+        )),
+    }
+}
+
+fn instruction_json(user: &str, data: &[u8]) -> Value {
+    json!({
+        "programId": JUPITER_PROGRAM,
+        "accounts": [{ "pubkey": user, "isSigner": true, "isWritable": true }],
+        "data": BASE64.encode(data)
+    })
+}
+
+/// A `SwapInstructionsResponse` JSON body whose instructions only touch `user`.
+pub fn swap_instructions_json(user: &str) -> Value {
+    json!({
+        "tokenLedgerInstruction": null,
+        "computeBudgetInstructions": [],
+        "setupInstructions": [instruction_json(user, &[1])],
+        "swapInstruction": instruction_json(user, &[2, 3, 4]),
+        "cleanupInstruction": null,
+        "otherInstructions": [],
+        "addressLookupTableAddresses": [],
+        "prioritizationFeeLamports": 0,
+        "computeUnitLimit": 200000,
+        "prioritizationType": null,
+        "dynamicSlippageReport": null,
+        "simulationError": null
+    })
+}
+
+async fn swap_instructions(
+    State(state): State<Arc<JupiterState>>,
+    Json(body): Json<Value>,
+) -> Json<Value> {
+    let user = body["userPublicKey"].as_str().unwrap_or_default().to_string();
+    state.swap_requests.lock().unwrap().push(body);
+    Json(swap_instructions_json(&user))
+}
+
+// -----------------------------------------------------------------------------
+// RPC
+// -----------------------------------------------------------------------------
+
+#[derive(Clone)]
+pub struct FakeAccount {
+    pub owner: Pubkey,
+    pub lamports: u64,
+    pub data: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct RpcState {
+    pub accounts: Mutex<HashMap<Pubkey, FakeAccount>>,
+    /// Transactions received by `sendTransaction`, in arrival order.
+    pub sent: Mutex<Vec<VersionedTransaction>>,
+    pub calls: Mutex<Vec<String>>,
+    /// Per-slot fees returned by `getRecentPrioritizationFees`.
+    pub prioritization_fees: Mutex<Vec<u64>>,
+    /// Accounts of every `getRecentPrioritizationFees` request, in arrival order.
+    pub prioritization_fee_requests: Mutex<Vec<Vec<String>>>,
+    /// Report sent signatures as confirmed in `getSignatureStatuses`, see [`MockRpc::confirm_sent`].
+    pub confirm_sent: AtomicBool,
+    /// Returned by `getBlockHeight`; `getLatestBlockhash` is valid up to 150.
+    pub block_height: AtomicU64,
+    /// Fail every `sendTransaction`, see [`MockRpc::reject_sends`].
+    pub reject_sends: AtomicBool,
+    /// Delay before answering `sendTransaction`.
+    pub send_delay_ms: AtomicU64,
+}
+
+/// JSON-RPC stand-in implementing `getVersion`, `getAccountInfo`,
+/// `getMultipleAccounts`, `getSignatureStatuses`, `getRecentPrioritizationFees`,
+/// `getLatestBlockhash`, `getBlockHeight`, `isBlockhashValid`,
+/// `getMinimumBalanceForRentExemption`, `sendTransaction` and `simulateTransaction`.
+pub struct MockRpc {
+    pub url: String,
+    pub state: Arc<RpcState>,
+}
+
+impl MockRpc {
+    pub async fn start() -> Self {
+        let state = Arc::new(RpcState::default());
+        let router = Router::new()
+            .route("/", post(rpc))
+            .with_state(state.clone());
+        let addr = serve(router).await;
+        let rpc = Self {
+            url: format!("http://{}", addr),
+            state,
+        };
+        for (mint, decimals) in [(WSOL, 9), (USDC, 6), (USDT, 6), (JITOSOL, 9)] {
+            rpc.set_mint(mint, decimals, TOKEN_PROGRAM);
+        }
+        rpc
+    }
+
+    /// Store an initialized mint with `decimals`, owned by `program` (SPL Token or Token-2022).
+    pub fn set_mint(&self, mint: &str, decimals: u8, program: &str) {
+        // Base mint layout: authority option, supply, decimals at 44, is_initialized at 45
+        let mut data = vec![0u8; 82];
+        data[44] = decimals;
+        data[45] = 1;
+        self.set_account(
+            mint.parse().unwrap(),
+            FakeAccount {
+                owner: program.parse().unwrap(),
+                lamports: 1_461_600,
+                data,
+            },
+        );
+    }
+
+    pub fn set_account(&self, address: Pubkey, account: FakeAccount) {
+        self.state.accounts.lock().unwrap().insert(address, account);
+    }
+
+    /// Store an initialized durable nonce account and return its current nonce hash.
+    pub fn set_nonce_account(&self, address: Pubkey, authority: Pubkey, blockhash: Hash) -> Hash {
+        let durable_nonce = DurableNonce::from_blockhash(&blockhash);
+        let data = NonceData::new(authority, durable_nonce, 5000);
+        let versions = Versions::new(NonceState::Initialized(data));
+        self.set_account(
+            address,
+            FakeAccount {
+                owner: system_program::id(),
+                lamports: 1_447_680,
+                data: bincode::serialize(&versions).unwrap(),
+            },
+        );
+        *durable_nonce.as_hash()
+    }
+
+    pub fn set_prioritization_fees(&self, fees: &[u64]) {
+        *self.state.prioritization_fees.lock().unwrap() = fees.to_vec();
+    }
+
+    pub fn prioritization_fee_requests(&self) -> Vec<Vec<String>> {
+        self.state.prioritization_fee_requests.lock().unwrap().clone()
+    }
+
+    pub fn sent(&self) -> Vec<VersionedTransaction> {
+        self.state.sent.lock().unwrap().clone()
+    }
+
+    /// Report every sent signature as confirmed from now on (`send_and_confirm_transaction`).
+    pub fn confirm_sent(&self) {
+        self.state.confirm_sent.store(true, Ordering::SeqCst);
+    }
+
+    /// Answer every `sendTransaction` with an error (nothing is recorded as sent).
+    pub fn reject_sends(&self) {
+        self.state.reject_sends.store(true, Ordering::SeqCst);
+    }
+
+    pub fn set_send_delay(&self, delay: Duration) {
+        self.state.send_delay_ms.store(delay.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    pub fn set_block_height(&self, height: u64) {
+        self.state.block_height.store(height, Ordering::SeqCst);
+    }
+
+    pub fn calls(&self, method: &str) -> usize {
+        self.state.calls.lock().unwrap().iter().filter(|m| *m == method).count()
+    }
+}
+
+fn account_json(account: Option<&FakeAccount>) -> Value {
+    match account {
+        Some(account) => json!({
+            "data": [BASE64.encode(&account.data), "base64"],
+            "executable": false,
+            "lamports": account.lamports,
+            "owner": account.owner.to_string(),
+            "rentEpoch": 0,
+            "space": account.data.len()
+        }),
+        None => Value::Null,
+    }
+}
+
+fn lookup(state: &RpcState, key: &Value) -> Value {
+    let accounts = state.accounts.lock().unwrap();
+    let account = key
+        .as_str()
+        .and_then(|k| k.parse::<Pubkey>().ok())
+        .and_then(|k| accounts.get(&k));
+    account_json(account)
+}
+
+async fn rpc(State(state): State<Arc<RpcState>>, Json(request): Json<Value>) -> Json<Value> {
+    let id = request["id"].clone();
+    let method = request["method"].as_str().unwrap_or_default().to_string();
+    let params = &request["params"];
+    state.calls.lock().unwrap().push(method.clone());
+
+    let context = json!({ "slot": 1 });
+    let result = match method.as_str() {
+        "getVersion" => json!({ "solana-core": "2.1.0", "feature-set": 1 }),
+        "getAccountInfo" => json!({ "context": context, "value": lookup(&state, &params[0]) }),
+        "getMultipleAccounts" => {
+            let keys = params[0].as_array().cloned().unwrap_or_default();
+            let values: Vec<Value> = keys.iter().map(|k| lookup(&state, k)).collect();
+            json!({ "context": context, "value": values })
+        }
+        "getSignatureStatuses" => {
+            let signatures = params[0].as_array().cloned().unwrap_or_default();
+            let confirm = state.confirm_sent.load(Ordering::SeqCst);
+            let sent: Vec<String> = state.sent.lock().unwrap().iter().map(|tx| tx.signatures[0].to_string()).collect();
+            let statuses: Vec<Value> = signatures
+                .iter()
+                .map(|sig| match sig.as_str() {
+                    Some(sig) if confirm && sent.iter().any(|s| s == sig) => json!({
+                        "slot": 1,
+                        "confirmations": 1,
+                        "err": null,
+                        "status": { "Ok": null },
+                        "confirmationStatus": "confirmed"
+                    }),
+                    _ => Value::Null,
+                })
+                .collect();
+            json!({ "context": context, "value": statuses })
+        }
+        "getLatestBlockhash" => json!({
+            "context": context,
+            "value": { "blockhash": Hash::new_from_array([7; 32]).to_string(), "lastValidBlockHeight": 150 }
+        }),
+        "getBlockHeight" => json!(state.block_height.load(Ordering::SeqCst)),
+        "isBlockhashValid" => json!({ "context": context, "value": true }),
+        "getMinimumBalanceForRentExemption" => json!(1_447_680),
+        "getRecentPrioritizationFees" => {
+            let accounts = params[0].as_array().cloned().unwrap_or_default();
+            let accounts = accounts.iter().filter_map(|a| a.as_str().map(String::from)).collect();
+            state.prioritization_fee_requests.lock().unwrap().push(accounts);
+            let fees = state.prioritization_fees.lock().unwrap().clone();
+            let fees: Vec<Value> = fees
+                .iter()
+                .enumerate()
+                .map(|(slot, fee)| json!({ "slot": slot + 1, "prioritizationFee": fee }))
+                .collect();
+            json!(fees)
+        }
+        "sendTransaction" => {
+            let delay = state.send_delay_ms.load(Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            if state.reject_sends.load(Ordering::SeqCst) {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "error": { "code": -32002, "message": "Transaction rejected by mock" },
+                    "id": id
+                }));
+            }
+            let encoded = params[0].as_str().unwrap_or_default();
+            let bytes = match params[1]["encoding"].as_str() {
+                Some("base58") => bs58::decode(encoded).into_vec().unwrap(),
+                _ => BASE64.decode(encoded).unwrap(),
+            };
+            let tx: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+            let signature = tx.signatures[0].to_string();
+            state.sent.lock().unwrap().push(tx);
+            json!(signature)
+        }
+        "simulateTransaction" => {
+            let count = params[1]["accounts"]["addresses"].as_array().map(|a| a.len()).unwrap_or(0);
+            json!({
+                "context": context,
+                "value": {
+                    "err": null,
+                    "logs": ["Program log: mock simulation"],
+                    "accounts": vec![Value::Null; count],
+                    "unitsConsumed": 120000
+                }
+            })
+        }
+        _ => {
+            return Json(json!({
+                "jsonrpc": "2.0",
+                "error": { "code": -32601, "message": format!("Method not found: {}", method) },
+                "id": id
+            }))
+        }
+    };
+
+    Json(json!({ "jsonrpc": "2.0", "result": result, "id": id }))
+}
+
+// -----------------------------------------------------------------------------
+// Jito block engine
+// -----------------------------------------------------------------------------
+
+#[derive(Default)]
+pub struct BlockEngineState {
+    /// Transactions of every accepted `sendBundle`, in arrival order.
+    pub bundles: Mutex<Vec<Vec<VersionedTransaction>>>,
+    pub calls: Mutex<Vec<String>>,
+    /// `x-jito-auth` header of every request, in arrival order.
+    pub auth_headers: Mutex<Vec<Option<String>>>,
+    /// Returned by `getInflightBundleStatuses` for known bundles ("Pending" if unset).
+    pub status: Mutex<Option<String>>,
+    /// Fail every `sendBundle`, see [`MockBlockEngine::reject_bundles`].
+    pub reject_bundles: AtomicBool,
+}
+
+/// Block engine stand-in implementing `sendBundle` and `getInflightBundleStatuses`.
+/// A bundle's ID is the signature of its first transaction.
+pub struct MockBlockEngine {
+    pub url: String,
+    pub state: Arc<BlockEngineState>,
+}
+
+impl MockBlockEngine {
+    pub async fn start() -> Self {
+        let state = Arc::new(BlockEngineState::default());
+        let router = Router::new()
+            .route("/api/v1/bundles", post(block_engine))
+            .route("/api/v1/getInflightBundleStatuses", post(block_engine))
+            .with_state(state.clone());
+        let addr = serve(router).await;
+        Self {
+            url: format!("http://{}", addr),
+            state,
+        }
+    }
+
+    pub fn bundles(&self) -> Vec<Vec<VersionedTransaction>> {
+        self.state.bundles.lock().unwrap().clone()
+    }
+
+    /// Report every known bundle as `status` ("Pending", "Landed", "Failed").
+    pub fn set_status(&self, status: &str) {
+        *self.state.status.lock().unwrap() = Some(status.to_string());
+    }
+
+    /// Answer every `sendBundle` with an error (nothing is recorded).
+    pub fn reject_bundles(&self) {
+        self.state.reject_bundles.store(true, Ordering::SeqCst);
+    }
+
+    pub fn auth_headers(&self) -> Vec<Option<String>> {
+        self.state.auth_headers.lock().unwrap().clone()
+    }
+
+    pub fn calls(&self, method: &str) -> usize {
+        self.state.calls.lock().unwrap().iter().filter(|m| *m == method).count()
+    }
+}
+
+async fn block_engine(
+    State(state): State<Arc<BlockEngineState>>,
+    headers: HeaderMap,
+    Json(request): Json<Value>,
+) -> Json<Value> {
+    let id = request["id"].clone();
+    let method = request["method"].as_str().unwrap_or_default().to_string();
+    let params = &request["params"];
+    state.calls.lock().unwrap().push(method.clone());
+    let auth = headers.get("x-jito-auth").and_then(|v| v.to_str().ok()).map(String::from);
+    state.auth_headers.lock().unwrap().push(auth);
+
+    let error = |message: &str| {
+        Json(json!({ "jsonrpc": "2.0", "error": { "code": -32000, "message": message }, "id": id }))
+    };
+    let result = match method.as_str() {
+        "sendBundle" => {
+            if state.reject_bundles.load(Ordering::SeqCst) {
+                return error("bundle rejected by mock");
+            }
+            let transactions: Vec<VersionedTransaction> = params[0]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+                .iter()
+                .map(|encoded| {
+                    let bytes = bs58::decode(encoded.as_str().unwrap_or_default()).into_vec().unwrap();
+                    bincode::deserialize(&bytes).unwrap()
+                })
+                .collect();
+            let Some(first) = transactions.first() else {
+                return error("empty bundle");
+            };
+            let bundle_id = first.signatures[0].to_string();
+            state.bundles.lock().unwrap().push(transactions);
+            json!(bundle_id)
+        }
+        "getInflightBundleStatuses" => {
+            let status = state.status.lock().unwrap().clone().unwrap_or_else(|| "Pending".to_string());
+            let known: Vec<String> = state.bundles.lock().unwrap().iter().map(|b| b[0].signatures[0].to_string()).collect();
+            let ids = params[0].as_array().cloned().unwrap_or_default();
+            let statuses: Vec<Value> = ids
+                .iter()
+                .map(|id| {
+                    let is_known = id.as_str().is_some_and(|id| known.iter().any(|k| k == id));
+                    let bundle_status = if is_known { status.as_str() } else { "Invalid" };
+                    let landed_slot = if bundle_status == "Landed" { json!(1) } else { Value::Null };
+                    json!({ "bundle_id": id, "status": bundle_status, "landed_slot": landed_slot })
+                })
+                .collect();
+            json!({ "context": { "slot": 1 }, "value": statuses })
+        }
+        _ => return error(&format!("Method not found: {}", method)),
+    };
+
+    Json(json!({ "jsonrpc": "2.0", "result": result, "id": id }))
+}
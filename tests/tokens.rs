@@ -0,0 +1,186 @@
+//! Token registry: decimals, token program and transfer fee read from the mint account,
+//! symbol overrides and the on-disk cache.
+
+mod support;
+
+use std::sync::Arc;
+
+use jupiter_arbitrage_bot_offchain::*;
+use jupiter_swap_api_client::quote::QuoteResponse;
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use spl_associated_token_account::{get_associated_token_address, get_associated_token_address_with_program_id};
+use support::{FakeAccount, MockRpc, TOKEN_2022_PROGRAM, TOKEN_PROGRAM, USDC, WSOL, quote_json};
+
+fn registry(rpc_url: &str, cache_path: &str, symbols: &[(&str, &str)]) -> TokenRegistry {
+    let config = TokensConfig {
+        cache_path: cache_path.to_string(),
+        symbols: symbols.iter().map(|(m, s)| (m.to_string(), s.to_string())).collect(),
+    };
+    TokenRegistry::new(&config, Arc::new(RpcClient::new(rpc_url.to_string())))
+}
+
+/// Token-2022 mint account carrying a `TransferFeeConfig` extension with the same fee in
+/// both schedules.
+fn transfer_fee_mint(decimals: u8, basis_points: u16, maximum_fee: u64) -> FakeAccount {
+    let mut data = vec![0u8; 166];
+    data[44] = decimals;
+    data[45] = 1;
+    data[165] = 1;
+    // TLV header: type 1 (TransferFeeConfig), length 108
+    data.extend_from_slice(&1u16.to_le_bytes());
+    data.extend_from_slice(&108u16.to_le_bytes());
+    // Config and withdraw authorities, withheld amount
+    data.extend_from_slice(&[0u8; 72]);
+    for _ in 0..2 {
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&maximum_fee.to_le_bytes());
+        data.extend_from_slice(&basis_points.to_le_bytes());
+    }
+    FakeAccount {
+        owner: TOKEN_2022_PROGRAM.parse().unwrap(),
+        lamports: 2_000_000,
+        data,
+    }
+}
+
+fn quote(input: &str, output: &str, in_amount: u64, out_amount: u64) -> QuoteResponse {
+    serde_json::from_value(quote_json(input, output, in_amount, out_amount)).unwrap()
+}
+
+#[tokio::test]
+async fn decimals_and_program_come_from_the_mint_account() {
+    let rpc = MockRpc::start().await;
+    let legacy = Pubkey::new_unique().to_string();
+    let token_2022 = Pubkey::new_unique().to_string();
+    rpc.set_mint(&legacy, 8, TOKEN_PROGRAM);
+    // Token-2022 mint with extensions: padded to the account size, type byte 1 at 165
+    let mut data = vec![0u8; 200];
+    data[44] = 5;
+    data[45] = 1;
+    data[165] = 1;
+    rpc.set_account(
+        token_2022.parse().unwrap(),
+        FakeAccount {
+            owner: TOKEN_2022_PROGRAM.parse().unwrap(),
+            lamports: 1_000_000,
+            data,
+        },
+    );
+    let tokens = registry(&rpc.url, "", &[]);
+
+    let resolved = tokens.resolve_all(&[legacy.clone(), token_2022.clone(), WSOL.to_string()]).await.unwrap();
+    let decimals: Vec<u8> = resolved.iter().map(|t| t.decimals).collect();
+    assert_eq!(decimals, vec![8, 5, 9]);
+    assert_eq!(resolved[0].program_id, TOKEN_PROGRAM_ID);
+    assert_eq!(resolved[1].program_id, TOKEN_2022_PROGRAM_ID);
+    assert_eq!(resolved[2].symbol, "WSOL");
+    // Unknown mints are shown abbreviated
+    assert_eq!(resolved[0].symbol, format!("{}..{}", &legacy[..4], &legacy[legacy.len() - 4..]));
+
+    // Cached: no second read
+    let reads = rpc.calls("getMultipleAccounts");
+    tokens.resolve(&legacy).await.unwrap();
+    assert_eq!(rpc.calls("getMultipleAccounts"), reads);
+}
+
+#[tokio::test]
+async fn accounts_that_are_not_mints_are_rejected() {
+    let rpc = MockRpc::start().await;
+    let tokens = registry(&rpc.url, "", &[]);
+
+    let missing = Pubkey::new_unique().to_string();
+    let err = tokens.resolve(&missing).await.unwrap_err();
+    assert!(err.to_string().contains("not found"), "{}", err);
+
+    let system_owned = Pubkey::new_unique();
+    rpc.set_account(
+        system_owned,
+        FakeAccount {
+            owner: solana_sdk::system_program::id(),
+            lamports: 1,
+            data: vec![0; 82],
+        },
+    );
+    let err = tokens.resolve(&system_owned.to_string()).await.unwrap_err();
+    assert!(err.to_string().contains("not a token mint"), "{}", err);
+    assert!(tokens.get(&system_owned.to_string()).is_none());
+}
+
+#[tokio::test]
+async fn symbol_overrides_and_lookup_by_symbol() {
+    let rpc = MockRpc::start().await;
+    let mint = Pubkey::new_unique().to_string();
+    rpc.set_mint(&mint, 6, TOKEN_PROGRAM);
+    let tokens = registry(&rpc.url, "", &[(&mint, "MYTK"), (USDC, "USD Coin")]);
+
+    assert_eq!(tokens.resolve(&mint).await.unwrap().symbol, "MYTK");
+    assert_eq!(tokens.symbol(USDC), "USD Coin");
+    assert_eq!(tokens.find_mint("mytk"), Some(mint.clone()));
+    assert_eq!(tokens.find_mint("wsol"), Some(WSOL.to_string()));
+    assert_eq!(tokens.find_mint(&mint), Some(mint));
+    assert_eq!(tokens.find_mint("NOPE"), None);
+}
+
+#[tokio::test]
+async fn resolved_mints_survive_a_restart_through_the_cache() {
+    let rpc = MockRpc::start().await;
+    let mint = Pubkey::new_unique().to_string();
+    rpc.set_mint(&mint, 7, TOKEN_PROGRAM);
+    let path = std::env::temp_dir().join(format!("token-cache-{}.json", Pubkey::new_unique()));
+    let path = path.to_str().unwrap();
+
+    registry(&rpc.url, path, &[]).resolve(&mint).await.unwrap();
+
+    // No RPC behind this one: everything must come from the file
+    let restarted = registry("http://127.0.0.1:1", path, &[]);
+    let token = restarted.get(&mint).unwrap();
+    let _ = std::fs::remove_file(path);
+    assert_eq!((token.decimals, token.program_id), (7, TOKEN_PROGRAM_ID));
+}
+
+#[tokio::test]
+async fn transfer_fee_extension_and_token_2022_ata() {
+    let rpc = MockRpc::start().await;
+    let mint = Pubkey::new_unique();
+    rpc.set_account(mint, transfer_fee_mint(6, 100, 5_000));
+    let tokens = registry(&rpc.url, "", &[]);
+
+    let token = tokens.resolve(&mint.to_string()).await.unwrap();
+    assert!(token.is_token_2022());
+    assert_eq!(token.decimals, 6);
+    assert_eq!(token.transfer_fee_on(10_001), 101); // 1%, rounded up
+    assert_eq!(token.transfer_fee_on(1_000_000), 5_000); // capped
+
+    let owner = Pubkey::new_unique();
+    let ata = token.associated_token_address(&owner);
+    assert_eq!(ata, get_associated_token_address_with_program_id(&owner, &mint, &TOKEN_2022_PROGRAM_ID));
+    assert_ne!(ata, get_associated_token_address(&owner, &mint));
+
+    // Legacy mints keep the classic derivation
+    let usdc = tokens.resolve(USDC).await.unwrap();
+    assert_eq!(usdc.transfer_fee, None);
+    assert_eq!(usdc.associated_token_address(&owner), get_associated_token_address(&owner, &USDC.parse().unwrap()));
+}
+
+#[tokio::test]
+async fn transfer_fees_reduce_the_cycle_output() {
+    let rpc = MockRpc::start().await;
+    let fee_mint = Pubkey::new_unique();
+    rpc.set_account(fee_mint, transfer_fee_mint(6, 100, u64::MAX));
+    let tokens = registry(&rpc.url, "", &[]);
+    let fee_mint = fee_mint.to_string();
+    tokens.resolve_all(&[USDC.to_string(), fee_mint.clone()]).await.unwrap();
+
+    // Quoted at +2%, but 1% is withheld when the pool pays out and again when it is paid
+    let legs = [
+        quote(USDC, &fee_mint, 1_000_000, 1_000_000),
+        quote(&fee_mint, USDC, 1_000_000, 1_020_000),
+    ];
+    // 1_000_000 - 10_000 reach the wallet, 990_000 - 9_900 reach the pool
+    assert_eq!(amount_after_transfer_fees(&tokens, &legs), 1_020_000 * 980_100 / 1_000_000);
+
+    // Fee-free legs are passed through unchanged
+    let plain = [quote(USDC, WSOL, 1_000_000, 5_000_000), quote(WSOL, USDC, 5_000_000, 1_010_000)];
+    assert_eq!(amount_after_transfer_fees(&tokens, &plain), 1_010_000);
+}
@@ -0,0 +1,77 @@
+//! Trade-size search: grid edge cases and golden-section convergence.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use jupiter_arbitrage_bot_offchain::*;
+
+/// Concave profit curve peaking at `peak`: fees dominate below, price impact above.
+fn profit_at(amount: u64, peak: u64) -> i64 {
+    let x = (amount as f64 / peak as f64).ln();
+    (1_000_000.0 * (1.0 - x * x)) as i64
+}
+
+#[test]
+fn single_grid_step_quotes_the_lower_bound() {
+    assert_eq!(amount_grid(0.1, 50.0, 1, 9), vec![100_000_000]);
+    assert!(amount_grid(0.1, 50.0, 0, 9).is_empty());
+
+    let grid = amount_grid(1.0, 100.0, 3, 6);
+    assert_eq!(grid, vec![1_000_000, 10_000_000, 100_000_000]);
+}
+
+#[test]
+fn the_quote_budget_gives_each_searched_route_a_coarse_pass() {
+    assert_eq!(split_quote_budget(12, 2), (2, 6));
+    // 29 targets on 12 quotes: the first four get three each
+    assert_eq!(split_quote_budget(12, 29), (4, 3));
+    assert_eq!(split_quote_budget(2, 5), (1, 2));
+    assert_eq!(split_quote_budget(0, 5), (0, 0));
+    assert_eq!(split_quote_budget(12, 0), (0, 0));
+}
+
+#[tokio::test]
+async fn golden_section_converges_on_the_peak_within_budget() {
+    let peak = 7_300_000_000u64;
+    let calls = AtomicUsize::new(0);
+
+    let results = golden_section_search(100_000_000, 50_000_000_000, 4, 16, 0.01, |amount| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        async move { Some((profit_at(amount, peak), amount)) }
+    })
+    .await;
+
+    assert!(calls.load(Ordering::SeqCst) <= 16);
+    assert_eq!(results.len(), calls.load(Ordering::SeqCst));
+
+    let best = results.iter().copied().max_by_key(|&a| profit_at(a, peak)).unwrap();
+    let error = (best as f64 / peak as f64 - 1.0).abs();
+    assert!(error < 0.05, "best {} is {:.2}% from peak {}", best, error * 100.0, peak);
+}
+
+#[tokio::test]
+async fn golden_section_respects_a_tight_budget() {
+    let calls = AtomicUsize::new(0);
+
+    let results = golden_section_search(1_000, 1_000_000, 3, 4, 0.0001, |amount| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        async move { Some((profit_at(amount, 20_000), amount)) }
+    })
+    .await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+    assert_eq!(results.len(), 4);
+}
+
+#[tokio::test]
+async fn golden_section_skips_refinement_when_every_quote_fails() {
+    let calls = AtomicUsize::new(0);
+
+    let results: Vec<u64> = golden_section_search(1_000, 1_000_000, 5, 20, 0.01, |_| {
+        calls.fetch_add(1, Ordering::SeqCst);
+        async move { None }
+    })
+    .await;
+
+    assert!(results.is_empty());
+    assert_eq!(calls.load(Ordering::SeqCst), 5);
+}